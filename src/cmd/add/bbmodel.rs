@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use tempfile::tempdir;
+
+use crate::{
+    cmd::{
+        Run,
+        add::{ContentOptions, model3d::Model3D},
+    },
+    pipeline::{bbmodel::BbModel, image_validator::ContentReport},
+    utils::json::write_json,
+    validation::should_snake_case,
+};
+
+/// 🧊 Blockbench プロジェクト（.bbmodel）から3Dモデルを追加
+///
+/// プロジェクトに埋め込まれたテクスチャを取り出し、Java版のモデルに変換してから
+/// 3Dモデルと同じ手順で追加します。
+#[derive(Debug, clap::Parser)]
+#[command(
+    about = "Blockbench プロジェクト（.bbmodel）から3Dモデルを追加",
+    long_about = "Blockbench のプロジェクトファイル（.bbmodel）から3Dモデルを作成します。\n\n\
+                  要素・グループ・display を Java版のモデルJSONに変換し、\n\
+                  埋め込まれたテクスチャをテクスチャ変数ごとのレイヤー画像として追加します。\n\
+                  particle に指定したテクスチャはそのまま particle に割り当てます。\n\n\
+                  テクスチャは Blockbench でプロジェクトに埋め込んで保存してください。\n\
+                  直方体以外の要素や、2軸以上で回転している要素は変換できません。"
+)]
+pub struct Bbmodel {
+    /// カンマ区切りのマテリアルリスト
+    ///
+    /// 例: diamond_axe,iron_sword,golden_pickaxe
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        required = true,
+        value_name = "MATERIALS",
+        help = "適用するマテリアル（カンマ区切り）"
+    )]
+    materials: Vec<String>,
+
+    /// カスタムモデルデータ名
+    ///
+    /// スネークケース（小文字 + アンダースコア）で指定してください。
+    #[arg(short, long, value_name = "NAME", help = "カスタムモデルデータ名")]
+    custom_model_data: String,
+
+    /// Blockbench のプロジェクトファイル
+    #[arg(
+        value_name = "BBMODEL",
+        help = "Blockbench プロジェクト（.bbmodel）のパス"
+    )]
+    bbmodel_file: PathBuf,
+
+    /// 数値カスタムモデルデータのID
+    ///
+    /// 指定すると、文字列のケースに加えて `minecraft:range_dispatch` の
+    /// エントリーとしても登録します。
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        help = "数値カスタムモデルデータのID（range_dispatch に登録）"
+    )]
+    index: Option<f64>,
+
+    /// テクスチャの内容チェックの設定
+    #[command(flatten)]
+    content: ContentOptions,
+}
+
+impl Bbmodel {
+    #[must_use]
+    pub fn new(
+        materials: Vec<String>,
+        custom_model_data: String,
+        bbmodel_file: PathBuf,
+        index: Option<f64>,
+    ) -> Self {
+        Self {
+            materials,
+            custom_model_data,
+            bbmodel_file,
+            index,
+            content: ContentOptions::default(),
+        }
+    }
+
+    /// テクスチャの内容チェックの設定
+    #[must_use]
+    pub fn with_content_options(mut self, content: ContentOptions) -> Self {
+        self.content = content;
+        self
+    }
+}
+
+impl Run for Bbmodel {
+    fn run(&self) -> anyhow::Result<()> {
+        self.run_with_report().map(|_| ())
+    }
+}
+
+impl Bbmodel {
+    /// モデルを追加し、テクスチャの内容チェックの結果を返す
+    ///
+    /// # Errors
+    ///
+    /// - カスタムモデルデータ名が不正な場合
+    /// - プロジェクトの変換、またはモデルの追加に失敗した場合
+    pub fn run_with_report(&self) -> anyhow::Result<ContentReport> {
+        // 一時ディレクトリ内のファイル名に使うため、書き込む前に検証する
+        should_snake_case(&self.custom_model_data)?;
+
+        let project = BbModel::load(&self.bbmodel_file)?;
+        let model = project.to_item_model().with_context(|| {
+            format!(
+                "Blockbench プロジェクトの変換に失敗: {}",
+                self.bbmodel_file.to_string_lossy()
+            )
+        })?;
+
+        let dir = tempdir()?;
+        let model_json_path = dir.path().join(format!("{}.json", self.custom_model_data));
+        write_json(&model_json_path, &model)?;
+        let layers = project.write_textures(dir.path())?;
+
+        println!(
+            "🧊 Blockbench プロジェクトを変換しました（要素: {}、テクスチャ: {}）",
+            model.elements().len(),
+            layers.iter().filter(|l| l.key != "particle").count()
+        );
+
+        Model3D::new(
+            self.materials.clone(),
+            self.custom_model_data.clone(),
+            model_json_path,
+            Vec::new(),
+            layers,
+            self.index,
+        )
+        .with_content_options(self.content.clone())
+        .run_with_report()
+    }
+}
@@ -1,11 +1,16 @@
+pub mod bbmodel;
 pub mod model;
 pub mod model3d;
+pub mod stateful;
+
+use crate::pipeline::image_validator::{ContentRules, RuleOverride};
 
 /// ✨ カスタムモデル追加コマンド
 #[derive(clap::Parser, Debug)]
 #[command(
     about = "カスタムモデルをリソースパックに追加します",
-    long_about = "2Dモデル（テクスチャ）または3Dモデル（JSON + テクスチャレイヤー）を\n\
+    long_about = "2Dモデル（テクスチャ）、3Dモデル（JSON + テクスチャレイヤー、または .bbmodel）、\n\
+                  または状態付きモデル（弓・クロスボウ・釣り竿・盾）を\n\
                   リソースパックに追加します。"
 )]
 pub struct Add {
@@ -29,6 +34,16 @@ pub enum Subcommand {
     /// モデルJSONファイルと複数のテクスチャレイヤーから3Dモデルを作成します。
     #[command(visible_alias = "3d")]
     Model3D(model3d::Model3D),
+
+    /// 🧊 Blockbench プロジェクト（.bbmodel）から3Dモデルを追加
+    ///
+    /// 埋め込まれたテクスチャを取り出し、Java版のモデルに変換して追加します。
+    Bbmodel(bbmodel::Bbmodel),
+
+    /// 🏹 状態付きモデル（弓・クロスボウ・釣り竿・盾）を追加
+    ///
+    /// ベースと状態ごとのテクスチャから、状態に応じて切り替わるモデルを作成します。
+    Stateful(stateful::Stateful),
 }
 
 impl super::Run for Subcommand {
@@ -36,6 +51,8 @@ impl super::Run for Subcommand {
         match self {
             Self::Model(cmd) => cmd.run(),
             Self::Model3D(cmd) => cmd.run(),
+            Self::Bbmodel(cmd) => cmd.run(),
+            Self::Stateful(cmd) => cmd.run(),
         }
     }
 }
@@ -45,3 +62,71 @@ impl super::Run for Add {
         self.subcommand.run()
     }
 }
+
+/// テクスチャの内容チェックの設定
+///
+/// 各 add コマンドと Issue の処理で共通して使用します。
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ContentOptions {
+    /// 静止画の幅・高さの上限（ピクセル）
+    ///
+    /// 省略時は256pxです。--normalize で縮小するサイズの上限にもなります。
+    #[arg(
+        long,
+        value_name = "PIXELS",
+        help = "静止画の幅・高さの上限（省略時は256px）"
+    )]
+    pub max_static_size: Option<u32>,
+
+    /// 内容チェックの重大度（複数指定可能）
+    ///
+    /// `<チェック>=<error|warning|off>` の形式で指定します。
+    /// チェックは oversized, fully_transparent, semi_transparent, color_profile,
+    /// interlaced, lossy_source です。
+    /// 例: --content-rule semi_transparent=error --content-rule color_profile=off
+    #[arg(
+        long = "content-rule",
+        value_name = "CHECK=SEVERITY",
+        help = "内容チェックの重大度（例: semi_transparent=error, color_profile=off）"
+    )]
+    pub rule_overrides: Vec<RuleOverride>,
+}
+
+impl ContentOptions {
+    /// 既定のルールに指定した設定を反映する
+    #[must_use]
+    pub fn rules(&self) -> ContentRules {
+        let mut rules = ContentRules::default();
+        if let Some(max_static_size) = self.max_static_size {
+            rules.max_static_size = max_static_size;
+        }
+        for rule in &self.rule_overrides {
+            rules.set_severity(rule.check, rule.severity);
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::image_validator::Severity;
+
+    #[test]
+    fn test_content_options_rules() {
+        assert_eq!(ContentOptions::default().rules(), ContentRules::default());
+
+        let options = ContentOptions {
+            max_static_size: Some(512),
+            rule_overrides: vec![
+                "semi_transparent=error".parse().unwrap(),
+                "color_profile=off".parse().unwrap(),
+            ],
+        };
+        let rules = options.rules();
+        assert_eq!(rules.max_static_size, 512);
+        assert_eq!(rules.semi_transparent, Some(Severity::Error));
+        assert_eq!(rules.color_profile, None);
+        assert_eq!(rules.oversized, ContentRules::default().oversized);
+    }
+}
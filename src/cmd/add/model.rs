@@ -1,12 +1,22 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
 use crate::{
-    cmd::Run,
-    pipeline::image_validator::ImageValidator,
-    schema::animation::{AnimationData, AnimationInfo},
-    types::ItemModelParent,
+    cmd::{Run, add::ContentOptions},
+    pipeline::{
+        frame_stitcher::StitchedAnimation,
+        image_converter::{SourceImage, convert_to_png},
+        image_normalizer::{NormalizeChange, Normalized},
+        image_validator::{
+            ContentCheck, ContentIssue, ContentReport, ImageValidator, TextureUsage,
+        },
+    },
+    schema::{
+        animation::{AnimationData, AnimationFrame, AnimationInfo},
+        items::ItemModelNode,
+    },
+    types::{ItemModelParent, Tint},
     utils::add as helpers,
 };
 
@@ -16,9 +26,18 @@ use crate::{
 #[derive(Debug, clap::Parser)]
 #[command(
     about = "2Dモデル（テクスチャ）を追加",
-    long_about = "PNG画像からアイテムモデルを作成し、指定したマテリアルに適用します。\n\n\
+    long_about = "PNG画像からアイテムモデルを作成し、指定したマテリアルに適用します。\n\
+                  WebP・GIF（最初のフレーム）・BMP・JPEG の画像はPNGに変換して追加し、\n\
+                  JPEG などの非可逆圧縮の画像からの変換は警告を表示します。\n\n\
                   アニメーションテクスチャの場合は --frametime オプションで\n\
-                  フレームごとのtick数を指定できます。"
+                  フレームごとのtick数を指定できます。\n\
+                  --interpolate、--frame-order、--frame-width、--frame-height で\n\
+                  .mcmeta のその他の設定も指定できます。\n\n\
+                  --frames にGIF画像またはフレームごとのPNG画像を含むディレクトリを\n\
+                  指定すると、フレームを縦に並べたアニメーションテクスチャを作成します。\n\n\
+                  256pxを超える静止画と完全に透明な画像は追加できません。\n\
+                  --normalize を指定すると、静止画を正方形・2の累乗のサイズに自動調整します。\n\
+                  半透明のピクセル、sRGB以外のカラープロファイル、インターレースPNGは警告を表示します。"
 )]
 pub struct Model {
     /// カンマ区切りのマテリアルリスト
@@ -58,12 +77,70 @@ pub struct Model {
     )]
     frametime: Option<u32>,
 
+    /// フレーム間を補間する
+    ///
+    /// 指定するとアニメーションテクスチャとして扱います。
+    #[arg(long, help = "アニメーションのフレーム間を補間する")]
+    interpolate: bool,
+
+    /// 表示するフレームの順序（カンマ区切り）
+    ///
+    /// `<番号>` または `<番号>:<tick数>` で指定します。番号は画像の上から0始まりです。
+    /// 例: --frame-order 0,1,2:10,1
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "FRAMES",
+        help = "表示するフレームの順序（例: 0,1,2:10,1）"
+    )]
+    frame_order: Vec<AnimationFrame>,
+
+    /// フレームの幅（ピクセル）
+    ///
+    /// 省略時は画像の幅と高さのうち小さい方です。
+    #[arg(
+        long,
+        value_name = "PIXELS",
+        conflicts_with = "frames",
+        help = "アニメーションのフレームの幅"
+    )]
+    frame_width: Option<u32>,
+
+    /// フレームの高さ（ピクセル）
+    ///
+    /// 省略時は画像の幅と高さのうち小さい方です。
+    #[arg(
+        long,
+        value_name = "PIXELS",
+        conflicts_with = "frames",
+        help = "アニメーションのフレームの高さ"
+    )]
+    frame_height: Option<u32>,
+
     /// テクスチャ画像ファイルのパス
     ///
-    /// PNG形式の画像を指定してください。
+    /// PNG・WebP・GIF・BMP・JPEG に対応しています。PNG以外はPNGに変換し、
+    /// GIFの場合は最初のフレームを使用します。
     /// 画像サイズは2の累乗（16x16, 32x32, 64x64など）である必要があります。
-    #[arg(value_name = "IMAGE_FILE", help = "テクスチャ画像（PNG）のパス")]
-    path_to_image: PathBuf,
+    #[arg(
+        value_name = "IMAGE_FILE",
+        required_unless_present = "frames",
+        conflicts_with = "frames",
+        help = "テクスチャ画像（PNG・WebP・GIF・BMP・JPEG）のパス"
+    )]
+    path_to_image: Option<PathBuf>,
+
+    /// アニメーションのフレーム（GIF画像、またはフレームごとのPNG画像を含むディレクトリ）
+    ///
+    /// フレームを縦に並べて1枚のテクスチャにします。GIFの場合は各フレームの
+    /// 表示時間から frametime（表示時間が異なる場合はフレームごとのtick数）を求めます。
+    /// ディレクトリの場合はファイル名順（数字は数値として比較）に並べます。
+    #[arg(
+        long,
+        value_name = "DIR|GIF",
+        help = "アニメーションのフレーム（GIF画像またはPNG画像のディレクトリ）"
+    )]
+    frames: Option<PathBuf>,
 
     /// モデル親（2Dの描画スタイル）
     ///
@@ -78,87 +155,397 @@ pub struct Model {
         help = "モデル親（表示スタイル）"
     )]
     parent: ItemModelParent,
+
+    /// 数値カスタムモデルデータのID
+    ///
+    /// 指定すると、文字列のケースに加えて `minecraft:range_dispatch` の
+    /// エントリーとしても登録します。同じマテリアルで別のモデルが
+    /// 同じIDを使用している場合はエラーになります。
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        help = "数値カスタムモデルデータのID（range_dispatch に登録）"
+    )]
+    index: Option<f64>,
+
+    /// ティントソース（複数指定可能）
+    ///
+    /// 指定した順に layer0, layer1, ... の色付けに使われます。
+    /// 種類は constant, dye, firework, map_color, potion, team, custom_model_data です。
+    /// `=<色>` で既定色（`#RRGGBB`）を指定できます。constant は色が必須です。
+    /// 例: --tint dye=#A06540
+    #[arg(
+        long = "tint",
+        value_name = "SOURCE[=COLOR]",
+        help = "ティントソース（例: dye, potion=#385DC6, constant=#FF0000）"
+    )]
+    tints: Vec<Tint>,
+
+    /// 画像を正方形・2の累乗のサイズに自動調整する
+    ///
+    /// 透明な余白を追加して正方形にしてから、最も近い2の累乗のサイズ
+    /// （最大256px）にニアレストネイバーで拡大縮小します。静止画のみ対応しています。
+    #[arg(
+        long,
+        conflicts_with = "frames",
+        help = "画像を正方形・2の累乗のサイズに自動調整する（静止画のみ）"
+    )]
+    normalize: bool,
+
+    /// テクスチャの内容チェックの設定
+    #[command(flatten)]
+    content: ContentOptions,
+}
+
+/// 2Dモデルのテクスチャの入力元
+#[derive(Debug, Clone)]
+pub enum TextureSource {
+    /// 1枚の画像（PNG以外はPNGに変換）
+    Image(PathBuf),
+    /// GIF画像、またはフレームごとのPNG画像を含むディレクトリ
+    Frames(PathBuf),
 }
 
 impl Model {
     #[must_use]
-    pub const fn new(
+    pub fn new(
         materials: Vec<String>,
         custom_model_data: Option<String>,
-        frametime: Option<u32>,
-        path_to_image: PathBuf,
+        animation: Option<AnimationData>,
+        texture: TextureSource,
         parent: ItemModelParent,
+        index: Option<f64>,
+        tints: Vec<Tint>,
     ) -> Self {
+        let animation = animation.as_ref();
+        let (path_to_image, frames) = match texture {
+            TextureSource::Image(path) => (Some(path), None),
+            TextureSource::Frames(path) => (None, Some(path)),
+        };
         Self {
             materials,
             custom_model_data,
-            frametime,
+            frametime: animation.map(|animation| animation.frametime),
+            interpolate: animation.and_then(|animation| animation.interpolate) == Some(true),
+            frame_order: animation
+                .and_then(|animation| animation.frames.clone())
+                .unwrap_or_default(),
+            frame_width: animation.and_then(|animation| animation.width),
+            frame_height: animation.and_then(|animation| animation.height),
             path_to_image,
+            frames,
             parent,
+            index,
+            tints,
+            normalize: false,
+            content: ContentOptions::default(),
         }
     }
+
+    /// 画像を正方形・2の累乗のサイズに自動調整するか設定
+    #[must_use]
+    pub const fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// テクスチャの内容チェックの設定
+    #[must_use]
+    pub fn with_content_options(mut self, content: ContentOptions) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// 入力元の画像（またはディレクトリ）のパス
+    fn source_path(&self) -> anyhow::Result<&Path> {
+        self.path_to_image
+            .as_deref()
+            .or(self.frames.as_deref())
+            .context("テクスチャ画像または --frames を指定してください")
+    }
+
+    /// 指定されたオプションからアニメーション設定を作成
+    ///
+    /// `base` はフレームの結合で求めた設定で、オプションで指定した値が優先されます。
+    /// どちらもない場合は `None` です。
+    fn animation_data(&self, base: Option<AnimationData>) -> Option<AnimationData> {
+        let specified = self.frametime.is_some()
+            || self.interpolate
+            || !self.frame_order.is_empty()
+            || self.frame_width.is_some()
+            || self.frame_height.is_some();
+        if base.is_none() && !specified {
+            return None;
+        }
+
+        let mut animation = base.unwrap_or_else(|| AnimationData::with_frametime(1));
+        if let Some(frametime) = self.frametime {
+            // GIFから求めたフレームごとのtick数より明示した frametime を優先する
+            animation.frametime = frametime;
+            animation.frames = None;
+        }
+        if self.interpolate {
+            animation.interpolate = Some(true);
+        }
+        if !self.frame_order.is_empty() {
+            animation.frames = Some(self.frame_order.clone());
+        }
+        if self.frame_width.is_some() {
+            animation.width = self.frame_width;
+        }
+        if self.frame_height.is_some() {
+            animation.height = self.frame_height;
+        }
+        Some(animation)
+    }
 }
 
 impl Run for Model {
     fn run(&self) -> anyhow::Result<()> {
+        self.run_with_report().map(|_| ())
+    }
+}
+
+/// 2Dモデルの追加結果
+#[derive(Debug, Default)]
+pub struct ModelReport {
+    /// テクスチャの内容チェックの結果
+    pub content: ContentReport,
+    /// 画像の自動調整で行った変更
+    pub normalize_changes: Vec<NormalizeChange>,
+    /// 書き込んだアニメーション設定
+    pub animation: Option<AnimationData>,
+    /// 明示した frametime でGIFのフレームごとの表示時間を上書きしたか
+    pub gif_timing_overridden: bool,
+}
+
+impl Model {
+    /// モデルを追加し、テクスチャの内容チェックと自動調整の結果を返す
+    ///
+    /// 警告は追加を妨げないため、Issue への返信などに利用できます。
+    ///
+    /// # Errors
+    ///
+    /// 入力の検証、テクスチャの内容チェック、またはファイルの書き込みに失敗した場合
+    pub fn run_with_report(&self) -> anyhow::Result<ModelReport> {
         println!("\n📄 2Dモデル追加を開始します...\n");
 
         helpers::validate_materials(&self.materials)?;
+        helpers::validate_index(self.index)?;
 
-        if !self.path_to_image.exists() {
+        let source_path = self.source_path()?;
+        if !source_path.exists() {
             anyhow::bail!(
                 "❌ 画像ファイルが存在しません: {}",
-                self.path_to_image.to_string_lossy()
+                source_path.to_string_lossy()
             );
         }
 
         let custom_model_data =
-            helpers::infer_or_validate_name(&self.custom_model_data, &self.path_to_image)?;
+            helpers::infer_or_validate_name(&self.custom_model_data, source_path)?;
 
         println!("📋 カスタムモデルデータ名: {}", custom_model_data);
         println!("📦 適用マテリアル: {}", self.materials.join(", "));
         println!("🎨 モデル親: {}", self.parent.as_str());
+        if let Some(index) = self.index {
+            println!("🔢 数値ID: {}", index);
+        }
+        if !self.tints.is_empty() {
+            let tints: Vec<_> = self.tints.iter().map(Tint::to_string).collect();
+            println!("🎨 ティント: {}", tints.join(", "));
+        }
 
-        if let Some(ft) = self.frametime {
-            println!("🎬 アニメーション: frametime = {} tick", ft);
+        // フレームを結合したテクスチャは一時ファイルに書き出してから検証・コピーする
+        let stitched_file = match &self.frames {
+            Some(frames) => {
+                let from_gif = frames.is_file();
+                println!("🎞️  フレームを結合中: {}", frames.display());
+                let stitched = StitchedAnimation::load(frames)?;
+                println!(
+                    "  ✓ {} フレームを結合しました（{}x{}）",
+                    stitched.frame_count(),
+                    stitched.image.width(),
+                    stitched.image.height()
+                );
+                let file = tempfile::Builder::new()
+                    .suffix(".png")
+                    .tempfile()
+                    .context("一時ファイルの作成に失敗しました")?;
+                stitched
+                    .image
+                    .save_with_format(file.path(), image::ImageFormat::Png)
+                    .context("結合したテクスチャの書き込みに失敗しました")?;
+                Some((file, stitched.animation, from_gif))
+            }
+            None => None,
+        };
+        let (path_to_image, base_animation) = match &stitched_file {
+            Some((file, animation, _)) => (file.path(), Some(animation.clone())),
+            None => (source_path, None),
+        };
+        // GIFから求めた表示時間を明示した frametime で置き換える場合は警告する
+        let gif_timing_overridden = match (&stitched_file, self.frametime) {
+            (Some((_, animation, true)), Some(frametime)) => !animation.is_uniform(frametime),
+            _ => false,
+        };
+        if gif_timing_overridden {
+            println!("⚠️  指定した frametime がGIFのフレームごとの表示時間より優先されます");
+        }
+
+        let animation_info = self
+            .animation_data(base_animation)
+            .map(|animation| AnimationInfo { animation });
+        if let Some(animation_info) = &animation_info {
+            let animation = &animation_info.animation;
+            println!(
+                "🎬 アニメーション: frametime = {} tick",
+                animation.frametime
+            );
+            if animation.interpolate == Some(true) {
+                println!("🎬 フレーム補間: 有効");
+            }
+            if let Some(frames) = &animation.frames {
+                let frames: Vec<_> = frames.iter().map(AnimationFrame::to_string).collect();
+                println!("🎬 フレーム順序: {}", frames.join(", "));
+            }
+            if let Some(width) = animation.width {
+                println!("🎬 フレームの幅: {} px", width);
+            }
+            if let Some(height) = animation.height {
+                println!("🎬 フレームの高さ: {} px", height);
+            }
         }
         println!();
 
         helpers::ensure_not_exists_2d(&custom_model_data)?;
 
-        let animation_info = self.frametime.map(|frametime| AnimationInfo {
-            animation: AnimationData { frametime },
-        });
+        let rules = self.content.rules();
+
+        // PNG以外の静止画は、検証の前にRGBAのPNGに変換する
+        let mut source_issues = Vec::new();
+        let converted_file = match &stitched_file {
+            Some(_) => None,
+            None => {
+                let data = std::fs::read(path_to_image).with_context(|| {
+                    format!(
+                        "画像ファイルの読み込みに失敗しました: {}",
+                        path_to_image.display()
+                    )
+                })?;
+                let source = SourceImage::detect(&data)?;
+                if source.format == image::ImageFormat::Png {
+                    None
+                } else {
+                    let file = tempfile::Builder::new()
+                        .suffix(".png")
+                        .tempfile()
+                        .context("一時ファイルの作成に失敗しました")?;
+                    convert_to_png(path_to_image, file.path())?;
+                    println!("🔄 {} 画像を PNG に変換しました", source.label());
+                    if source.lossy
+                        && let Some(severity) = rules.lossy_source
+                    {
+                        source_issues.push(ContentIssue {
+                            path: path_to_image.to_path_buf(),
+                            check: ContentCheck::LossySource,
+                            severity,
+                            message: format!(
+                                "非可逆圧縮の {} 画像から変換しました（圧縮ノイズが残っている場合があります）",
+                                source.label()
+                            ),
+                        });
+                    }
+                    Some(file)
+                }
+            }
+        };
+        let path_to_image = converted_file
+            .as_ref()
+            .map_or(path_to_image, |file| file.path());
+
+        let mut normalize_changes = Vec::new();
+        let normalized_file = if self.normalize {
+            if animation_info.is_some() {
+                anyhow::bail!("--normalize は静止画のみ対応しています");
+            }
+            println!("📐 画像を自動調整中...");
+            let file = tempfile::Builder::new()
+                .suffix(".png")
+                .tempfile()
+                .context("一時ファイルの作成に失敗しました")?;
+            normalize_changes =
+                Normalized::convert_file(path_to_image, file.path(), rules.max_static_size)?;
+            if normalize_changes.is_empty() {
+                println!("  ✓ 調整は不要でした");
+            }
+            for change in &normalize_changes {
+                println!("  ✓ {change}");
+            }
+            Some(file)
+        } else {
+            None
+        };
+        let path_to_image = normalized_file
+            .as_ref()
+            .map_or(path_to_image, |file| file.path());
 
         println!("🔍 画像を検証中...");
-        ImageValidator::new_png(&self.path_to_image)?.should_model(animation_info.as_ref())?;
+        let validator = ImageValidator::new_png(path_to_image)?;
+        validator.should_model(animation_info.as_ref())?;
+        let usage = if animation_info.is_some() {
+            TextureUsage::Animated
+        } else {
+            TextureUsage::Item
+        };
+        let mut report = validator.check_content(&rules, usage)?;
+        report.issues.extend(source_issues);
+        let report = report.ensure()?;
+
+        let mut tx = helpers::Transaction::new();
 
         println!("📝 モデルファイルを作成中...");
-        helpers::write_new_item_model(self.parent, &custom_model_data)?;
+        helpers::write_new_item_model(&mut tx, self.parent, &custom_model_data)?;
 
-        if let Some(animation_info) = animation_info {
+        if let Some(animation_info) = &animation_info {
             println!("📝 アニメーションファイルを作成中...");
-            helpers::write_new_animation(&custom_model_data, &animation_info)?;
+            helpers::write_new_animation(&mut tx, &custom_model_data, animation_info)?;
         }
 
         println!("🖼️  テクスチャをコピー中...");
         let texture_path = crate::paths::Paths::texture_path(&custom_model_data);
-        std::fs::copy(&self.path_to_image, &texture_path).with_context(|| {
-            format!(
-                "テクスチャファイルのコピーに失敗: {} -> {}",
-                self.path_to_image.display(),
-                texture_path.display()
-            )
-        })?;
+        tx.copy_file(path_to_image, &texture_path)
+            .with_context(|| {
+                format!(
+                    "テクスチャファイルのコピーに失敗: {} -> {}",
+                    path_to_image.display(),
+                    texture_path.display()
+                )
+            })?;
 
         println!("⚙️  マテリアルに適用中...");
-        helpers::update_materials(&self.materials, &custom_model_data)?;
+        let mut model = ItemModelNode::item(&custom_model_data);
+        model.set_tints(self.tints.iter().map(|tint| tint.to_source()).collect());
+        helpers::update_materials(
+            &mut tx,
+            &self.materials,
+            &custom_model_data,
+            &model,
+            self.index,
+        )?;
+
+        println!("💾 変更を書き込み中...");
+        tx.commit()?;
 
         println!(
             "\n✅ 2Dモデル '{}' を正常に追加しました！\n",
             custom_model_data
         );
 
-        Ok(())
+        Ok(ModelReport {
+            content: report,
+            normalize_changes,
+            animation: animation_info.map(|info| info.animation),
+            gif_timing_overridden,
+        })
     }
 }
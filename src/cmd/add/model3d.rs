@@ -1,14 +1,18 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use anyhow::Context;
 
 use crate::{
-    cmd::Run,
+    cmd::{Run, add::ContentOptions},
     paths::Paths,
-    pipeline::image_validator::ImageValidator,
-    schema::models::ItemModel,
+    pipeline::image_validator::{ContentReport, ImageValidator, TextureUsage},
+    schema::{
+        items::ItemModelNode,
+        models::{ItemModel, Textures},
+    },
+    types::LayerTexture,
     utils::add as helpers,
-    utils::json::{merge_json, read_json, write_json},
+    utils::json::read_json,
     validation::should_snake_case,
 };
 
@@ -20,7 +24,13 @@ use crate::{
 #[command(
     about = "3Dモデルを追加",
     long_about = "モデルJSONファイルと複数のテクスチャレイヤーから3Dモデルを作成します。\n\n\
-                  モデルJSON内のテクスチャパスは自動的に更新されます。"
+                  モデルJSON内のテクスチャパスは自動的に更新されます。\n\n\
+                  位置引数の画像は数字のテクスチャ変数（#0, #1, ...）に順に割り当てます。\n\
+                  名前付きのテクスチャ変数は --layer blade=blade.png のように指定し、\n\
+                  --layer particle=blade のように別の変数の画像を共有できます。\n\
+                  particle を指定しない場合は最初のレイヤー画像を使用します。\n\n\
+                  追加前に、面のテクスチャ参照・UV（0〜16）・座標（-16〜32）・\n\
+                  回転角度（-45, -22.5, 0, 22.5, 45）・display の値を検証します。"
 )]
 pub struct Model3D {
     /// カンマ区切りのマテリアルリスト
@@ -51,39 +61,175 @@ pub struct Model3D {
     /// レイヤー画像ファイルのパス（複数指定可能）
     ///
     /// モデルで使用するテクスチャレイヤーをすべて指定します。
-    /// PNG形式で、モデルJSONで定義されたレイヤー数と一致する必要があります。
+    /// PNG形式で、モデルJSONで定義された数字のテクスチャ変数の数と一致する必要があります。
     #[arg(
-        required = true,
+        required_unless_present = "layers",
         value_name = "LAYER_IMAGES",
         help = "テクスチャレイヤー画像（PNG）のパス"
     )]
     layer_images: Vec<PathBuf>,
+
+    /// テクスチャ変数ごとのレイヤー画像（複数指定可能）
+    ///
+    /// 例: --layer blade=blade.png --layer particle=blade
+    #[arg(
+        short,
+        long = "layer",
+        value_name = "KEY=IMAGE_FILE",
+        help = "テクスチャ変数ごとの画像（KEY=画像ファイル または KEY=別の変数）"
+    )]
+    layers: Vec<LayerTexture>,
+
+    /// 数値カスタムモデルデータのID
+    ///
+    /// 指定すると、文字列のケースに加えて `minecraft:range_dispatch` の
+    /// エントリーとしても登録します。同じマテリアルで別のモデルが
+    /// 同じIDを使用している場合はエラーになります。
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        help = "数値カスタムモデルデータのID（range_dispatch に登録）"
+    )]
+    index: Option<f64>,
+
+    /// テクスチャの内容チェックの設定
+    #[command(flatten)]
+    content: ContentOptions,
 }
 
 impl Model3D {
     #[must_use]
-    pub const fn new(
+    pub fn new(
         materials: Vec<String>,
         custom_model_data: String,
         model_json_file: PathBuf,
         layer_images: Vec<PathBuf>,
+        layers: Vec<LayerTexture>,
+        index: Option<f64>,
     ) -> Self {
         Self {
             materials,
             custom_model_data,
             model_json_file,
             layer_images,
+            layers,
+            index,
+            content: ContentOptions::default(),
         }
     }
+
+    /// テクスチャの内容チェックの設定
+    #[must_use]
+    pub fn with_content_options(mut self, content: ContentOptions) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// テクスチャ変数とレイヤー画像の対応を決める
+    ///
+    /// # Returns
+    ///
+    /// (テクスチャ変数 → レイヤー名, レイヤー名 → 画像ファイル)
+    fn layer_mapping(
+        &self,
+        textures: &Textures,
+    ) -> anyhow::Result<(BTreeMap<String, String>, BTreeMap<String, PathBuf>)> {
+        let mut images = BTreeMap::new();
+        let mut aliases = BTreeMap::new();
+
+        if !self.layer_images.is_empty() {
+            let numeric_keys = textures.numeric_keys();
+            if numeric_keys.len() != self.layer_images.len() {
+                anyhow::bail!(
+                    "モデルのレイヤー数({})と指定された画像ファイル数({})が一致しません。",
+                    numeric_keys.len(),
+                    self.layer_images.len()
+                );
+            }
+            for (key, image) in numeric_keys.into_iter().zip(&self.layer_images) {
+                images.insert(key.to_string(), image.clone());
+            }
+        }
+
+        for layer in &self.layers {
+            should_snake_case(&layer.key)?;
+            if images.contains_key(&layer.key) || aliases.contains_key(&layer.key) {
+                anyhow::bail!(
+                    "テクスチャ変数 '{}' が重複して指定されています。",
+                    layer.key
+                );
+            }
+            let is_key = textures.get(&layer.source).is_some()
+                || self.layers.iter().any(|l| l.key == layer.source);
+            if is_key {
+                aliases.insert(layer.key.clone(), layer.source.clone());
+            } else {
+                images.insert(layer.key.clone(), PathBuf::from(&layer.source));
+            }
+        }
+
+        let first_layer = images
+            .keys()
+            .next()
+            .cloned()
+            .context("少なくとも1つのレイヤー画像ファイルを指定してください。")?;
+
+        let mut keys: Vec<String> = textures.iter().map(|(key, _)| key.to_string()).collect();
+        keys.extend(self.layers.iter().map(|l| l.key.clone()));
+        keys.sort();
+        keys.dedup();
+
+        let mut mapping = BTreeMap::new();
+        for key in keys {
+            let layer = if images.contains_key(&key) {
+                key.clone()
+            } else if let Some(source) = aliases.get(&key) {
+                // 別名の連鎖をたどって画像のあるレイヤーを探す
+                let mut target = source;
+                for _ in 0..aliases.len() {
+                    match aliases.get(target) {
+                        Some(next) => target = next,
+                        None => break,
+                    }
+                }
+                if !images.contains_key(target) {
+                    anyhow::bail!(
+                        "テクスチャ変数 '{key}' の参照先 '{source}' に画像が指定されていません。"
+                    );
+                }
+                target.clone()
+            } else if textures.get(&key).is_some_and(|v| v.starts_with('#')) {
+                // 他のテクスチャ変数への参照はそのまま残す
+                continue;
+            } else if key == "particle" {
+                first_layer.clone()
+            } else {
+                anyhow::bail!(
+                    "テクスチャ変数 '{key}' の画像が指定されていません（--layer {key}=<画像ファイル> で指定してください）。"
+                );
+            };
+            mapping.insert(key, layer);
+        }
+
+        Ok((mapping, images))
+    }
 }
 
 impl Run for Model3D {
     fn run(&self) -> anyhow::Result<()> {
-        helpers::validate_materials(&self.materials)?;
+        self.run_with_report().map(|_| ())
+    }
+}
 
-        if self.layer_images.is_empty() {
-            anyhow::bail!("少なくとも1つのレイヤー画像ファイルを指定してください。");
-        }
+impl Model3D {
+    /// モデルを追加し、レイヤー画像の内容チェックの結果を返す
+    ///
+    /// # Errors
+    ///
+    /// 入力の検証、レイヤー画像の内容チェック、またはファイルの書き込みに失敗した場合
+    pub fn run_with_report(&self) -> anyhow::Result<ContentReport> {
+        helpers::validate_materials(&self.materials)?;
+        helpers::validate_index(self.index)?;
 
         should_snake_case(&self.custom_model_data)?;
 
@@ -95,63 +241,58 @@ impl Run for Model3D {
             );
         }
 
-        let mut model_value =
-            read_json::<serde_json::Value>(&self.model_json_file).with_context(|| {
-                format!(
-                    "モデルJSONファイルの読み込みに失敗: {}",
-                    self.model_json_file.to_string_lossy()
-                )
-            })?;
-
-        let mut model =
-            serde_json::from_value::<ItemModel>(model_value.clone()).with_context(|| {
-                format!(
-                    "モデルJSONのパースに失敗: {}",
-                    self.model_json_file.to_string_lossy()
-                )
-            })?;
-
-        let layer_count = model.textures.overwrite(&self.custom_model_data);
-        if layer_count != self.layer_images.len() {
-            anyhow::bail!(
-                "モデルのレイヤー数({})と指定された画像ファイル数({})が一致しません。",
-                layer_count,
-                self.layer_images.len()
-            );
-        }
-
-        let model = serde_json::to_value(&model).with_context(|| {
+        let mut model = read_json::<ItemModel>(&self.model_json_file).with_context(|| {
             format!(
-                "モデルJSONのシリアライズに失敗: {}",
+                "モデルJSONのパースに失敗: {}",
                 self.model_json_file.to_string_lossy()
             )
         })?;
 
-        model_value.get_mut("textures").map(serde_json::Value::take);
-        merge_json(&mut model_value, &model);
-        write_json(&model_path, &model_value).with_context(|| {
+        model.validate().with_context(|| {
             format!(
-                "モデルファイルの書き込みに失敗: {}",
-                model_path.to_string_lossy()
+                "モデルJSONの検証に失敗: {}",
+                self.model_json_file.to_string_lossy()
             )
         })?;
 
-        for (i, layer_image) in self.layer_images.iter().enumerate() {
+        let (mapping, images) = self.layer_mapping(&model.textures)?;
+        for (key, layer) in &mapping {
+            model
+                .textures
+                .set_layer(key, &self.custom_model_data, layer);
+        }
+        // 差し替え後も面のテクスチャ参照が切れていないか再確認する
+        model
+            .validate()
+            .context("テクスチャ変数の差し替え後のモデルが不正です")?;
+
+        let rules = self.content.rules();
+        let mut report = ContentReport::default();
+        for layer_image in images.values() {
             if !layer_image.exists() {
                 anyhow::bail!(
                     "レイヤー画像ファイルが存在しません: {}",
                     layer_image.to_string_lossy()
                 );
             }
-            ImageValidator::new_png(layer_image)?;
-
-            let texture_path = Paths::texture_layer_path(&self.custom_model_data, i);
-            std::fs::create_dir_all(
-                texture_path
-                    .parent()
-                    .context("テクスチャパスの親ディレクトリの取得に失敗")?,
-            )?;
-            std::fs::copy(layer_image, &texture_path).with_context(|| {
+            report.extend(
+                ImageValidator::new_png(layer_image)?.check_content(&rules, TextureUsage::Layer)?,
+            );
+        }
+        let report = report.ensure()?;
+
+        let mut tx = helpers::Transaction::new();
+
+        tx.write_json(&model_path, &model).with_context(|| {
+            format!(
+                "モデルファイルの書き込みに失敗: {}",
+                model_path.to_string_lossy()
+            )
+        })?;
+
+        for (layer, layer_image) in &images {
+            let texture_path = Paths::texture_layer_path(&self.custom_model_data, layer);
+            tx.copy_file(layer_image, &texture_path).with_context(|| {
                 format!(
                     "レイヤー画像ファイルのコピーに失敗: {} -> {}",
                     layer_image.to_string_lossy(),
@@ -160,8 +301,15 @@ impl Run for Model3D {
             })?;
         }
 
-        helpers::update_materials(&self.materials, &self.custom_model_data)?;
+        helpers::update_materials(
+            &mut tx,
+            &self.materials,
+            &self.custom_model_data,
+            &ItemModelNode::item(&self.custom_model_data),
+            self.index,
+        )?;
+        tx.commit()?;
 
-        Ok(())
+        Ok(report)
     }
 }
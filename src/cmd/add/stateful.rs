@@ -0,0 +1,254 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::{
+    cmd::{Run, add::ContentOptions},
+    paths::Paths,
+    pipeline::image_validator::{ImageValidator, TextureUsage},
+    types::{ItemModelParent, StateTexture, StatefulKind},
+    utils::add as helpers,
+    validation::should_snake_case,
+};
+
+/// 🏹 状態付きモデル（弓・クロスボウ・釣り竿・盾）を追加
+///
+/// ベースのテクスチャと状態ごとのテクスチャからモデルを作成し、
+/// 状態に応じて切り替わるアイテム定義を指定したマテリアルに適用します。
+#[derive(Debug, clap::Parser)]
+#[command(
+    about = "状態付きモデル（弓・クロスボウ・釣り竿・盾）を追加",
+    long_about = "ベースのテクスチャと状態ごとのテクスチャからモデルを作成します。\n\n\
+                  アイテム定義には、使用中や引き絞りなどの状態に応じて\n\
+                  モデルを切り替える condition / range_dispatch が生成されます。\n\n\
+                  必要な状態:\n\
+                  - bow: pulling_0, pulling_1, pulling_2\n\
+                  - crossbow: pulling_0, pulling_1, pulling_2, arrow, firework\n\
+                  - fishing_rod: cast\n\
+                  - shield: blocking"
+)]
+pub struct Stateful {
+    /// カンマ区切りのマテリアルリスト
+    ///
+    /// 例: bow,crossbow
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        required = true,
+        value_name = "MATERIALS",
+        help = "適用するマテリアル（カンマ区切り）"
+    )]
+    materials: Vec<String>,
+
+    /// カスタムモデルデータ名
+    ///
+    /// スネークケース（小文字 + アンダースコア）で指定してください。
+    #[arg(
+        short,
+        long,
+        required = true,
+        value_name = "NAME",
+        help = "カスタムモデルデータ名"
+    )]
+    custom_model_data: String,
+
+    /// 状態付きモデルの種類
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_name = "KIND",
+        help = "状態付きモデルの種類"
+    )]
+    kind: StatefulKind,
+
+    /// ベースのテクスチャ画像ファイルのパス
+    ///
+    /// どの状態にも当てはまらない場合に表示されるテクスチャです。
+    #[arg(
+        value_name = "IMAGE_FILE",
+        help = "ベースのテクスチャ画像（PNG）のパス"
+    )]
+    path_to_image: PathBuf,
+
+    /// 状態ごとのテクスチャ（複数指定可能）
+    ///
+    /// `<状態>=<画像ファイル>` の形式で、種類ごとに必要なすべての状態を指定します。
+    /// 例: --state pulling_0=bow_0.png --state pulling_1=bow_1.png
+    #[arg(
+        short,
+        long = "state",
+        required = true,
+        value_name = "STATE=IMAGE_FILE",
+        help = "状態ごとのテクスチャ（例: pulling_0=bow_0.png）"
+    )]
+    states: Vec<StateTexture>,
+
+    /// モデル親（2Dの描画スタイル）
+    #[arg(
+        long,
+        value_enum,
+        default_value = "handheld",
+        value_name = "PARENT",
+        help = "モデル親（表示スタイル）"
+    )]
+    parent: ItemModelParent,
+
+    /// 数値カスタムモデルデータのID
+    ///
+    /// 指定すると、文字列のケースに加えて `minecraft:range_dispatch` の
+    /// エントリーとしても登録します。
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        help = "数値カスタムモデルデータのID（range_dispatch に登録）"
+    )]
+    index: Option<f64>,
+
+    /// テクスチャの内容チェックの設定
+    #[command(flatten)]
+    content: ContentOptions,
+}
+
+impl Stateful {
+    /// 指定された状態が種類に必要な状態と一致するか検証
+    fn validate_states(&self) -> anyhow::Result<()> {
+        let required = self.kind.states();
+
+        let mut seen = Vec::new();
+        for texture in &self.states {
+            if !required.contains(&texture.state.as_str()) {
+                anyhow::bail!(
+                    "'{}' は {} の状態ではありません（{}）",
+                    texture.state,
+                    self.kind.as_str(),
+                    required.join(", ")
+                );
+            }
+            if seen.contains(&texture.state.as_str()) {
+                anyhow::bail!("状態 '{}' が複数回指定されています", texture.state);
+            }
+            seen.push(texture.state.as_str());
+        }
+
+        let missing: Vec<_> = required
+            .iter()
+            .filter(|state| !seen.contains(state))
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "{} に必要な状態のテクスチャが不足しています: {}",
+                self.kind.as_str(),
+                missing.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Run for Stateful {
+    fn run(&self) -> anyhow::Result<()> {
+        println!("\n🏹 状態付きモデル追加を開始します...\n");
+
+        helpers::validate_materials(&self.materials)?;
+        helpers::validate_index(self.index)?;
+        should_snake_case(&self.custom_model_data)?;
+        self.validate_states()?;
+
+        let images = std::iter::once(&self.path_to_image)
+            .chain(self.states.iter().map(|texture| &texture.path));
+        for image in images {
+            if !image.exists() {
+                anyhow::bail!("❌ 画像ファイルが存在しません: {}", image.to_string_lossy());
+            }
+        }
+
+        let custom_model_data = &self.custom_model_data;
+        println!("📋 カスタムモデルデータ名: {}", custom_model_data);
+        println!("📦 適用マテリアル: {}", self.materials.join(", "));
+        println!("🏹 種類: {}", self.kind.as_str());
+        println!("🎨 モデル親: {}", self.parent.as_str());
+        if let Some(index) = self.index {
+            println!("🔢 数値ID: {}", index);
+        }
+        println!();
+
+        helpers::ensure_not_exists_2d(custom_model_data)?;
+        for dir in [
+            Paths::model_path_dir(custom_model_data),
+            Paths::texture_path_dir(custom_model_data),
+        ] {
+            if dir.exists() {
+                anyhow::bail!("ディレクトリが既に存在します: {}", dir.to_string_lossy());
+            }
+        }
+
+        println!("🔍 画像を検証中...");
+        let validator = ImageValidator::new_png(&self.path_to_image)?;
+        validator.should_model(None).with_context(|| {
+            format!(
+                "ベースのテクスチャが不正です: {}",
+                self.path_to_image.display()
+            )
+        })?;
+        let rules = self.content.rules();
+        let mut report = validator.check_content(&rules, TextureUsage::Item)?;
+        for texture in &self.states {
+            let validator = ImageValidator::new_png(&texture.path)?;
+            validator
+                .should_model(None)
+                .with_context(|| format!("状態 '{}' のテクスチャが不正です", texture.state))?;
+            report.extend(validator.check_content(&rules, TextureUsage::Item)?);
+        }
+        report.ensure()?;
+
+        let mut tx = helpers::Transaction::new();
+
+        println!("📝 モデルファイルを作成中...");
+        helpers::write_new_item_model(&mut tx, self.parent, custom_model_data)?;
+        for texture in &self.states {
+            let name = Paths::state_name(custom_model_data, &texture.state);
+            helpers::write_new_item_model(&mut tx, self.parent, &name)?;
+        }
+
+        println!("🖼️  テクスチャをコピー中...");
+        let copies = std::iter::once((&self.path_to_image, Paths::texture_path(custom_model_data)))
+            .chain(self.states.iter().map(|texture| {
+                (
+                    &texture.path,
+                    Paths::state_texture_path(custom_model_data, &texture.state),
+                )
+            }));
+        for (from, to) in copies {
+            tx.copy_file(from, &to).with_context(|| {
+                format!(
+                    "テクスチャファイルのコピーに失敗: {} -> {}",
+                    from.display(),
+                    to.display()
+                )
+            })?;
+        }
+
+        println!("⚙️  マテリアルに適用中...");
+        helpers::update_materials(
+            &mut tx,
+            &self.materials,
+            custom_model_data,
+            &self.kind.to_model(custom_model_data),
+            self.index,
+        )?;
+
+        println!("💾 変更を書き込み中...");
+        tx.commit()?;
+
+        println!(
+            "\n✅ 状態付きモデル '{}' を正常に追加しました！\n",
+            custom_model_data
+        );
+
+        Ok(())
+    }
+}
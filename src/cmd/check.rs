@@ -0,0 +1,581 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::Path,
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    paths::Paths,
+    pipeline::image_validator::ImageValidator,
+    schema::{animation::AnimationInfo, items::ItemResource},
+    utils::{
+        items::list_item_files, json::read_json, materials::MaterialMapping,
+        models::list_model_files,
+    },
+};
+
+/// 🔎 リソースパックを検証
+///
+/// `assets/` 全体を検査し、壊れた参照や不正なテクスチャ、
+/// 使われていないファイルを報告します。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "リソースパックを検証",
+    long_about = "assetsディレクトリ全体を検査し、問題を報告します。\n\n\
+                  - アイテム定義のケースが存在するモデルを指しているか\n\
+                  - モデルの textures が存在するPNGを指しているか\n\
+                  - テクスチャが2Dモデルの要件を満たしているか\n\
+                  - .mcmeta が正しく、画像のフレーム数と一致しているか\n\
+                  - 使われていないモデル・テクスチャがないか\n\
+                  - アイテム定義のファイル名が既知のマテリアルか\n\n\
+                  問題が1つでも見つかった場合は終了コード1で終了します。"
+)]
+pub struct Check {
+    /// 出力形式
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        value_name = "FORMAT",
+        help = "出力形式（text, json）"
+    )]
+    pub format: OutputFormat,
+}
+
+/// 検証結果の出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// 人が読むためのテキスト
+    Text,
+    /// CI向けのJSON
+    Json,
+}
+
+/// 問題の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// アイテム定義ファイルが読み込めない
+    InvalidItem,
+    /// アイテム定義のファイル名が既知のマテリアルではない
+    UnknownMaterial,
+    /// ケースが存在しないモデルを指している
+    MissingModel,
+    /// モデルファイルが読み込めない
+    InvalidModel,
+    /// モデルが存在しないテクスチャを指している
+    MissingTexture,
+    /// テクスチャが要件を満たしていない
+    InvalidTexture,
+    /// .mcmeta が不正、または画像と一致しない
+    InvalidAnimation,
+    /// どのアイテム定義からも使われていないモデル
+    OrphanModel,
+    /// どのモデルからも使われていないテクスチャ
+    OrphanTexture,
+}
+
+impl ProblemKind {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::InvalidItem => "不正なアイテム定義",
+            Self::UnknownMaterial => "未知のマテリアル",
+            Self::MissingModel => "モデルが存在しない",
+            Self::InvalidModel => "不正なモデル",
+            Self::MissingTexture => "テクスチャが存在しない",
+            Self::InvalidTexture => "不正なテクスチャ",
+            Self::InvalidAnimation => "不正なアニメーション",
+            Self::OrphanModel => "未使用のモデル",
+            Self::OrphanTexture => "未使用のテクスチャ",
+        }
+    }
+}
+
+/// 検出された問題
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    /// 問題の種類
+    pub kind: ProblemKind,
+    /// 問題のあるファイル
+    pub path: String,
+    /// 詳細
+    pub message: String,
+}
+
+/// 検証結果
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    /// 問題がなければ `true`
+    pub ok: bool,
+    /// 問題の件数
+    pub count: usize,
+    /// 問題一覧
+    pub problems: Vec<Problem>,
+}
+
+impl super::Run for Check {
+    fn run(&self) -> anyhow::Result<()> {
+        if self.format == OutputFormat::Text {
+            println!("\n🔎 リソースパックを検証中...\n");
+        }
+
+        let problems = Checker::default().run()?;
+        let report = CheckReport {
+            ok: problems.is_empty(),
+            count: problems.len(),
+            problems,
+        };
+
+        match self.format {
+            OutputFormat::Json => {
+                let json = serde_json::to_string_pretty(&report)
+                    .context("JSONのシリアライズに失敗しました")?;
+                println!("{json}");
+            }
+            OutputFormat::Text => {
+                for problem in &report.problems {
+                    println!(
+                        "  ✗ [{}] {}: {}",
+                        problem.kind.label(),
+                        problem.path,
+                        problem.message
+                    );
+                }
+                if report.ok {
+                    println!("✅ 問題は見つかりませんでした\n");
+                }
+            }
+        }
+
+        if !report.ok {
+            anyhow::bail!("{} 件の問題が見つかりました", report.count);
+        }
+
+        Ok(())
+    }
+}
+
+/// 検証処理の状態
+#[derive(Default)]
+struct Checker {
+    problems: Vec<Problem>,
+    /// マテリアルとバニラのテクスチャ
+    mapping: MaterialMapping,
+    /// 存在するモデル名
+    models: BTreeSet<String>,
+    /// アイテム定義から参照されているモデル名
+    referenced_models: HashSet<String>,
+    /// モデルから参照されているテクスチャ（`item/` 以下のパス）
+    referenced_textures: HashSet<String>,
+}
+
+impl Checker {
+    fn run(mut self) -> anyhow::Result<Vec<Problem>> {
+        self.mapping = MaterialMapping::load()?;
+        self.models = list_model_files()?
+            .into_iter()
+            .map(|model_file| model_file.name)
+            .collect();
+
+        self.check_items()?;
+        self.check_models()?;
+        self.check_textures()?;
+
+        for name in &self.models {
+            if !self.referenced_models.contains(name) {
+                let path = Paths::model_path(name);
+                self.problems.push(Problem {
+                    kind: ProblemKind::OrphanModel,
+                    path: path.display().to_string(),
+                    message: format!("モデル '{name}' はどのアイテム定義からも使われていません"),
+                });
+            }
+        }
+
+        Ok(self.problems)
+    }
+
+    fn report(&mut self, kind: ProblemKind, path: &Path, message: impl Into<String>) {
+        self.problems.push(Problem {
+            kind,
+            path: path.display().to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn check_items(&mut self) -> anyhow::Result<()> {
+        for item_file in list_item_files()? {
+            if !self.mapping.contains(&item_file.material) {
+                self.report(
+                    ProblemKind::UnknownMaterial,
+                    &item_file.path,
+                    format!("'{}' は既知のマテリアルではありません", item_file.material),
+                );
+            }
+
+            let resource = match read_json::<ItemResource>(&item_file.path) {
+                Ok(resource) => resource,
+                Err(err) => {
+                    self.report(
+                        ProblemKind::InvalidItem,
+                        &item_file.path,
+                        format!("{err:#}"),
+                    );
+                    continue;
+                }
+            };
+
+            for model in resource.model_references() {
+                // バニラのフォールバックモデルはリソースパックに含まれない
+                if self.mapping.is_fallback_model(model) {
+                    continue;
+                }
+                let Some(name) = custom_item_path(model) else {
+                    continue;
+                };
+                self.referenced_models.insert(name.to_string());
+                if !self.models.contains(name) {
+                    self.report(
+                        ProblemKind::MissingModel,
+                        &item_file.path,
+                        format!("参照されているモデル '{model}' が存在しません"),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_models(&mut self) -> anyhow::Result<()> {
+        for model_file in list_model_files()? {
+            let model = match read_json::<Value>(&model_file.path) {
+                Ok(model) => model,
+                Err(err) => {
+                    self.report(
+                        ProblemKind::InvalidModel,
+                        &model_file.path,
+                        format!("{err:#}"),
+                    );
+                    continue;
+                }
+            };
+
+            // 他のモデルの親として使われているモデルも参照済みとみなす
+            if let Some(parent) = model
+                .get("parent")
+                .and_then(Value::as_str)
+                .and_then(custom_item_path)
+                .filter(|parent| self.models.contains(*parent))
+            {
+                self.referenced_models.insert(parent.to_string());
+            }
+
+            let Some(textures) = model.get("textures").and_then(Value::as_object) else {
+                continue;
+            };
+            for (key, value) in textures {
+                let Some(reference) = value.as_str() else {
+                    self.report(
+                        ProblemKind::InvalidModel,
+                        &model_file.path,
+                        format!("textures.{key} が文字列ではありません"),
+                    );
+                    continue;
+                };
+                // "#0" などのテクスチャ変数や、item/ 以外のテクスチャは対象外
+                let Some(texture) = custom_item_path(reference) else {
+                    continue;
+                };
+                self.referenced_textures.insert(texture.to_string());
+                // パックに含まれないバニラのテクスチャは存在しなくてよい
+                if !Paths::texture_path(texture).is_file()
+                    && !self.mapping.is_vanilla_texture(reference)
+                {
+                    self.report(
+                        ProblemKind::MissingTexture,
+                        &model_file.path,
+                        format!("textures.{key} のテクスチャ '{reference}' が存在しません"),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_textures(&mut self) -> anyhow::Result<()> {
+        let textures_dir = Paths::textures_dir();
+        let mut entries = std::fs::read_dir(&textures_dir)
+            .with_context(|| {
+                format!(
+                    "テクスチャディレクトリの読み込みに失敗: {}",
+                    textures_dir.display()
+                )
+            })?
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        for path in entries {
+            let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            if path.is_dir() {
+                self.check_layer_dir(file_name, &path)?;
+            } else if let Some(name) = file_name.strip_suffix(".png.mcmeta") {
+                if !Paths::texture_path(name).is_file() {
+                    self.report(
+                        ProblemKind::OrphanTexture,
+                        &path,
+                        format!("対応するテクスチャ '{name}.png' が存在しません"),
+                    );
+                }
+            } else if let Some(name) = file_name.strip_suffix(".png") {
+                self.check_texture(name, &path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_texture(&mut self, name: &str, path: &Path) {
+        if !self.referenced_textures.contains(name) {
+            self.report(
+                ProblemKind::OrphanTexture,
+                path,
+                format!("テクスチャ '{name}' はどのモデルからも使われていません"),
+            );
+        }
+
+        let animation_path = Paths::animation_path(name);
+        let animation = if animation_path.is_file() {
+            match read_json::<AnimationInfo>(&animation_path) {
+                Ok(info) => Some(info),
+                Err(err) => {
+                    self.report(
+                        ProblemKind::InvalidAnimation,
+                        &animation_path,
+                        format!("{err:#}"),
+                    );
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let validator = match ImageValidator::open_png(path) {
+            Ok(validator) => validator,
+            Err(err) => {
+                self.report(ProblemKind::InvalidTexture, path, format!("{err:#}"));
+                return;
+            }
+        };
+
+        if let Err(err) = validator.check_model(animation.as_ref()) {
+            let kind = if animation.is_some() {
+                ProblemKind::InvalidAnimation
+            } else {
+                ProblemKind::InvalidTexture
+            };
+            self.report(kind, path, format!("{err:#}"));
+        }
+    }
+
+    fn check_layer_dir(&mut self, name: &str, dir: &Path) -> anyhow::Result<()> {
+        let mut layers = std::fs::read_dir(dir)
+            .with_context(|| format!("ディレクトリの読み込みに失敗: {}", dir.display()))?
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("png"))
+            .collect::<Vec<_>>();
+        layers.sort();
+
+        for layer in layers {
+            let Some(layer_name) = layer.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let texture = format!("{name}/{layer_name}");
+            if !self.referenced_textures.contains(&texture) {
+                self.report(
+                    ProblemKind::OrphanTexture,
+                    &layer,
+                    format!("テクスチャ '{texture}' はどのモデルからも使われていません"),
+                );
+            }
+            // 3Dモデルのレイヤーは正方形である必要がないため、形式のみ検証する
+            if let Err(err) = ImageValidator::open_png(&layer) {
+                self.report(ProblemKind::InvalidTexture, &layer, format!("{err:#}"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `item/<path>`（`minecraft:` 名前空間は任意）から `<path>` を取り出す
+fn custom_item_path(reference: &str) -> Option<&str> {
+    reference
+        .trim_start_matches("minecraft:")
+        .strip_prefix("item/")
+        .filter(|path| !path.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+    use crate::{paths::TestRoot, schema::items::ItemCase, utils::json::write_json};
+
+    fn write_png(name: &str, width: u32, height: u32) {
+        RgbaImage::from_pixel(width, height, Rgba([255, 0, 0, 255]))
+            .save(Paths::texture_path(name))
+            .unwrap();
+    }
+
+    fn write_model(name: &str, textures: &[&str]) {
+        let textures = textures
+            .iter()
+            .enumerate()
+            .map(|(i, texture)| (format!("layer{i}"), Value::from(*texture)))
+            .collect::<serde_json::Map<_, _>>();
+        write_json(
+            &Paths::model_path(name),
+            &serde_json::json!({"parent": "minecraft:item/generated", "textures": textures}),
+        )
+        .unwrap();
+    }
+
+    fn write_item(material: &str, names: &[&str]) {
+        let mut resource = ItemResource::new_with_fallback(&format!("minecraft:item/{material}"));
+        for name in names {
+            resource.add_case(ItemCase::new(name));
+        }
+        write_json(&Paths::item_path(material), &resource).unwrap();
+    }
+
+    fn problems() -> Vec<(ProblemKind, String)> {
+        let mut problems = Checker::default()
+            .run()
+            .unwrap()
+            .into_iter()
+            .map(|problem| (problem.kind, problem.message))
+            .collect::<Vec<_>>();
+        problems.sort_by_key(|(kind, _)| *kind as u8);
+        problems
+    }
+
+    #[test]
+    fn test_clean_pack() {
+        let _root = TestRoot::new();
+        write_png("ruby_sword", 16, 16);
+        write_model("ruby_sword", &["minecraft:item/ruby_sword"]);
+        write_item("diamond_sword", &["ruby_sword"]);
+
+        assert_eq!(problems(), []);
+    }
+
+    #[test]
+    fn test_missing_model() {
+        let _root = TestRoot::new();
+        write_item("diamond_sword", &["ruby_sword"]);
+
+        let problems = problems();
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert_eq!(problems[0].0, ProblemKind::MissingModel);
+        assert!(problems[0].1.contains("item/ruby_sword"));
+    }
+
+    #[test]
+    fn test_missing_texture() {
+        let _root = TestRoot::new();
+        write_png("ruby_sword", 16, 16);
+        // モデル名と一致しないテクスチャや、他のモデルのディレクトリの参照も検査する
+        write_model(
+            "ruby_sword",
+            &[
+                "minecraft:item/ruby_sword",
+                "item/shared/gem",
+                "item/other_model/0",
+                "minecraft:item/diamond_sword",
+                "minecraft:items/bow",
+                "minecraft:block/stone",
+                "#0",
+            ],
+        );
+        write_item("diamond_sword", &["ruby_sword"]);
+
+        let problems = problems();
+        assert_eq!(
+            problems.iter().map(|(kind, _)| *kind).collect::<Vec<_>>(),
+            [ProblemKind::MissingTexture, ProblemKind::MissingTexture],
+            "{problems:?}"
+        );
+        assert!(problems[0].1.contains("item/shared/gem"));
+        assert!(problems[1].1.contains("item/other_model/0"));
+    }
+
+    #[test]
+    fn test_orphan_texture() {
+        let _root = TestRoot::new();
+        write_png("ruby_sword", 16, 16);
+        write_png("unused", 16, 16);
+        write_model("ruby_sword", &["minecraft:item/ruby_sword"]);
+        write_item("diamond_sword", &["ruby_sword"]);
+        std::fs::write(
+            Paths::animation_path("no_image"),
+            r#"{"animation":{"frametime":2}}"#,
+        )
+        .unwrap();
+
+        let problems = problems();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(
+            problems
+                .iter()
+                .all(|(kind, _)| *kind == ProblemKind::OrphanTexture)
+        );
+        assert!(
+            problems
+                .iter()
+                .any(|(_, message)| message.contains("no_image.png"))
+        );
+        assert!(
+            problems
+                .iter()
+                .any(|(_, message)| message.contains("'unused'"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_animation() {
+        let _root = TestRoot::new();
+        // 2フレームの画像に存在しないフレーム番号を指定
+        write_png("ruby_sword", 16, 32);
+        std::fs::write(
+            Paths::animation_path("ruby_sword"),
+            r#"{"animation":{"frames":[0,1,5]}}"#,
+        )
+        .unwrap();
+        write_png("broken", 16, 32);
+        std::fs::write(Paths::animation_path("broken"), "not json").unwrap();
+        write_model("ruby_sword", &["minecraft:item/ruby_sword"]);
+        write_model("broken", &["minecraft:item/broken"]);
+        write_item("diamond_sword", &["ruby_sword", "broken"]);
+
+        let problems = problems();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(
+            problems
+                .iter()
+                .all(|(kind, _)| *kind == ProblemKind::InvalidAnimation)
+        );
+    }
+}
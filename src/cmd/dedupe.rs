@@ -0,0 +1,70 @@
+use crate::utils::{dedupe::DedupePlan, format::format_size};
+
+/// ♻️ 同一テクスチャの重複排除
+///
+/// 同じ画像のテクスチャを1つにまとめ、モデルの参照を書き換えます。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "同一テクスチャの重複排除",
+    long_about = "assets/minecraft/textures/item 配下のテクスチャから同一の画像を探し、\n\
+                  1つの共有テクスチャにまとめます。\n\n\
+                  ファイルの内容が同じもの（バイト一致）に加えて、圧縮方法などが異なっても\n\
+                  サイズと全ピクセルが同じもの（ピクセル一致）もまとめます。\n\
+                  グループ内で最もファイルサイズが小さいテクスチャを残し、\n\
+                  モデルの textures の参照をそのテクスチャに書き換えてから残りを削除します。\n\n\
+                  アニメーションテクスチャ（.mcmeta を持つもの）は対象外です。\n\
+                  リポジトリを変更せずにZipだけを小さくする場合は zip --dedupe を使用してください。"
+)]
+pub struct Dedupe {
+    /// 変更内容を表示するだけで実際には変更しない
+    #[arg(long, help = "変更内容を表示するだけで実際には変更しない")]
+    pub dry_run: bool,
+}
+
+impl super::Run for Dedupe {
+    fn run(&self) -> anyhow::Result<()> {
+        println!("\n♻️  重複テクスチャを検索中...\n");
+
+        let plan = DedupePlan::scan()?;
+        if plan.is_empty() {
+            println!("✅ 重複したテクスチャはありません\n");
+            return Ok(());
+        }
+
+        for group in &plan.groups {
+            println!("📋 {}", group.kept.display());
+            for removed in &group.removed {
+                println!("  ➜ 削除: {}（{}）", removed.display(), group.kind.label());
+            }
+        }
+        println!();
+        for rewrite in &plan.rewrites {
+            println!(
+                "  ✏️  {} の参照を {} 件書き換え",
+                rewrite.path.display(),
+                rewrite.count
+            );
+        }
+
+        let removed_count = plan
+            .groups
+            .iter()
+            .map(|group| group.removed.len())
+            .sum::<usize>();
+        println!(
+            "\n📊 {} 個のテクスチャを削除し、{} 削減できます",
+            removed_count,
+            format_size(plan.saved_bytes())
+        );
+
+        if self.dry_run {
+            println!("\n💡 --dry-run が指定されているため、変更は行いません\n");
+            return Ok(());
+        }
+
+        plan.apply()?;
+        println!("\n✅ 重複テクスチャをまとめました\n");
+
+        Ok(())
+    }
+}
@@ -2,8 +2,11 @@ use anyhow::Context;
 
 use crate::{
     paths::Paths,
-    schema::items::{ItemCase, ItemResource},
-    utils::json::{read_json, write_json},
+    schema::items::{ItemCase, ItemModelNode, ItemResource},
+    utils::{
+        add::{Transaction, add_index_entry, find_case_model},
+        json::read_json,
+    },
     validation::should_snake_case,
 };
 
@@ -43,6 +46,18 @@ pub struct Extend {
         help = "既存のカスタムモデルデータ名"
     )]
     pub custom_model_data: String,
+
+    /// 数値カスタムモデルデータのID
+    ///
+    /// 指定すると、文字列のケースに加えて `minecraft:range_dispatch` の
+    /// エントリーとしても登録します。同じマテリアルで別のモデルが
+    /// 同じIDを使用している場合はエラーになります。
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        help = "数値カスタムモデルデータのID（range_dispatch に登録）"
+    )]
+    pub index: Option<f64>,
 }
 
 impl super::Run for Extend {
@@ -59,6 +74,7 @@ impl super::Run for Extend {
 
         // マテリアルの存在検証（items_textures.json に基づく）
         crate::utils::add::validate_materials(&self.materials)?;
+        crate::utils::add::validate_index(self.index)?;
 
         // モデルは存在する必要がある（テクスチャの存在は不要）
         let model_path = Paths::model_path(&self.custom_model_data);
@@ -70,12 +86,28 @@ impl super::Run for Extend {
         }
 
         println!("📋 対象モデル: {}", self.custom_model_data);
-        println!("📦 追加マテリアル: {}\n", self.materials.join(", "));
+        println!("📦 追加マテリアル: {}", self.materials.join(", "));
+        if let Some(index) = self.index {
+            println!("🔢 数値ID: {}", index);
+        }
+        println!();
+
+        // ティントや状態ごとのモデルを含む場合は、既存のケースと同じモデルを使う
+        let model = find_case_model(&self.custom_model_data)?
+            .unwrap_or_else(|| ItemModelNode::item(&self.custom_model_data));
 
+        let mut tx = Transaction::new();
         for material in &self.materials {
             println!("  ➜ マテリアル '{}' に適用中...", material);
 
-            extend_material(&self.custom_model_data, material).with_context(|| {
+            extend_material(
+                &mut tx,
+                &self.custom_model_data,
+                &model,
+                material,
+                self.index,
+            )
+            .with_context(|| {
                 format!(
                     "マテリアル '{}' への custom_model_data '{}' の追加に失敗しました",
                     material, self.custom_model_data
@@ -83,6 +115,8 @@ impl super::Run for Extend {
             })?;
         }
 
+        tx.commit()?;
+
         let added_materials = self.materials.join(", ");
         println!(
             "\n✅ マテリアル [{}] に custom_model_data '{}' を追加しました\n",
@@ -93,7 +127,13 @@ impl super::Run for Extend {
     }
 }
 
-fn extend_material(custom_model_data: &str, material: &str) -> anyhow::Result<()> {
+fn extend_material(
+    tx: &mut Transaction,
+    custom_model_data: &str,
+    model: &ItemModelNode,
+    material: &str,
+    index: Option<f64>,
+) -> anyhow::Result<()> {
     let material_path = Paths::item_path(material);
 
     let mut item_resource = if material_path.exists() {
@@ -109,30 +149,48 @@ fn extend_material(custom_model_data: &str, material: &str) -> anyhow::Result<()
         ItemResource::new_with_fallback(&fallback)
     };
 
-    if item_resource
-        .model
-        .cases
-        .iter()
-        .any(|case| case.when == custom_model_data)
-    {
+    let added_case = if item_resource.has_case(custom_model_data) {
         println!(
             "  ⚠️  custom_model_data '{}' はすでにマテリアル '{}' に適用されています（スキップ）",
             custom_model_data, material
         );
+        false
+    } else {
+        item_resource.add_case(ItemCase::with_model(custom_model_data, model.clone()));
+        true
+    };
+
+    let added_entry = match index {
+        Some(index) => {
+            let added = add_index_entry(
+                &mut item_resource,
+                material,
+                custom_model_data,
+                index,
+                model,
+            )?;
+            if !added {
+                println!(
+                    "  ⚠️  数値ID {} はすでにマテリアル '{}' に登録されています（スキップ）",
+                    index, material
+                );
+            }
+            added
+        }
+        None => false,
+    };
+
+    if !added_case && !added_entry {
         return Ok(());
     }
 
-    item_resource
-        .model
-        .cases
-        .push(ItemCase::new(custom_model_data));
-
-    write_json(&material_path, &item_resource).with_context(|| {
-        format!(
-            "マテリアルファイルの書き込みに失敗: {}",
-            material_path.display()
-        )
-    })?;
+    tx.write_json(&material_path, &item_resource)
+        .with_context(|| {
+            format!(
+                "マテリアルファイルの書き込みに失敗: {}",
+                material_path.display()
+            )
+        })?;
 
     println!("  ✓ 追加完了");
 
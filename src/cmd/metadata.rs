@@ -6,12 +6,14 @@ use std::process::Command;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::cmd::Run;
+use crate::{cmd::Run, utils::hash::file_digests};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CollectMetadata {
     version: String,
     sha1: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
     size: u64,
     commit: String,
     updated_at: String,
@@ -34,6 +36,10 @@ pub struct Metadata {
     /// ZipファイルのパスSHA1計算とサイズ取得に使用
     #[arg(short, long, default_value = "OfroPack.zip")]
     pub zip: PathBuf,
+
+    /// SHA1に加えてSHA256も出力する
+    #[arg(long)]
+    pub sha256: bool,
 }
 
 impl Run for Metadata {
@@ -45,9 +51,17 @@ impl Run for Metadata {
             anyhow::bail!("Zipファイルが見つかりません: {}", self.zip.display());
         }
 
-        // Get SHA1
-        let sha1 = self.calculate_sha1()?;
-        println!("  ✓ SHA1: {}", sha1);
+        // Get SHA1 (and SHA256 if requested)
+        let digests = file_digests(&self.zip, self.sha256)
+            .with_context(|| format!("ハッシュの計算に失敗: {}", self.zip.display()))?;
+        println!("  ✓ SHA1: {}", digests.sha1);
+        println!(
+            "    (server.properties: resource-pack-sha1={})",
+            digests.sha1
+        );
+        if let Some(ref sha256) = digests.sha256 {
+            println!("  ✓ SHA256: {}", sha256);
+        }
 
         // Get file size
         let size = fs::metadata(&self.zip)
@@ -76,7 +90,8 @@ impl Run for Metadata {
 
         let metadata = CollectMetadata {
             version,
-            sha1,
+            sha1: digests.sha1,
+            sha256: digests.sha256,
             size,
             commit,
             updated_at,
@@ -93,30 +108,6 @@ impl Run for Metadata {
 }
 
 impl Metadata {
-    /// Calculate SHA1 hash of the zip file
-    fn calculate_sha1(&self) -> Result<String> {
-        let output = Command::new("sha1sum")
-            .arg(&self.zip)
-            .output()
-            .context("sha1sumコマンドの実行に失敗")?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "SHA1の計算に失敗: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-
-        let sha1 = String::from_utf8(output.stdout)
-            .context("SHA1出力のパースに失敗")?
-            .split_whitespace()
-            .next()
-            .context("SHA1の抽出に失敗")?
-            .to_string();
-
-        Ok(sha1)
-    }
-
     /// Get current git commit hash
     fn get_current_commit(&self) -> Result<String> {
         let output = Command::new("git")
@@ -1,9 +1,16 @@
 pub mod add;
+mod check;
+mod dedupe;
 pub mod extend;
 mod generates;
 mod metadata;
 mod models;
+mod optimize;
+mod preview;
+mod remove;
+mod rename;
 mod runner;
+pub mod unextend;
 mod zip;
 
 /// 🎨 OfroPack - Minecraft リソースパック管理ツール
@@ -31,11 +38,46 @@ pub enum Cmd {
     /// 既存のカスタムモデルデータに新しいマテリアルを適用します。
     Extend(extend::Extend),
 
+    /// 🗑️ カスタムモデルを削除
+    ///
+    /// モデル・テクスチャ・プレビューを削除し、すべてのマテリアルから取り除きます。
+    Remove(remove::Remove),
+
+    /// ✂️ 既存モデルからマテリアルを外す
+    ///
+    /// 既存のカスタムモデルデータを指定したマテリアルから取り除きます。
+    Unextend(unextend::Unextend),
+
+    /// 🏷️ カスタムモデルデータ名を変更
+    ///
+    /// ファイル名とすべての参照をまとめて書き換えます。失敗時は元に戻します。
+    Rename(rename::Rename),
+
     /// 📊 ギャラリー用モデルデータを生成
     ///
     /// Webギャラリー表示用のmodels.jsonを生成します。
     Models(models::Models),
 
+    /// 🖼️ プレビュー画像を生成
+    ///
+    /// 2D/3Dモデルのプレビュー画像（256×256）を生成します。
+    Preview(preview::Preview),
+
+    /// 🔎 リソースパックを検証
+    ///
+    /// 壊れた参照・不正なテクスチャ・未使用ファイルを検出します。
+    Check(check::Check),
+
+    /// ♻️ 同一テクスチャの重複排除
+    ///
+    /// 同じ画像のテクスチャを1つにまとめ、モデルの参照を書き換えます。
+    Dedupe(dedupe::Dedupe),
+
+    /// 🪶 テクスチャを可逆最適化
+    ///
+    /// PNGを可逆に再エンコードし、補助チャンクの削除や減色でサイズを削減します。
+    Optimize(optimize::Optimize),
+
     /// 📝 メタデータを生成
     ///
     /// リソースパックのメタデータファイルを生成します。
@@ -67,7 +109,14 @@ impl Run for Cmd {
         match self {
             Self::Add(cmd) => cmd.run(),
             Self::Extend(cmd) => cmd.run(),
+            Self::Remove(cmd) => cmd.run(),
+            Self::Unextend(cmd) => cmd.run(),
+            Self::Rename(cmd) => cmd.run(),
             Self::Models(cmd) => cmd.run(),
+            Self::Preview(cmd) => cmd.run(),
+            Self::Check(cmd) => cmd.run(),
+            Self::Dedupe(cmd) => cmd.run(),
+            Self::Optimize(cmd) => cmd.run(),
             Self::Metadata(cmd) => cmd.run(),
             Self::Zip(cmd) => cmd.run(),
             Self::Runner(cmd) => cmd.run(),
@@ -10,8 +10,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     paths::Paths,
-    schema::{animation::AnimationInfo, items::ItemResource},
-    utils::json::{read_json, write_json},
+    schema::{animation::AnimationInfo, items::ItemResource, models::ItemModel},
+    utils::{
+        json::{read_json, write_json},
+        models::model_texture_path,
+    },
 };
 
 /// 📊 ギャラリー用モデルデータ生成
@@ -47,15 +50,49 @@ pub struct ModelInfo {
     pub materials: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub texture_path: Option<String>,
+    /// プレビュー画像のパス（3Dモデルの表示に使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_path: Option<String>,
+    /// 数値カスタムモデルデータとして登録されているID
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indices: Vec<ModelIndex>,
     pub added_date: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub animation: Option<AnimationMetadata>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelIndex {
+    pub material: String,
+    pub index: f64,
+}
+
+/// モデルごとのマテリアル適用状況
+#[derive(Debug, Default)]
+struct MaterialUsage {
+    materials: Vec<String>,
+    indices: Vec<ModelIndex>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnimationMetadata {
     pub frame_count: u32,
     pub frametime: u32,
+    /// アニメーション1周の合計tick数（`frames` のフレームごとのtick数を含む）
+    pub total_duration: u32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub interpolate: bool,
+    /// 再生順のフレーム（`frames` を展開したもの）
+    #[serde(default)]
+    pub frames: Vec<FrameMetadata>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FrameMetadata {
+    /// フレーム番号
+    pub index: u32,
+    /// 表示するtick数
+    pub time: u32,
 }
 
 impl super::Run for Models {
@@ -94,8 +131,8 @@ impl Models {
             .map(String::from)
     }
 
-    fn build_material_map() -> anyhow::Result<HashMap<String, Vec<String>>> {
-        let mut material_map_by_model: HashMap<String, Vec<String>> = HashMap::new();
+    fn build_material_map() -> anyhow::Result<HashMap<String, MaterialUsage>> {
+        let mut material_map_by_model: HashMap<String, MaterialUsage> = HashMap::new();
 
         for entry in std::fs::read_dir(Paths::ITEMS)
             .context("アイテムディレクトリの読み込みに失敗")?
@@ -115,12 +152,31 @@ impl Models {
                 )
             })?;
 
-            for case in &item_resource.model.cases {
+            for name in item_resource
+                .cases()
+                .iter()
+                .flat_map(|case| case.when.values())
+            {
                 material_map_by_model
-                    .entry(case.when.clone())
+                    .entry(name.to_string())
                     .or_default()
+                    .materials
                     .push(material.clone());
             }
+
+            for entry in item_resource.entries() {
+                let Some(name) = entry.model.custom_model_name() else {
+                    continue;
+                };
+                let usage = material_map_by_model.entry(name.to_string()).or_default();
+                if !usage.materials.contains(&material) {
+                    usage.materials.push(material.clone());
+                }
+                usage.indices.push(ModelIndex {
+                    material: material.clone(),
+                    index: entry.threshold.get(),
+                });
+            }
         }
 
         Ok(material_map_by_model)
@@ -137,7 +193,7 @@ impl Models {
 
     fn process_model_file(
         entry: std::fs::DirEntry,
-        material_map_by_model: &HashMap<String, Vec<String>>,
+        material_map_by_model: &HashMap<String, MaterialUsage>,
     ) -> anyhow::Result<ModelInfo> {
         let model_path = entry.path();
         let Some(model_name) = Self::extract_file_stem(&model_path) else {
@@ -145,28 +201,39 @@ impl Models {
             anyhow::bail!("Invalid model file name");
         };
 
-        let texture_path = Paths::texture_path(&model_name);
+        // dedupe で共有テクスチャにまとめた場合は、参照先の画像を使う
+        let texture_path = model_texture_path(&model_name)?;
         let texture_path_dir = Paths::texture_path_dir(&model_name);
-        let texture_path = if texture_path_dir.is_dir() {
-            None
-        } else if !texture_path.exists() {
-            eprintln!("  ✗ テクスチャファイルが存在しません: {:?}", texture_path);
+        // 3Dモデルはテクスチャディレクトリのみを持つ
+        // （レイヤーをすべて共有テクスチャにまとめた場合はディレクトリもない）
+        if texture_path.is_none()
+            && !texture_path_dir.is_dir()
+            && read_json::<ItemModel>(&model_path)?.elements().is_empty()
+        {
+            eprintln!(
+                "  ✗ テクスチャファイルが存在しません: {:?}",
+                Paths::texture_path(&model_name)
+            );
             anyhow::bail!("Texture file not found");
-        } else {
-            Some(texture_path)
-        };
+        }
+
+        let preview_path = Some(Paths::preview_path(&model_name)).filter(|path| path.is_file());
 
         let added_date = find_git_added_data(&model_path).context("Gitメタデータの取得に失敗")?;
         let animation_metadata =
             find_animation_metadata(&model_name).context("アニメーションメタデータの取得に失敗")?;
 
+        let (materials, indices) = material_map_by_model
+            .get(&model_name)
+            .map(|usage| (usage.materials.clone(), usage.indices.clone()))
+            .unwrap_or_default();
+
         Ok(ModelInfo {
             name: model_name.clone(),
-            materials: material_map_by_model
-                .get(&model_name)
-                .cloned()
-                .unwrap_or_default(),
+            materials,
             texture_path: texture_path.map(|v| v.to_string_lossy().to_string()),
+            preview_path: preview_path.map(|v| v.to_string_lossy().to_string()),
+            indices,
             added_date,
             animation: animation_metadata,
         })
@@ -242,7 +309,12 @@ fn find_animation_metadata(model_name: &str) -> anyhow::Result<Option<AnimationM
     let image_path = Paths::texture_path(model_name);
     let frame_count = if let Ok(img) = image::open(&image_path) {
         let (width, height) = img.dimensions();
-        height / width
+        info.animation.frame_count(width, height).with_context(|| {
+            format!(
+                "アニメーションのフレーム数の取得に失敗: {}",
+                image_path.display()
+            )
+        })?
     } else {
         anyhow::bail!(
             "アニメーション画像の読み込みに失敗: {}",
@@ -253,5 +325,13 @@ fn find_animation_metadata(model_name: &str) -> anyhow::Result<Option<AnimationM
     Ok(Some(AnimationMetadata {
         frame_count,
         frametime: info.animation.frametime,
+        total_duration: info.animation.total_duration(frame_count),
+        interpolate: info.animation.interpolate == Some(true),
+        frames: info
+            .animation
+            .frame_sequence(frame_count)
+            .into_iter()
+            .map(|(index, time)| FrameMetadata { index, time })
+            .collect(),
     }))
 }
@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::{
+    paths::Paths,
+    utils::{format::format_size, optimize::optimize_file},
+};
+
+/// 🪶 テクスチャの可逆最適化
+///
+/// assets 配下のPNGを可逆に再エンコードしてファイルサイズを削減します。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "テクスチャの可逆最適化",
+    long_about = "assets 配下のすべてのPNGを可逆に再エンコードし、ファイルサイズを削減します。\n\n\
+                  - テキスト・ガンマ・Exif などの補助チャンクを削除\n\
+                  - 色数が256以下ならインデックスカラーに変換\n\
+                  - 無彩色ならグレースケール、不透明ならアルファなしに変換\n\
+                  - 16bit の画像は値が変わらない場合のみ 8bit に変換\n\n\
+                  置き換える前に元の画像とすべてのピクセルが一致することを確認し、\n\
+                  小さくならなかったファイルはそのままにします。\n\
+                  リポジトリを変更せずにZipだけを小さくする場合は zip --optimize を使用してください。"
+)]
+pub struct Optimize {
+    /// 削減量を表示するだけで実際には書き換えない
+    #[arg(long, help = "削減量を表示するだけで実際には書き換えない")]
+    pub dry_run: bool,
+}
+
+impl super::Run for Optimize {
+    fn run(&self) -> anyhow::Result<()> {
+        println!("\n🪶 テクスチャを最適化中...\n");
+
+        let mut paths = Vec::new();
+        collect_pngs(&Paths::assets_path(), &mut paths)?;
+        paths.sort();
+
+        let mut optimized_count = 0;
+        let mut total_before = 0;
+        let mut total_saved = 0;
+        for path in &paths {
+            let Some(optimized) = optimize_file(path)? else {
+                continue;
+            };
+            println!(
+                "  ✓ {}: {} → {}（-{:.1}%）",
+                path.display(),
+                format_size(optimized.original_size),
+                format_size(optimized.data.len() as u64),
+                optimized.saved_bytes() as f64 / optimized.original_size as f64 * 100.0
+            );
+            if !self.dry_run {
+                std::fs::write(path, &optimized.data)
+                    .with_context(|| format!("ファイルの書き込みに失敗: {}", path.display()))?;
+            }
+            optimized_count += 1;
+            total_before += optimized.original_size;
+            total_saved += optimized.saved_bytes();
+        }
+
+        if optimized_count == 0 {
+            println!("✅ これ以上小さくできるテクスチャはありません\n");
+            return Ok(());
+        }
+
+        println!(
+            "\n📊 {} 個中 {} 個のテクスチャで {} 削減（最適化したファイルの合計 {} → {}）",
+            paths.len(),
+            optimized_count,
+            format_size(total_saved),
+            format_size(total_before),
+            format_size(total_before - total_saved)
+        );
+
+        if self.dry_run {
+            println!("\n💡 --dry-run が指定されているため、変更は行いません\n");
+        } else {
+            println!("\n✅ テクスチャの最適化が完了しました\n");
+        }
+
+        Ok(())
+    }
+}
+
+/// ディレクトリ配下のPNGを再帰的に列挙
+fn collect_pngs(dir: &Path, paths: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("ディレクトリの読み込みに失敗: {}", dir.display()))?
+    {
+        let path = entry
+            .with_context(|| format!("ディレクトリエントリの取得に失敗: {}", dir.display()))?
+            .path();
+        if path.is_dir() {
+            collect_pngs(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "png") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
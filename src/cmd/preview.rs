@@ -0,0 +1,68 @@
+use crate::{
+    paths::Paths,
+    pipeline::preview_generator::PreviewGenerator,
+    utils::models::{list_model_files, model_texture_path},
+};
+
+/// 🖼️ プレビュー画像を生成
+///
+/// 2Dモデルはテクスチャを拡大（アニメーションはGIFも作成）し、
+/// 3Dモデルはインベントリでの見た目を描画して `preview/` に保存します。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "プレビュー画像を生成",
+    long_about = "カスタムモデルのプレビュー画像（256×256）を preview/ に生成します。\n\n\
+                  2Dモデルはテクスチャを拡大し、アニメーションテクスチャの場合は\n\
+                  GIFプレビューも作成します。3Dモデルはモデルの display.gui に従って\n\
+                  インベントリでの見た目を描画します。"
+)]
+pub struct Preview {
+    /// カスタムモデルデータ名（カンマ区切り）
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        required_unless_present = "all",
+        value_name = "NAMES",
+        help = "プレビューを生成するカスタムモデルデータ名（カンマ区切り）"
+    )]
+    pub custom_model_data: Vec<String>,
+
+    /// すべてのモデルのプレビューを生成
+    #[arg(
+        long,
+        conflicts_with = "custom_model_data",
+        help = "すべてのモデルのプレビューを生成"
+    )]
+    pub all: bool,
+}
+
+impl super::Run for Preview {
+    fn run(&self) -> anyhow::Result<()> {
+        let names = if self.all {
+            // 状態ごとのモデル（<名前>/<状態>）はベースのモデルのプレビューに含める
+            list_model_files()?
+                .into_iter()
+                .map(|file| file.name)
+                .filter(|name| !name.contains('/'))
+                .collect()
+        } else {
+            self.custom_model_data.clone()
+        };
+
+        println!("\n🖼️  {} 個のプレビューを生成中...\n", names.len());
+        for name in &names {
+            let model_path = Paths::model_path(name);
+            if let Some(texture_path) = model_texture_path(name)? {
+                PreviewGenerator::generate(&texture_path, name)?;
+            } else if model_path.is_file() {
+                PreviewGenerator::generate_3d(&model_path, name)?;
+            } else {
+                anyhow::bail!("❌ モデルが存在しません: {}", name);
+            }
+        }
+
+        println!("\n✅ プレビューの生成が完了しました\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::{
+    paths::Paths,
+    schema::items::ItemResource,
+    utils::{
+        add::Transaction, items::list_item_files, json::read_json, models::models_referencing,
+    },
+    validation::should_snake_case,
+};
+
+/// 🗑️ カスタムモデルを削除
+///
+/// カスタムモデルデータに関連するすべてのファイルを削除し、
+/// すべてのマテリアルから該当するケースを取り除きます。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "カスタムモデルを削除",
+    long_about = "カスタムモデルデータに関連するファイルをすべて削除します。\n\n\
+                  モデルJSON、テクスチャ（またはレイヤーディレクトリ）、アニメーション、\n\
+                  プレビュー画像を削除し、すべてのアイテム定義から該当するケースを取り除きます。\n\
+                  ケースが空になったアイテム定義ファイルは削除されます。"
+)]
+pub struct Remove {
+    /// カスタムモデルデータ名
+    #[arg(
+        short,
+        long,
+        required = true,
+        value_name = "NAME",
+        help = "削除するカスタムモデルデータ名"
+    )]
+    pub custom_model_data: String,
+
+    /// 変更内容を表示するだけで実際には削除しない
+    #[arg(long, help = "変更内容を表示するだけで実際には削除しない")]
+    pub dry_run: bool,
+}
+
+/// 削除処理で行う変更
+#[derive(Debug)]
+enum Change {
+    /// ファイルを削除
+    DeleteFile(PathBuf),
+    /// ディレクトリを削除
+    DeleteDir(PathBuf),
+    /// アイテム定義からケースを削除して書き戻す
+    UpdateItem {
+        material: String,
+        path: PathBuf,
+        resource: Box<ItemResource>,
+    },
+    /// ケースが空になったアイテム定義を削除
+    DeleteItem { material: String, path: PathBuf },
+}
+
+impl Change {
+    fn describe(&self) -> String {
+        match self {
+            Self::DeleteFile(path) => format!("ファイルを削除: {}", path.display()),
+            Self::DeleteDir(path) => format!("ディレクトリを削除: {}", path.display()),
+            Self::UpdateItem { material, .. } => {
+                format!("マテリアル '{material}' からケースを削除")
+            }
+            Self::DeleteItem { material, path } => format!(
+                "マテリアル '{material}' のケースが空になるため削除: {}",
+                path.display()
+            ),
+        }
+    }
+
+    fn stage(&self, tx: &mut Transaction) -> anyhow::Result<()> {
+        match self {
+            Self::DeleteFile(path) | Self::DeleteItem { path, .. } => tx.remove_file(path),
+            Self::DeleteDir(path) => tx.remove_dir(path),
+            Self::UpdateItem { path, resource, .. } => tx.write_json(path, resource)?,
+        }
+        Ok(())
+    }
+}
+
+/// 変更をトランザクションにまとめて反映
+///
+/// 途中で失敗した場合は、削除したファイルも含めて元に戻します。
+fn apply_changes(changes: &[Change]) -> anyhow::Result<()> {
+    let mut tx = Transaction::new();
+    for change in changes {
+        change.stage(&mut tx)?;
+    }
+    tx.commit()
+}
+
+impl super::Run for Remove {
+    fn run(&self) -> anyhow::Result<()> {
+        println!("\n🗑️  カスタムモデルの削除を開始します...\n");
+
+        should_snake_case(&self.custom_model_data)?;
+
+        let changes = plan_changes(&self.custom_model_data)?;
+        if changes.is_empty() {
+            anyhow::bail!(
+                "❌ custom_model_data '{}' に関連するファイルが見つかりません",
+                self.custom_model_data
+            );
+        }
+
+        println!("📋 対象モデル: {}", self.custom_model_data);
+        for change in &changes {
+            println!("  ➜ {}", change.describe());
+        }
+
+        if self.dry_run {
+            println!("\n💡 --dry-run が指定されているため、変更は行いません\n");
+            return Ok(());
+        }
+
+        apply_changes(&changes)?;
+
+        println!(
+            "\n✅ custom_model_data '{}' を削除しました\n",
+            self.custom_model_data
+        );
+
+        Ok(())
+    }
+}
+
+/// 削除するテクスチャを他のモデルが参照していないか確認
+///
+/// `dedupe` で共有テクスチャにまとめた場合、他のモデルがこのモデルの
+/// テクスチャを参照していることがあります。
+fn ensure_textures_not_shared(custom_model_data: &str) -> anyhow::Result<()> {
+    let mut textures = vec![Paths::texture_path(custom_model_data)];
+    let texture_dir = Paths::texture_path_dir(custom_model_data);
+    if texture_dir.is_dir() {
+        textures.extend(
+            std::fs::read_dir(&texture_dir)
+                .with_context(|| {
+                    format!("ディレクトリの読み込みに失敗: {}", texture_dir.display())
+                })?
+                .filter_map(std::result::Result::ok)
+                .map(|entry| entry.path()),
+        );
+    }
+
+    let state_prefix = format!("{custom_model_data}/");
+    let users = models_referencing(&textures)?
+        .into_iter()
+        .filter(|name| name != custom_model_data && !name.starts_with(&state_prefix))
+        .collect::<Vec<_>>();
+    if !users.is_empty() {
+        anyhow::bail!(
+            "❌ '{}' のテクスチャは他のモデルからも参照されているため削除できません: {}",
+            custom_model_data,
+            users.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// 削除に必要な変更を列挙
+fn plan_changes(custom_model_data: &str) -> anyhow::Result<Vec<Change>> {
+    ensure_textures_not_shared(custom_model_data)?;
+
+    let mut changes = Vec::new();
+
+    for path in [
+        Paths::model_path(custom_model_data),
+        Paths::texture_path(custom_model_data),
+        Paths::animation_path(custom_model_data),
+        Paths::preview_path(custom_model_data),
+        Paths::animated_preview_path(custom_model_data),
+    ] {
+        if path.is_file() {
+            changes.push(Change::DeleteFile(path));
+        }
+    }
+
+    for dir in [
+        Paths::model_path_dir(custom_model_data),
+        Paths::texture_path_dir(custom_model_data),
+    ] {
+        if dir.is_dir() {
+            changes.push(Change::DeleteDir(dir));
+        }
+    }
+
+    for item_file in list_item_files()? {
+        let mut resource = read_json::<ItemResource>(&item_file.path).with_context(|| {
+            format!(
+                "マテリアルファイルの読み込みに失敗: {}",
+                item_file.path.display()
+            )
+        })?;
+        if resource.remove_case(custom_model_data) == 0 {
+            continue;
+        }
+        if resource.is_fallback_only() {
+            changes.push(Change::DeleteItem {
+                material: item_file.material,
+                path: item_file.path,
+            });
+        } else {
+            changes.push(Change::UpdateItem {
+                material: item_file.material,
+                path: item_file.path,
+                resource: Box::new(resource),
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cmd::Run, paths::TestRoot};
+
+    fn remove(name: &str, dry_run: bool) -> anyhow::Result<()> {
+        Remove {
+            custom_model_data: name.to_string(),
+            dry_run,
+        }
+        .run()
+    }
+
+    #[test]
+    fn test_plan_changes() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword", "iron_sword"]);
+        root.add_model("other_sword", &["iron_sword"]);
+        std::fs::create_dir_all(Paths::texture_path_dir("ruby_sword")).unwrap();
+
+        let changes = plan_changes("ruby_sword").unwrap();
+        let descriptions = changes.iter().map(Change::describe).collect::<Vec<_>>();
+        assert_eq!(changes.len(), 5, "{descriptions:#?}");
+        assert!(
+            matches!(&changes[0], Change::DeleteFile(path) if *path == Paths::model_path("ruby_sword"))
+        );
+        assert!(
+            matches!(&changes[1], Change::DeleteFile(path) if *path == Paths::texture_path("ruby_sword"))
+        );
+        assert!(
+            matches!(&changes[2], Change::DeleteDir(path) if *path == Paths::texture_path_dir("ruby_sword"))
+        );
+        assert!(
+            matches!(&changes[3], Change::DeleteItem { material, .. } if material == "diamond_sword")
+        );
+        let Change::UpdateItem {
+            material, resource, ..
+        } = &changes[4]
+        else {
+            panic!("iron_sword は更新されるはずです: {descriptions:#?}");
+        };
+        assert_eq!(material, "iron_sword");
+        assert!(!resource.has_case("ruby_sword"));
+        assert!(resource.has_case("other_sword"));
+
+        assert!(plan_changes("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_changes_nothing() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword"]);
+        let item = std::fs::read(Paths::item_path("diamond_sword")).unwrap();
+
+        remove("ruby_sword", true).unwrap();
+
+        assert!(Paths::model_path("ruby_sword").is_file());
+        assert!(Paths::texture_path("ruby_sword").is_file());
+        assert_eq!(
+            std::fs::read(Paths::item_path("diamond_sword")).unwrap(),
+            item
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword", "iron_sword"]);
+        root.add_model("other_sword", &["iron_sword"]);
+
+        remove("ruby_sword", false).unwrap();
+
+        assert!(!Paths::model_path("ruby_sword").exists());
+        assert!(!Paths::texture_path("ruby_sword").exists());
+        assert!(!Paths::item_path("diamond_sword").exists());
+        let iron = read_json::<ItemResource>(&Paths::item_path("iron_sword")).unwrap();
+        assert!(!iron.has_case("ruby_sword"));
+        assert!(iron.has_case("other_sword"));
+
+        assert!(remove("ruby_sword", false).is_err());
+    }
+
+    #[test]
+    fn test_invalid_item_file_aborts_before_deleting() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword"]);
+        root.write(&format!("{}/broken.json", Paths::ITEMS), "not json");
+
+        assert!(remove("ruby_sword", false).is_err());
+        assert!(Paths::model_path("ruby_sword").is_file());
+        assert!(Paths::item_path("diamond_sword").is_file());
+    }
+}
@@ -0,0 +1,237 @@
+use anyhow::Context;
+use serde_json::Value;
+
+use crate::{
+    paths::Paths,
+    schema::items::{ItemResource, rename_resource_path},
+    utils::{add::Transaction, items::list_item_files, json::read_json, models::list_model_files},
+    validation::should_snake_case,
+};
+
+/// 🏷️ カスタムモデルデータ名を変更
+///
+/// モデル・テクスチャ・アニメーション・プレビューのファイル名と、
+/// アイテム定義やモデル内の参照をまとめて書き換えます。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "カスタムモデルデータ名を変更",
+    long_about = "カスタムモデルデータ名を変更します。\n\n\
+                  モデルJSON、テクスチャ（またはレイヤーディレクトリ）、アニメーション、\n\
+                  プレビュー画像のファイル名と、アイテム定義の when / model、\n\
+                  モデルの textures / parent の参照をすべて書き換えます。\n\
+                  途中で失敗した場合は、それまでの変更をすべて元に戻します。"
+)]
+pub struct Rename {
+    /// 変更前のカスタムモデルデータ名
+    #[arg(value_name = "OLD", help = "変更前のカスタムモデルデータ名")]
+    pub old: String,
+
+    /// 変更後のカスタムモデルデータ名
+    #[arg(value_name = "NEW", help = "変更後のカスタムモデルデータ名")]
+    pub new: String,
+}
+
+impl super::Run for Rename {
+    fn run(&self) -> anyhow::Result<()> {
+        println!("\n🏷️  カスタムモデルデータ名の変更を開始します...\n");
+
+        should_snake_case(&self.old)?;
+        should_snake_case(&self.new)?;
+        if self.old == self.new {
+            anyhow::bail!("変更前と変更後の名前が同じです: {}", self.old);
+        }
+
+        let old_model_path = Paths::model_path(&self.old);
+        if !old_model_path.exists() {
+            anyhow::bail!(
+                "❌ モデルファイルが存在しません: {}",
+                old_model_path.to_string_lossy()
+            );
+        }
+
+        println!("📋 {} ➜ {}\n", self.old, self.new);
+
+        let tx = plan_rename(&self.old, &self.new)?;
+        tx.commit().context("名前変更の反映に失敗しました")?;
+
+        println!(
+            "\n✅ カスタムモデルデータ名を '{}' から '{}' に変更しました\n",
+            self.old, self.new
+        );
+
+        Ok(())
+    }
+}
+
+/// 名前変更に必要な書き換えと移動をステージ
+///
+/// 内容の書き換えは移動前のパスにステージし、移動時に移動先へ引き継がれます。
+fn plan_rename(old: &str, new: &str) -> anyhow::Result<Transaction> {
+    let moves = [
+        (Paths::model_path(old), Paths::model_path(new)),
+        (Paths::model_path_dir(old), Paths::model_path_dir(new)),
+        (Paths::texture_path(old), Paths::texture_path(new)),
+        (Paths::texture_path_dir(old), Paths::texture_path_dir(new)),
+        (Paths::animation_path(old), Paths::animation_path(new)),
+        (Paths::preview_path(old), Paths::preview_path(new)),
+        (
+            Paths::animated_preview_path(old),
+            Paths::animated_preview_path(new),
+        ),
+    ];
+
+    let mut tx = Transaction::new();
+
+    for item_file in list_item_files()? {
+        let mut resource = read_json::<ItemResource>(&item_file.path).with_context(|| {
+            format!(
+                "マテリアルファイルの読み込みに失敗: {}",
+                item_file.path.display()
+            )
+        })?;
+        if resource.has_case(new) {
+            anyhow::bail!(
+                "❌ マテリアル '{}' には既に '{}' が存在します",
+                item_file.material,
+                new
+            );
+        }
+        if resource.rename_case(old, new) == 0 {
+            continue;
+        }
+        println!("  ➜ 参照を書き換え: {}", item_file.path.display());
+        tx.write_json(&item_file.path, &resource)?;
+    }
+
+    for model_file in list_model_files()? {
+        let mut model = read_json::<Value>(&model_file.path).with_context(|| {
+            format!(
+                "モデルファイルの読み込みに失敗: {}",
+                model_file.path.display()
+            )
+        })?;
+        if rewrite_model_references(&mut model, old, new) {
+            println!("  ➜ 参照を書き換え: {}", model_file.path.display());
+            tx.write_json(&model_file.path, &model)?;
+        }
+    }
+
+    for (from, to) in moves {
+        if !from.exists() {
+            continue;
+        }
+        if to.exists() {
+            anyhow::bail!("❌ 変更先が既に存在します: {}", to.display());
+        }
+        println!("  ➜ 移動: {} ➜ {}", from.display(), to.display());
+        tx.rename(&from, &to);
+    }
+
+    Ok(tx)
+}
+
+/// モデルJSON内の `parent` と `textures` の参照を書き換える
+///
+/// # Returns
+///
+/// 1つ以上書き換えた場合は `true`
+fn rewrite_model_references(model: &mut Value, old: &str, new: &str) -> bool {
+    let mut changed = false;
+
+    if let Some(parent) = model.get_mut("parent") {
+        changed |= rewrite_string(parent, old, new);
+    }
+    if let Some(Value::Object(textures)) = model.get_mut("textures") {
+        for value in textures.values_mut() {
+            changed |= rewrite_string(value, old, new);
+        }
+    }
+
+    changed
+}
+
+fn rewrite_string(value: &mut Value, old: &str, new: &str) -> bool {
+    let Value::String(s) = value else {
+        return false;
+    };
+    match rename_resource_path(s, old, new) {
+        Some(renamed) => {
+            *s = renamed;
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+    use crate::paths::TestRoot;
+
+    /// パック内のすべてのファイルとその内容
+    fn snapshot(root: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut files = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    let contents = std::fs::read(&path).unwrap();
+                    files.push((path, contents));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_rename_rewrites_and_moves() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword"]);
+
+        plan_rename("ruby_sword", "gem_sword")
+            .unwrap()
+            .commit()
+            .unwrap();
+
+        assert!(!Paths::model_path("ruby_sword").exists());
+        assert!(Paths::texture_path("gem_sword").is_file());
+        let model = read_json::<Value>(&Paths::model_path("gem_sword")).unwrap();
+        assert_eq!(model["textures"]["layer0"], "minecraft:item/gem_sword");
+        let resource = read_json::<ItemResource>(&Paths::item_path("diamond_sword")).unwrap();
+        assert!(resource.has_case("gem_sword"));
+        assert!(!resource.has_case("ruby_sword"));
+    }
+
+    #[test]
+    fn test_existing_destination_is_rejected_before_changes() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword"]);
+        std::fs::write(Paths::texture_path("gem_sword"), "png").unwrap();
+
+        assert!(plan_rename("ruby_sword", "gem_sword").is_err());
+    }
+
+    #[test]
+    fn test_failure_partway_restores_pack() {
+        let root = TestRoot::new();
+        root.add_model("ruby_sword", &["diamond_sword"]);
+        let before = snapshot(&Paths::assets_path());
+
+        let tx = plan_rename("ruby_sword", "gem_sword").unwrap();
+        // 計画後に変更先のテクスチャがディレクトリとして作られ、反映に失敗する
+        root.write(
+            &format!("{}/gem_sword.png/blocker.txt", Paths::TEXTURES),
+            "x",
+        );
+
+        assert!(tx.commit().is_err());
+        std::fs::remove_dir_all(Paths::texture_path("gem_sword")).unwrap();
+        assert_eq!(snapshot(&Paths::assets_path()), before);
+    }
+}
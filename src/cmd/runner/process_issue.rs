@@ -1,6 +1,13 @@
 use anyhow::Result;
 
-use crate::{cmd::Run, pipeline::runner::process_issue::IssueProcessor, types::IssueType};
+use crate::{
+    cmd::{Run, add::ContentOptions},
+    pipeline::{
+        image_downloader::{DOWNLOAD_ERROR_KIND_PREFIX, DownloadError},
+        runner::process_issue::IssueProcessor,
+    },
+    types::IssueType,
+};
 
 /// 📝 Issueを処理してPRを作成
 ///
@@ -21,7 +28,7 @@ pub struct ProcessIssue {
     #[arg(
         long,
         value_name = "TYPE",
-        help = "Issue種別（model, model3d, extend）"
+        help = "Issue種別（model, model3d, extend, unextend）"
     )]
     issue_type: IssueType,
 
@@ -40,18 +47,30 @@ pub struct ProcessIssue {
         help = "Git commitに使用するメールアドレス"
     )]
     actor_email: String,
+
+    /// テクスチャの内容チェックの設定
+    #[command(flatten)]
+    content: ContentOptions,
 }
 
 impl Run for ProcessIssue {
     fn run(&self) -> Result<()> {
-        let processor = IssueProcessor::new()?;
-        processor.process(
+        let processor = IssueProcessor::new()?.with_content_options(self.content.clone());
+        let result = processor.process(
             self.issue_number,
             self.issue_type,
             &self.body,
             &self.actor,
             &self.actor_email,
-        )?;
-        Ok(())
+        );
+        // 失敗時のコメントで原因を説明できるよう、ダウンロードエラーの種類を出力する
+        if let Err(error) = &result
+            && let Some(download) = error
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<DownloadError>())
+        {
+            eprintln!("{DOWNLOAD_ERROR_KIND_PREFIX}{}", download.kind().as_str());
+        }
+        result
     }
 }
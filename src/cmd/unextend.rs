@@ -0,0 +1,271 @@
+use anyhow::Context;
+
+use crate::{
+    paths::Paths,
+    schema::items::ItemResource,
+    utils::{add::Transaction, json::read_json},
+    validation::should_snake_case,
+};
+
+/// ✂️ 既存モデルからマテリアルを外す
+///
+/// 既に存在するカスタムモデルデータを、指定したマテリアルから取り除きます。
+/// モデルやテクスチャは削除しません。
+#[derive(clap::Parser, Debug)]
+#[command(
+    about = "既存モデルからマテリアルを外す",
+    long_about = "既に作成済みのカスタムモデルデータを、指定したマテリアルから取り除きます。\n\n\
+                  モデルファイルやテクスチャは削除されません。\n\
+                  フォールバックのみが残るアイテム定義ファイルは削除されます。"
+)]
+pub struct Unextend {
+    /// カンマ区切りのマテリアルリスト
+    ///
+    /// 例: diamond_axe,iron_sword,golden_pickaxe
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        required = true,
+        value_name = "MATERIALS",
+        help = "外すマテリアル（カンマ区切り）"
+    )]
+    pub materials: Vec<String>,
+
+    /// カスタムモデルデータ名
+    ///
+    /// 既に存在するカスタムモデルデータの名前を指定します。
+    #[arg(
+        short,
+        long,
+        required = true,
+        value_name = "NAME",
+        help = "既存のカスタムモデルデータ名"
+    )]
+    pub custom_model_data: String,
+}
+
+/// マテリアルごとの処理結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnextendOutcome {
+    /// ケースを削除して書き戻した
+    Removed,
+    /// ケースが空になったためアイテム定義ファイルを削除した
+    RemovedFile,
+    /// ケースが存在しなかった
+    NotPresent,
+}
+
+impl Unextend {
+    /// ケースが存在しなかったマテリアルを除いて実行し、実際に外したマテリアルを返す
+    ///
+    /// # Errors
+    ///
+    /// - 入力値が不正な場合
+    /// - 指定したすべてのマテリアルにケースが存在しない場合
+    /// - アイテム定義ファイルの読み書きに失敗した場合（どのファイルも変更しません）
+    pub fn execute(&self) -> anyhow::Result<Vec<String>> {
+        println!("\n✂️  マテリアルの取り外しを開始します...\n");
+
+        if self.materials.is_empty() {
+            anyhow::bail!("少なくとも1つのmaterialを指定してください。");
+        }
+        should_snake_case(&self.custom_model_data)?;
+        self.materials
+            .iter()
+            .try_for_each(|material| should_snake_case(material))?;
+
+        println!("📋 対象モデル: {}", self.custom_model_data);
+        println!("📦 外すマテリアル: {}\n", self.materials.join(", "));
+
+        let mut tx = Transaction::new();
+        let mut removed = Vec::new();
+        let mut not_present = Vec::new();
+
+        for material in &self.materials {
+            println!("  ➜ マテリアル '{}' から取り外し中...", material);
+
+            let outcome = unextend_material(&mut tx, &self.custom_model_data, material)
+                .with_context(|| {
+                    format!(
+                        "マテリアル '{}' からの custom_model_data '{}' の削除に失敗しました",
+                        material, self.custom_model_data
+                    )
+                })?;
+
+            match outcome {
+                UnextendOutcome::Removed => {
+                    println!("  ✓ 削除完了");
+                    removed.push(material.clone());
+                }
+                UnextendOutcome::RemovedFile => {
+                    println!("  ✓ 削除完了（フォールバックのみになったためファイルを削除）");
+                    removed.push(material.clone());
+                }
+                UnextendOutcome::NotPresent => {
+                    println!(
+                        "  ⚠️  custom_model_data '{}' はマテリアル '{}' に適用されていません（スキップ）",
+                        self.custom_model_data, material
+                    );
+                    not_present.push(material.clone());
+                }
+            }
+        }
+
+        if removed.is_empty() {
+            anyhow::bail!(
+                "❌ custom_model_data '{}' は指定されたマテリアル [{}] のいずれにも適用されていません",
+                self.custom_model_data,
+                not_present.join(", ")
+            );
+        }
+
+        tx.commit()
+            .context("アイテム定義ファイルの更新に失敗しました")?;
+
+        println!(
+            "\n✅ マテリアル [{}] から custom_model_data '{}' を外しました",
+            removed.join(", "),
+            self.custom_model_data
+        );
+        if !not_present.is_empty() {
+            println!(
+                "⚠️  適用されていなかったマテリアル: {}",
+                not_present.join(", ")
+            );
+        }
+        println!();
+
+        Ok(removed)
+    }
+}
+
+impl super::Run for Unextend {
+    fn run(&self) -> anyhow::Result<()> {
+        self.execute().map(|_| ())
+    }
+}
+
+/// マテリアルのアイテム定義からケースを外す書き込み・削除をステージする
+fn unextend_material(
+    tx: &mut Transaction,
+    custom_model_data: &str,
+    material: &str,
+) -> anyhow::Result<UnextendOutcome> {
+    let material_path = Paths::item_path(material);
+    if !material_path.exists() {
+        return Ok(UnextendOutcome::NotPresent);
+    }
+
+    let mut item_resource = read_json::<ItemResource>(&material_path).with_context(|| {
+        format!(
+            "マテリアルファイルの読み込みに失敗: {}",
+            material_path.display()
+        )
+    })?;
+
+    if item_resource.remove_case(custom_model_data) == 0 {
+        return Ok(UnextendOutcome::NotPresent);
+    }
+
+    if item_resource.is_fallback_only() {
+        tx.remove_file(&material_path);
+        return Ok(UnextendOutcome::RemovedFile);
+    }
+
+    tx.write_json(&material_path, &item_resource)?;
+
+    Ok(UnextendOutcome::Removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{paths::TestRoot, schema::items::ItemCase, utils::json::write_json};
+
+    fn add_cases(material: &str, names: &[&str]) {
+        let mut resource = ItemResource::new_with_fallback(&format!("minecraft:item/{material}"));
+        for name in names {
+            resource.add_case(ItemCase::new(name));
+        }
+        write_json(&Paths::item_path(material), &resource).unwrap();
+    }
+
+    fn unextend(name: &str, materials: &[&str]) -> anyhow::Result<Vec<String>> {
+        Unextend {
+            materials: materials.iter().map(ToString::to_string).collect(),
+            custom_model_data: name.to_string(),
+        }
+        .execute()
+    }
+
+    #[test]
+    fn test_detach_material() {
+        let _root = TestRoot::new();
+        add_cases("diamond_sword", &["ruby_sword", "other_sword"]);
+
+        let mut tx = Transaction::new();
+        assert_eq!(
+            unextend_material(&mut tx, "ruby_sword", "diamond_sword").unwrap(),
+            UnextendOutcome::Removed
+        );
+        tx.commit().unwrap();
+
+        let resource = read_json::<ItemResource>(&Paths::item_path("diamond_sword")).unwrap();
+        assert!(!resource.has_case("ruby_sword"));
+        assert!(resource.has_case("other_sword"));
+        assert_eq!(
+            unextend_material(&mut Transaction::new(), "ruby_sword", "diamond_sword").unwrap(),
+            UnextendOutcome::NotPresent
+        );
+    }
+
+    #[test]
+    fn test_empty_item_file_is_deleted() {
+        let _root = TestRoot::new();
+        add_cases("diamond_sword", &["ruby_sword"]);
+        add_cases("iron_sword", &["ruby_sword", "other_sword"]);
+
+        let removed = unextend("ruby_sword", &["diamond_sword", "iron_sword", "stick"]).unwrap();
+
+        assert_eq!(removed, ["diamond_sword", "iron_sword"]);
+        assert!(!Paths::item_path("diamond_sword").exists());
+        assert!(Paths::item_path("iron_sword").is_file());
+        assert!(!Paths::item_path("stick").exists());
+    }
+
+    #[test]
+    fn test_not_applied_anywhere_is_error() {
+        let _root = TestRoot::new();
+        add_cases("diamond_sword", &["other_sword"]);
+
+        assert!(unextend("ruby_sword", &["diamond_sword", "stick"]).is_err());
+        assert!(
+            read_json::<ItemResource>(&Paths::item_path("diamond_sword"))
+                .unwrap()
+                .has_case("other_sword")
+        );
+    }
+
+    #[test]
+    fn test_failure_partway_leaves_item_files_unchanged() {
+        let root = TestRoot::new();
+        add_cases("diamond_sword", &["ruby_sword"]);
+        add_cases("golden_sword", &["ruby_sword", "other_sword"]);
+        let broken = root.write(&format!("{}/iron_sword.json", Paths::ITEMS), "{");
+        let read = |material: &str| std::fs::read(Paths::item_path(material)).unwrap();
+        let (diamond, golden) = (read("diamond_sword"), read("golden_sword"));
+
+        assert!(
+            unextend(
+                "ruby_sword",
+                &["diamond_sword", "golden_sword", "iron_sword"]
+            )
+            .is_err()
+        );
+
+        assert_eq!(read("diamond_sword"), diamond);
+        assert_eq!(read("golden_sword"), golden);
+        assert_eq!(std::fs::read_to_string(broken).unwrap(), "{");
+    }
+}
@@ -1,17 +1,27 @@
-use std::{path::PathBuf, process::Command};
+use std::path::PathBuf;
 
 use anyhow::Context;
 
+use crate::utils::{
+    archive::ArchiveBuilder, dedupe::DedupePlan, format::format_size, optimize::optimize_file,
+};
+
 /// 📦 リソースパックをZip化
 ///
 /// assetsディレクトリとpack.mcmetaをZipファイルにまとめます。
 /// 生成されたZipファイルはMinecraftのリソースパックとして使用できます。
+/// 同じ入力からは常に同一のZipファイルが生成されます。
 #[derive(clap::Parser, Debug)]
 #[command(
     about = "リソースパックをZip化",
     long_about = "assetsディレクトリとpack.mcmetaをZipファイルにまとめます。\n\n\
                   生成されたZipファイルは、Minecraftのリソースパックとして\n\
-                  そのまま使用できます。"
+                  そのまま使用できます。\n\n\
+                  エントリはパス順に並べられ、更新日時とパーミッションは\n\
+                  固定値に正規化されるため、内容が同じなら同一のZipになります。\n\n\
+                  --dedupe を指定すると同一のテクスチャを1つにまとめ、\n\
+                  --optimize を指定するとPNGを可逆に最適化してから圧縮します\n\
+                  （どちらもリポジトリのファイルは変更しません）。"
 )]
 pub struct Zip {
     /// 出力Zipファイルパス
@@ -33,6 +43,17 @@ pub struct Zip {
         help = "含めるファイル/ディレクトリ"
     )]
     pub files: Vec<String>,
+
+    /// 同一のテクスチャを1つにまとめてから圧縮する
+    #[arg(
+        long,
+        help = "同一のテクスチャを1つにまとめてから圧縮（リポジトリは変更しない）"
+    )]
+    pub dedupe: bool,
+
+    /// PNGを可逆に最適化してから圧縮する
+    #[arg(long, help = "PNGを可逆に最適化してから圧縮（リポジトリは変更しない）")]
+    pub optimize: bool,
 }
 
 impl super::Run for Zip {
@@ -57,27 +78,43 @@ impl super::Run for Zip {
             })?;
         }
 
-        {
-            println!("\n🗜️  圧縮中...");
-            // Build zip command
-            let mut cmd = Command::new("zip");
-            cmd.arg("-r").arg(&self.output);
-
-            // Add all files
-            for file in &self.files {
-                cmd.arg(file);
-            }
-
-            // Execute zip command
-            let output = cmd.output().context("zipコマンドの実行に失敗")?;
-
-            if !output.status.success() {
-                anyhow::bail!(
-                    "❌ Zipの作成に失敗:\n{}",
-                    String::from_utf8_lossy(&output.stderr)
-                );
+        println!("\n🗜️  圧縮中...");
+        let mut builder = ArchiveBuilder::new();
+        for file in &self.files {
+            builder.add_path(std::path::Path::new(file))?;
+        }
+        if self.dedupe {
+            let plan = DedupePlan::scan()?;
+            plan.apply_to_archive(&mut builder)?;
+            println!(
+                "  ♻️  重複テクスチャ {} 個を除外（{} 削減）",
+                plan.groups.iter().map(|g| g.removed.len()).sum::<usize>(),
+                format_size(plan.saved_bytes())
+            );
+        }
+        if self.optimize {
+            let mut count = 0;
+            let mut saved = 0;
+            for path in builder.files() {
+                if path.extension().is_none_or(|ext| ext != "png") {
+                    continue;
+                }
+                if let Some(optimized) = optimize_file(&path)? {
+                    count += 1;
+                    saved += optimized.saved_bytes();
+                    builder.replace_path(&path, optimized.data)?;
+                }
             }
+            println!(
+                "  🪶 {} 個のテクスチャを最適化（{} 削減）",
+                count,
+                format_size(saved)
+            );
         }
+        builder
+            .write(&self.output)
+            .with_context(|| format!("❌ Zipの作成に失敗: {}", self.output.display()))?;
+        println!("  ✓ {} 個のファイルを追加", builder.len());
 
         // Get file size
         let size = std::fs::metadata(&self.output)
@@ -89,18 +126,9 @@ impl super::Run for Zip {
             })?
             .len();
 
-        // Format file size
-        let size_str = if size > 1024 * 1024 {
-            format!("{:.2} MB", size as f64 / (1024.0 * 1024.0))
-        } else if size > 1024 {
-            format!("{:.2} KB", size as f64 / 1024.0)
-        } else {
-            format!("{} bytes", size)
-        };
-
         println!("\n✅ リソースパックを作成しました！");
         println!("  📁 出力: {}", self.output.display());
-        println!("  📊 サイズ: {}\n", size_str);
+        println!("  📊 サイズ: {}\n", format_size(size));
 
         Ok(())
     }
@@ -17,7 +17,7 @@ pub fn main() -> ExitCode {
     match Cmd::parse().run() {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            eprintln!("エラー: {e}");
+            eprintln!("エラー: {e:#}");
             ExitCode::FAILURE
         }
     }
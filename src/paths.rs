@@ -7,14 +7,23 @@ use std::path::PathBuf;
 /// リソースパック内のファイルパスを管理する構造体
 ///
 /// この構造体は、Minecraftリソースパック内の各種ファイルへのパスを
-/// 一元管理します。テスト時には `TEST_ROOT` 環境変数で
+/// 一元管理します。テスト時には [`TestRoot`] または `TEST_ROOT` 環境変数で
 /// ルートディレクトリを上書きできます。
 pub struct Paths;
 
+#[cfg(test)]
+thread_local! {
+    /// テスト中のスレッドで使うルートディレクトリ
+    static TEST_ROOT: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
 impl Paths {
     fn root() -> PathBuf {
         #[cfg(test)]
         {
+            if let Some(test_root) = TEST_ROOT.with(|root| root.borrow().clone()) {
+                return test_root;
+            }
             // In tests, use TEST_ROOT environment variable if set
             if let Ok(test_root) = std::env::var("TEST_ROOT") {
                 return PathBuf::from(test_root);
@@ -42,16 +51,51 @@ impl Paths {
         Self::root().join(Self::ASSETS)
     }
 
+    /// アイテム定義ディレクトリのパスを取得
+    pub fn items_dir() -> PathBuf {
+        Self::root().join(Self::ITEMS)
+    }
+
     /// 指定したマテリアルのアイテム定義ファイルパスを取得
     pub fn item_path(material: &str) -> PathBuf {
         Self::root().join(format!("{}/{material}.json", Self::ITEMS))
     }
 
+    /// モデルディレクトリのパスを取得
+    pub fn models_dir() -> PathBuf {
+        Self::root().join(Self::MODELS)
+    }
+
     /// 指定したカスタムモデルデータのモデルファイルパスを取得
     pub fn model_path(custom_model_data: &str) -> PathBuf {
         Self::root().join(format!("{}/{custom_model_data}.json", Self::MODELS))
     }
 
+    /// 指定したカスタムモデルデータの状態ごとのモデルを置くディレクトリパスを取得
+    pub fn model_path_dir(custom_model_data: &str) -> PathBuf {
+        Self::root().join(format!("{}/{custom_model_data}", Self::MODELS))
+    }
+
+    /// 指定したカスタムモデルデータの状態ごとのモデルファイルパスを取得
+    pub fn state_model_path(custom_model_data: &str, state: &str) -> PathBuf {
+        Self::model_path(&Self::state_name(custom_model_data, state))
+    }
+
+    /// 指定したカスタムモデルデータの状態ごとのテクスチャファイルパスを取得
+    pub fn state_texture_path(custom_model_data: &str, state: &str) -> PathBuf {
+        Self::texture_path(&Self::state_name(custom_model_data, state))
+    }
+
+    /// 状態ごとのモデル名（`<名前>/<状態>`）を取得
+    pub fn state_name(custom_model_data: &str, state: &str) -> String {
+        format!("{custom_model_data}/{state}")
+    }
+
+    /// テクスチャディレクトリのパスを取得
+    pub fn textures_dir() -> PathBuf {
+        Self::root().join(Self::TEXTURES)
+    }
+
     /// 指定したカスタムモデルデータのテクスチャファイルパスを取得
     pub fn texture_path(custom_model_data: &str) -> PathBuf {
         Self::root().join(format!("{}/{custom_model_data}.png", Self::TEXTURES))
@@ -62,8 +106,8 @@ impl Paths {
         Self::root().join(format!("{}/{custom_model_data}", Self::TEXTURES))
     }
 
-    /// 3Dモデルの指定レイヤーのテクスチャファイルパスを取得
-    pub fn texture_layer_path(custom_model_data: &str, layer: usize) -> PathBuf {
+    /// 3Dモデルの指定レイヤー（テクスチャ変数）のテクスチャファイルパスを取得
+    pub fn texture_layer_path(custom_model_data: &str, layer: &str) -> PathBuf {
         Self::root().join(format!(
             "{}/{custom_model_data}/{layer}.png",
             Self::TEXTURES
@@ -75,10 +119,94 @@ impl Paths {
         Self::root().join(format!("{}/{custom_model_data}.png.mcmeta", Self::TEXTURES))
     }
 
+    /// テクスチャ参照（`item/<名前>` など）からリソースパック内の画像パスを取得
+    ///
+    /// `minecraft` 以外の名前空間の参照は `None` です。
+    pub fn texture_reference_path(reference: &str) -> Option<PathBuf> {
+        let path = match reference.split_once(':') {
+            Some(("minecraft", path)) => path,
+            Some(_) => return None,
+            None => reference,
+        };
+        Some(Self::root().join(format!("{}/minecraft/textures/{path}.png", Self::ASSETS)))
+    }
+
     /// プレビュー画像のパスを取得
     pub fn preview_path(custom_model_data: &str) -> PathBuf {
         Self::root().join(format!("{}/{custom_model_data}.png", Self::PREVIEWS))
     }
+
+    /// アニメーションプレビュー画像のパスを取得
+    pub fn animated_preview_path(custom_model_data: &str) -> PathBuf {
+        Self::root().join(format!("{}/{custom_model_data}.gif", Self::PREVIEWS))
+    }
+}
+
+/// テスト用の一時的なリソースパック
+///
+/// 作成したスレッドの [`Paths`] のルートを一時ディレクトリに切り替え、
+/// ドロップ時に元に戻します。テストは並列に実行されるため、
+/// 環境変数ではなくスレッドごとに切り替えます。
+#[cfg(test)]
+pub(crate) struct TestRoot {
+    dir: tempfile::TempDir,
+}
+
+#[cfg(test)]
+impl TestRoot {
+    pub(crate) fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(Paths::ITEMS)).unwrap();
+        std::fs::create_dir_all(dir.path().join(Paths::MODELS)).unwrap();
+        std::fs::create_dir_all(dir.path().join(Paths::TEXTURES)).unwrap();
+        TEST_ROOT.with(|root| *root.borrow_mut() = Some(dir.path().to_path_buf()));
+        Self { dir }
+    }
+
+    /// ルートからの相対パスにファイルを書き込む（親ディレクトリも作成）
+    pub(crate) fn write(&self, relative: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.dir.path().join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// `name` のモデル・テクスチャと、それを参照する `materials` のアイテム定義を作成
+    ///
+    /// アイテム定義が既にある場合はケースを追加します。
+    pub(crate) fn add_model(&self, name: &str, materials: &[&str]) {
+        use crate::{
+            schema::items::{ItemCase, ItemResource},
+            utils::json::{read_json, write_json},
+        };
+
+        write_json(
+            &Paths::model_path(name),
+            &serde_json::json!({
+                "parent": "minecraft:item/generated",
+                "textures": {"layer0": format!("minecraft:item/{name}")}
+            }),
+        )
+        .unwrap();
+        std::fs::write(Paths::texture_path(name), "png").unwrap();
+        for material in materials {
+            let path = Paths::item_path(material);
+            let mut resource = if path.exists() {
+                read_json::<ItemResource>(&path).unwrap()
+            } else {
+                ItemResource::new_with_fallback(&format!("minecraft:item/{material}"))
+            };
+            resource.add_case(ItemCase::new(name));
+            write_json(&path, &resource).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestRoot {
+    fn drop(&mut self) {
+        TEST_ROOT.with(|root| *root.borrow_mut() = None);
+    }
 }
 
 #[cfg(test)]
@@ -106,10 +234,26 @@ mod tests {
         assert!(path.to_string_lossy().contains("textures"));
     }
 
+    #[test]
+    fn test_state_paths() {
+        let model = Paths::state_model_path("my_bow", "pulling_0");
+        assert!(model.ends_with("models/item/my_bow/pulling_0.json"));
+        let texture = Paths::state_texture_path("my_bow", "pulling_0");
+        assert!(texture.ends_with("textures/item/my_bow/pulling_0.png"));
+    }
+
     #[test]
     fn test_texture_layer_path() {
-        let path = Paths::texture_layer_path("my_model", 0);
+        let path = Paths::texture_layer_path("my_model", "0");
         assert!(path.to_string_lossy().contains("my_model"));
         assert!(path.to_string_lossy().contains("0.png"));
     }
+
+    #[test]
+    fn test_texture_reference_path() {
+        let path = Paths::texture_reference_path("minecraft:item/my_model/0").unwrap();
+        assert!(path.ends_with("assets/minecraft/textures/item/my_model/0.png"));
+        assert_eq!(Paths::texture_reference_path("item/my_model/0"), Some(path));
+        assert!(Paths::texture_reference_path("other:item/x").is_none());
+    }
 }
@@ -0,0 +1,642 @@
+//! Blockbench プロジェクト（`.bbmodel`）の変換
+//!
+//! Blockbench のプロジェクトファイルを読み込み、要素・グループ・表示位置を
+//! Java版のアイテムモデルに変換し、埋め込まれたテクスチャ（base64）を取り出します。
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    schema::models::{
+        Axis, Direction, Display, Element, ElementRotation, Face, Faces, Group, GroupChild,
+        GuiLight, ItemModel,
+    },
+    types::LayerTexture,
+    utils::json::read_json,
+    validation::is_snake_case,
+};
+
+/// Blockbench プロジェクト
+#[derive(Deserialize, Debug)]
+pub struct BbModel {
+    /// 親モデル
+    #[serde(default)]
+    parent: String,
+    /// 環境光による陰影をつけるか
+    #[serde(default)]
+    ambientocclusion: Option<bool>,
+    /// GUIで正面から照らすか
+    #[serde(default)]
+    front_gui_light: bool,
+    /// プロジェクトのテクスチャサイズ
+    #[serde(default)]
+    resolution: Resolution,
+    #[serde(default)]
+    elements: Vec<BbElement>,
+    #[serde(default)]
+    outliner: Vec<OutlinerNode>,
+    #[serde(default)]
+    textures: Vec<BbTexture>,
+    /// 表示位置ごとの変形（Java版と同じ形式）
+    #[serde(default)]
+    display: Map<String, Value>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+struct Resolution {
+    width: f64,
+    height: f64,
+}
+
+impl Default for Resolution {
+    fn default() -> Self {
+        Self {
+            width: 16.0,
+            height: 16.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct BbElement {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type", default = "cube")]
+    kind: String,
+    #[serde(default)]
+    uuid: String,
+    #[serde(default = "yes")]
+    export: bool,
+    #[serde(default)]
+    from: [f64; 3],
+    #[serde(default)]
+    to: [f64; 3],
+    #[serde(default)]
+    inflate: f64,
+    #[serde(default)]
+    origin: [f64; 3],
+    #[serde(default)]
+    rotation: [f64; 3],
+    #[serde(default)]
+    rescale: bool,
+    #[serde(default = "yes")]
+    shade: bool,
+    #[serde(default)]
+    light_emission: u8,
+    #[serde(default)]
+    faces: BTreeMap<String, BbFace>,
+}
+
+#[derive(Deserialize, Debug)]
+struct BbFace {
+    #[serde(default)]
+    uv: Option<[f64; 4]>,
+    /// テクスチャのインデックス（新しい形式では UUID）。`null` の面は書き出さない
+    #[serde(default)]
+    texture: Value,
+    #[serde(default)]
+    rotation: u32,
+    #[serde(default)]
+    cullface: Option<String>,
+    #[serde(default = "no_tint")]
+    tint: i32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum OutlinerNode {
+    /// 要素の UUID
+    Element(String),
+    Group(BbGroup),
+}
+
+#[derive(Deserialize, Debug)]
+struct BbGroup {
+    name: String,
+    #[serde(default)]
+    origin: [f64; 3],
+    #[serde(default)]
+    color: u32,
+    #[serde(default = "yes")]
+    export: bool,
+    #[serde(default)]
+    children: Vec<OutlinerNode>,
+}
+
+#[derive(Deserialize, Debug)]
+struct BbTexture {
+    #[serde(default)]
+    name: String,
+    /// Java版で書き出すときのテクスチャ変数
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    uuid: String,
+    #[serde(default)]
+    particle: bool,
+    /// UVの基準サイズ（省略時はプロジェクトのサイズ）
+    #[serde(default)]
+    uv_width: Option<f64>,
+    #[serde(default)]
+    uv_height: Option<f64>,
+    /// `data:image/png;base64,...`
+    #[serde(default)]
+    source: String,
+}
+
+fn cube() -> String {
+    "cube".to_string()
+}
+
+const fn yes() -> bool {
+    true
+}
+
+const fn no_tint() -> i32 {
+    -1
+}
+
+impl BbModel {
+    /// `.bbmodel` ファイルを読み込む
+    ///
+    /// # Errors
+    ///
+    /// ファイルが読み込めない、またはJSONとしてパースできない場合
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        read_json(path).with_context(|| {
+            format!(
+                "Blockbench プロジェクトのパースに失敗: {}",
+                path.to_string_lossy()
+            )
+        })
+    }
+
+    /// テクスチャのインデックスごとのテクスチャ変数
+    ///
+    /// Blockbench と同じく、`id` が設定されていればそれを、なければ番号を使います。
+    /// テクスチャ変数はファイル名にも使うため、スネークケースでない `id` はエラーにし、
+    /// 重複する場合は後のテクスチャに番号を付けて区別します。
+    ///
+    /// # Errors
+    ///
+    /// `id` がスネークケースでない場合
+    fn texture_keys(&self) -> anyhow::Result<Vec<String>> {
+        // particle は write_textures で別名として使う
+        let mut used = HashSet::from(["particle".to_string()]);
+        let mut keys = Vec::with_capacity(self.textures.len());
+        for (index, texture) in self.textures.iter().enumerate() {
+            let key = match texture.id.trim() {
+                "" => index.to_string(),
+                id if is_snake_case(id) => id.to_string(),
+                id => anyhow::bail!(
+                    "テクスチャ '{}' のID '{}' はスネークケースで指定してください",
+                    texture.name,
+                    id
+                ),
+            };
+            let mut unique = key.clone();
+            let mut suffix = index;
+            while used.contains(&unique) {
+                unique = format!("{key}_{suffix}");
+                suffix += 1;
+            }
+            used.insert(unique.clone());
+            keys.push(unique);
+        }
+        Ok(keys)
+    }
+
+    /// 面が参照しているテクスチャのインデックス
+    fn face_texture(&self, face: &BbFace) -> Option<usize> {
+        match &face.texture {
+            Value::Number(n) => n
+                .as_u64()
+                .and_then(|i| usize::try_from(i).ok())
+                .filter(|&i| i < self.textures.len()),
+            Value::String(uuid) => self.textures.iter().position(|t| &t.uuid == uuid),
+            _ => None,
+        }
+    }
+
+    /// Java版のアイテムモデルに変換
+    ///
+    /// テクスチャ変数の値はテクスチャ名のままなので、
+    /// [`Self::write_textures`] で取り出した画像と対応付けて差し替えてください。
+    ///
+    /// # Errors
+    ///
+    /// - メッシュなどJava版で表現できない要素が含まれている場合
+    /// - 要素が2軸以上で回転している場合
+    /// - 表示位置の変形が不正な場合
+    pub fn to_item_model(&self) -> anyhow::Result<ItemModel> {
+        let mut model = ItemModel::default();
+
+        if !self.parent.is_empty() {
+            model.parent = Some(self.parent.clone());
+        }
+        model.ambientocclusion = self.ambientocclusion;
+        if self.front_gui_light {
+            model.gui_light = Some(GuiLight::Front);
+        }
+
+        let keys = self.texture_keys()?;
+        for (key, texture) in keys.iter().zip(&self.textures) {
+            model.textures.set(key, &texture.name);
+        }
+
+        let mut elements = Vec::new();
+        let mut element_indices = HashMap::new();
+        for element in &self.elements {
+            match element.kind.as_str() {
+                "cube" => {}
+                // 位置の目印で、形状を持たない
+                "locator" | "null_object" => continue,
+                kind => anyhow::bail!(
+                    "要素 '{}' の種類 '{}' はJava版のモデルに変換できません（直方体のみ対応しています）",
+                    element.name,
+                    kind
+                ),
+            }
+            if !element.export {
+                continue;
+            }
+            element_indices.insert(element.uuid.as_str(), elements.len());
+            elements.push(self.convert_element(element, &keys)?);
+        }
+        if elements.is_empty() {
+            anyhow::bail!("書き出す要素（直方体）がありません");
+        }
+        model.elements = Some(elements);
+
+        let groups = self
+            .outliner
+            .iter()
+            .filter_map(|node| convert_outliner(node, &element_indices))
+            .collect::<Vec<_>>();
+        if !groups.is_empty() {
+            model.groups = Some(groups);
+        }
+
+        if !self.display.is_empty() {
+            let display = self
+                .display
+                .iter()
+                .map(|(context, transform)| (context.clone(), convert_transform(transform)))
+                .collect::<Map<_, _>>();
+            model.display = Some(
+                serde_json::from_value::<Display>(Value::Object(display))
+                    .context("display の変換に失敗")?,
+            );
+        }
+
+        Ok(model)
+    }
+
+    fn convert_element(&self, element: &BbElement, keys: &[String]) -> anyhow::Result<Element> {
+        let inflate = element.inflate;
+        let mut converted = Element {
+            from: element.from.map(|v| v - inflate),
+            to: element.to.map(|v| v + inflate),
+            rotation: convert_rotation(element)?,
+            shade: (!element.shade).then_some(false),
+            light_emission: (element.light_emission > 0).then_some(element.light_emission),
+            faces: Faces::default(),
+            extra: Map::new(),
+        };
+        if !element.name.is_empty() {
+            converted
+                .extra
+                .insert("name".to_string(), Value::String(element.name.clone()));
+        }
+
+        for direction in Direction::ALL {
+            let Some(face) = element.faces.get(direction.as_str()) else {
+                continue;
+            };
+            let Some(texture) = self.face_texture(face) else {
+                continue;
+            };
+            let (uv_width, uv_height) = (
+                self.textures[texture]
+                    .uv_width
+                    .unwrap_or(self.resolution.width),
+                self.textures[texture]
+                    .uv_height
+                    .unwrap_or(self.resolution.height),
+            );
+            let cullface = face
+                .cullface
+                .as_deref()
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    Direction::ALL
+                        .into_iter()
+                        .find(|d| d.as_str() == name)
+                        .with_context(|| {
+                            format!("要素 '{}' の cullface '{}' が不正です", element.name, name)
+                        })
+                })
+                .transpose()?;
+
+            converted.faces.set(
+                direction,
+                Face {
+                    uv: face.uv.map(|[x1, y1, x2, y2]| {
+                        [
+                            x1 * 16.0 / uv_width,
+                            y1 * 16.0 / uv_height,
+                            x2 * 16.0 / uv_width,
+                            y2 * 16.0 / uv_height,
+                        ]
+                    }),
+                    rotation: (face.rotation != 0).then_some(face.rotation),
+                    texture: format!("#{}", keys[texture]),
+                    cullface,
+                    tintindex: (face.tint >= 0).then_some(face.tint),
+                    extra: Map::new(),
+                },
+            );
+        }
+
+        Ok(converted)
+    }
+
+    /// 埋め込まれたテクスチャをPNGとして書き出す
+    ///
+    /// # Returns
+    ///
+    /// テクスチャ変数ごとの画像（`particle` に指定されたテクスチャがあれば、その別名を含む）
+    ///
+    /// # Errors
+    ///
+    /// - テクスチャの `id` がスネークケースでない場合
+    /// - テクスチャが埋め込まれていない場合
+    /// - base64 のデコードや書き込みに失敗した場合
+    pub fn write_textures(&self, dir: &Path) -> anyhow::Result<Vec<LayerTexture>> {
+        let keys = self.texture_keys()?;
+        let mut layers = Vec::new();
+
+        for (key, texture) in keys.iter().zip(&self.textures) {
+            let data = texture
+                .source
+                .strip_prefix("data:image/png;base64,")
+                .with_context(|| {
+                    format!(
+                        "テクスチャ '{}' がPNGとして埋め込まれていません（Blockbench でテクスチャを保存してからプロジェクトを保存してください）",
+                        texture.name
+                    )
+                })?;
+            let bytes = STANDARD
+                .decode(data.trim())
+                .with_context(|| format!("テクスチャ '{}' のデコードに失敗", texture.name))?;
+
+            let path: PathBuf = dir.join(format!("{key}.png"));
+            std::fs::write(&path, bytes).with_context(|| {
+                format!("テクスチャの書き込みに失敗: {}", path.to_string_lossy())
+            })?;
+
+            layers.push(LayerTexture {
+                key: key.clone(),
+                source: path.to_string_lossy().into_owned(),
+            });
+        }
+
+        if let Some(index) = self.textures.iter().position(|t| t.particle) {
+            layers.push(LayerTexture {
+                key: "particle".to_string(),
+                source: keys[index].clone(),
+            });
+        }
+
+        Ok(layers)
+    }
+}
+
+/// 要素の回転を変換（Java版は1軸のみ）
+fn convert_rotation(element: &BbElement) -> anyhow::Result<Option<ElementRotation>> {
+    let rotated = [Axis::X, Axis::Y, Axis::Z]
+        .into_iter()
+        .filter(|axis| element.rotation[axis.index()] != 0.0)
+        .collect::<Vec<_>>();
+
+    match rotated.as_slice() {
+        [] => Ok(None),
+        [axis] => Ok(Some(ElementRotation {
+            angle: element.rotation[axis.index()],
+            axis: *axis,
+            origin: element.origin,
+            rescale: element.rescale.then_some(true),
+            extra: Map::new(),
+        })),
+        _ => anyhow::bail!(
+            "要素 '{}' が複数の軸で回転しています（Java版のモデルは1軸のみ回転できます）",
+            element.name
+        ),
+    }
+}
+
+/// アウトライナーをグループに変換（書き出さない要素・グループは除く）
+fn convert_outliner(node: &OutlinerNode, indices: &HashMap<&str, usize>) -> Option<GroupChild> {
+    match node {
+        OutlinerNode::Element(uuid) => indices.get(uuid.as_str()).copied().map(GroupChild::Element),
+        OutlinerNode::Group(group) if group.export => {
+            let children = group
+                .children
+                .iter()
+                .filter_map(|child| convert_outliner(child, indices))
+                .collect();
+            Some(GroupChild::Group(Group {
+                name: group.name.clone(),
+                origin: Some(group.origin),
+                color: Some(group.color),
+                children,
+                extra: Map::new(),
+            }))
+        }
+        OutlinerNode::Group(_) => None,
+    }
+}
+
+/// 表示位置の変形から Java版で使うフィールドだけを取り出す
+///
+/// Blockbench は `rotation_pivot` や `mirror` などの編集用の値も保存します。
+fn convert_transform(transform: &Value) -> Value {
+    let fields = ["rotation", "translation", "scale"]
+        .into_iter()
+        .filter_map(|field| Some((field.to_string(), transform.get(field)?.clone())))
+        .collect::<Map<_, _>>();
+    Value::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> BbModel {
+        serde_json::from_value(serde_json::json!({
+            "meta": { "format_version": "4.10", "model_format": "java_block" },
+            "resolution": { "width": 32, "height": 32 },
+            "elements": [
+                {
+                    "name": "blade",
+                    "type": "cube",
+                    "uuid": "a",
+                    "from": [7, 0, 7],
+                    "to": [9, 16, 9],
+                    "origin": [8, 8, 8],
+                    "rotation": [0, 0, 45],
+                    "faces": {
+                        "north": { "uv": [0, 0, 32, 16], "texture": 0, "rotation": 90 },
+                        "south": { "uv": [0, 0, 4, 4], "texture": 1, "cullface": "south", "tint": 0 },
+                        "up": { "uv": [0, 0, 4, 4], "texture": null }
+                    }
+                },
+                { "name": "hidden", "uuid": "b", "export": false, "from": [0, 0, 0], "to": [1, 1, 1] },
+                { "name": "marker", "type": "locator", "uuid": "c" }
+            ],
+            "outliner": [
+                { "name": "sword", "origin": [8, 8, 8], "uuid": "g", "children": ["a", "b"] }
+            ],
+            "textures": [
+                { "name": "blade.png", "id": "blade", "particle": true, "source": "data:image/png;base64,iVBORw0KGgo=" },
+                { "name": "handle.png", "uuid": "t1", "uv_width": 16, "uv_height": 16, "source": "data:image/png;base64,iVBORw0KGgo=" }
+            ],
+            "display": {
+                "gui": { "rotation": [30, 225, 0], "scale": [0.6, 0.6, 0.6], "rotation_pivot": [0, 0, 0] }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_convert_elements() {
+        let model = project().to_item_model().unwrap();
+        let elements = model.elements();
+        assert_eq!(elements.len(), 1);
+
+        let blade = &elements[0];
+        let rotation = blade.rotation.as_ref().unwrap();
+        assert_eq!(rotation.axis, Axis::Z);
+        assert_eq!(rotation.angle, 45.0);
+        assert_eq!(rotation.origin, [8.0, 8.0, 8.0]);
+
+        // プロジェクトの解像度（32x32）を基準に 0〜16 に変換する
+        let north = blade.faces.get(Direction::North).unwrap();
+        assert_eq!(north.uv, Some([0.0, 0.0, 16.0, 8.0]));
+        assert_eq!(north.rotation, Some(90));
+        assert_eq!(north.texture, "#blade");
+
+        // テクスチャごとの UV サイズを優先する
+        let south = blade.faces.get(Direction::South).unwrap();
+        assert_eq!(south.uv, Some([0.0, 0.0, 4.0, 4.0]));
+        assert_eq!(south.texture, "#1");
+        assert_eq!(south.cullface, Some(Direction::South));
+        assert_eq!(south.tintindex, Some(0));
+
+        // テクスチャのない面は書き出さない
+        assert!(blade.faces.get(Direction::Up).is_none());
+
+        assert_eq!(model.textures.get("blade"), Some("blade.png"));
+        assert_eq!(model.textures.get("1"), Some("handle.png"));
+    }
+
+    #[test]
+    fn test_convert_groups_and_display() {
+        let model = project().to_item_model().unwrap();
+
+        let groups = model.groups.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].element_indices(), vec![0]);
+
+        let gui = model.display.unwrap().gui.unwrap();
+        assert_eq!(gui.rotation, Some([30.0, 225.0, 0.0]));
+        assert_eq!(gui.scale, Some([0.6, 0.6, 0.6]));
+        assert!(gui.extra.is_empty());
+    }
+
+    #[test]
+    fn test_multi_axis_rotation_is_error() {
+        let mut project = project();
+        project.elements[0].rotation = [22.5, 0.0, 45.0];
+        assert!(project.to_item_model().is_err());
+    }
+
+    #[test]
+    fn test_mesh_is_error() {
+        let mut project = project();
+        project.elements[0].kind = "mesh".to_string();
+        assert!(project.to_item_model().is_err());
+    }
+
+    #[test]
+    fn test_write_textures() {
+        let dir = tempfile::tempdir().unwrap();
+        let layers = project().write_textures(dir.path()).unwrap();
+
+        let keys = layers.iter().map(|l| l.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, vec!["blade", "1", "particle"]);
+        assert_eq!(
+            std::fs::read(dir.path().join("blade.png")).unwrap(),
+            b"\x89PNG\r\n\x1a\n"
+        );
+        assert_eq!(layers[2].source, "blade");
+    }
+
+    #[test]
+    fn test_texture_not_embedded_is_error() {
+        let mut project = project();
+        project.textures[0].source = String::new();
+        let dir = tempfile::tempdir().unwrap();
+        assert!(project.write_textures(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_malicious_texture_id_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let textures = dir.path().join("textures");
+        std::fs::create_dir(&textures).unwrap();
+
+        for id in ["../../../x", "blade/../../x", "Blade"] {
+            let mut project = project();
+            project.textures[0].id = id.to_string();
+            assert!(project.to_item_model().is_err(), "{id}");
+            assert!(project.write_textures(&textures).is_err(), "{id}");
+        }
+        // 何も書き出さない
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(std::fs::read_dir(&textures).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_texture_ids() {
+        let mut project = project();
+        project.textures[1].id = "blade".to_string();
+        project.textures.push(
+            serde_json::from_value(serde_json::json!({
+                "name": "guard.png",
+                "id": "particle",
+                "source": "data:image/png;base64,iVBORw0KGgo="
+            }))
+            .unwrap(),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let layers = project.write_textures(dir.path()).unwrap();
+        let keys = layers.iter().map(|l| l.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, vec!["blade", "blade_1", "particle_2", "particle"]);
+        for key in ["blade", "blade_1", "particle_2"] {
+            assert!(dir.path().join(format!("{key}.png")).is_file(), "{key}");
+        }
+
+        let model = project.to_item_model().unwrap();
+        let south = model.elements()[0].faces.get(Direction::South).unwrap();
+        assert_eq!(south.texture, "#blade_1");
+    }
+}
@@ -0,0 +1,290 @@
+//! フレーム画像の結合
+//!
+//! GIFアニメーションやフレームごとのPNG画像を、Minecraftの
+//! アニメーションテクスチャ形式（フレームを縦に並べた1枚の画像）に結合します。
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use image::{AnimationDecoder, ImageFormat, RgbaImage, codecs::gif::GifDecoder};
+
+use crate::schema::animation::{AnimationData, AnimationFrame};
+
+/// 1 tick のミリ秒数
+const MS_PER_TICK: f64 = 50.0;
+
+/// 結合したアニメーションテクスチャ
+pub struct StitchedAnimation {
+    /// フレームを縦に並べた画像
+    pub image: RgbaImage,
+    /// `.mcmeta` に書き込むアニメーション設定
+    pub animation: AnimationData,
+}
+
+impl StitchedAnimation {
+    /// ディレクトリ内のPNG画像、またはGIFアニメーションからフレームを読み込んで結合
+    ///
+    /// ディレクトリの場合は、ファイル名の数字部分を数値として比較した順に並べ、
+    /// すべてのフレームを同じtick数（`frametime` 省略時は1）で表示します。
+    /// GIFの場合は各フレームの表示時間をtick数に変換します。
+    ///
+    /// # Errors
+    ///
+    /// - フレームが読み込めない場合
+    /// - フレームのサイズが揃っていない場合
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if path.is_dir() {
+            Self::from_dir(path)
+        } else {
+            Self::from_gif(path)
+        }
+    }
+
+    /// フレームごとのPNG画像を含むディレクトリから読み込む
+    ///
+    /// # Errors
+    ///
+    /// - PNG画像が1つも含まれていない場合
+    /// - 画像が読み込めない、またはサイズが揃っていない場合
+    pub fn from_dir(dir: &Path) -> anyhow::Result<Self> {
+        let mut paths = std::fs::read_dir(dir)
+            .with_context(|| format!("フレームディレクトリの読み込みに失敗: {}", dir.display()))?
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+            })
+            .collect::<Vec<_>>();
+        paths.sort_by_key(|path| natural_key(path));
+
+        if paths.is_empty() {
+            anyhow::bail!(
+                "フレームディレクトリにPNG画像がありません: {}",
+                dir.display()
+            );
+        }
+
+        let frames = paths
+            .iter()
+            .map(|path| {
+                image::open(path)
+                    .map(|img| img.to_rgba8())
+                    .with_context(|| format!("フレーム画像の読み込みに失敗: {}", path.display()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Self::stitch(frames, AnimationData::with_frametime(1))
+    }
+
+    /// GIFアニメーションから読み込む
+    ///
+    /// # Errors
+    ///
+    /// - GIFとしてデコードできない場合
+    pub fn from_gif(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("GIFファイルの読み込みに失敗: {}", path.display()))?;
+        let format = image::ImageReader::new(BufReader::new(&file))
+            .with_guessed_format()
+            .context("画像形式を判定できませんでした")?
+            .format();
+        if format != Some(ImageFormat::Gif) {
+            anyhow::bail!(
+                "--frames にはディレクトリまたはGIF画像を指定してください: {}",
+                path.display()
+            );
+        }
+
+        let decoder =
+            GifDecoder::new(BufReader::new(file)).context("GIFのデコードに失敗しました")?;
+        let frames = decoder
+            .into_frames()
+            .collect_frames()
+            .context("GIFのフレームのデコードに失敗しました")?;
+
+        let times: Vec<u32> = frames
+            .iter()
+            .map(|frame| {
+                let (numer, denom) = frame.delay().numer_denom_ms();
+                delay_to_ticks(f64::from(numer) / f64::from(denom.max(1)))
+            })
+            .collect();
+        let images = frames.into_iter().map(image::Frame::into_buffer).collect();
+
+        Self::stitch(images, animation_from_times(&times))
+    }
+
+    /// フレームを縦に並べて1枚の画像にする
+    fn stitch(frames: Vec<RgbaImage>, mut animation: AnimationData) -> anyhow::Result<Self> {
+        let Some(first) = frames.first() else {
+            anyhow::bail!("フレームが1つもありません");
+        };
+        let (width, height) = first.dimensions();
+        if let Some(index) = frames
+            .iter()
+            .position(|frame| frame.dimensions() != (width, height))
+        {
+            let (w, h) = frames[index].dimensions();
+            anyhow::bail!(
+                "フレーム {index} のサイズ({w}x{h})が最初のフレーム({width}x{height})と異なります"
+            );
+        }
+
+        let count = u32::try_from(frames.len()).context("フレーム数が多すぎます")?;
+        let mut image = RgbaImage::new(width, height * count);
+        for (i, frame) in (0..count).zip(&frames) {
+            image::imageops::replace(&mut image, frame, 0, i64::from(i * height));
+        }
+
+        // 正方形でないフレームは、フレームサイズを明示しないと正しく分割されない
+        if width != height {
+            animation.width = Some(width);
+            animation.height = Some(height);
+        }
+
+        Ok(Self { image, animation })
+    }
+
+    /// フレーム数
+    #[must_use]
+    pub fn frame_count(&self) -> u32 {
+        self.animation
+            .frame_count(self.image.width(), self.image.height())
+            .unwrap_or(1)
+    }
+}
+
+/// GIFの表示時間（ミリ秒）をtick数に変換（最低1tick）
+fn delay_to_ticks(ms: f64) -> u32 {
+    // 表示時間の上限を考えると u32 に収まる
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let ticks = (ms / MS_PER_TICK).round() as u32;
+    ticks.max(1)
+}
+
+/// フレームごとのtick数からアニメーション設定を作成
+///
+/// すべて同じ場合は `frametime` のみ、異なる場合は最も多いtick数を
+/// `frametime` にして、それ以外のフレームに個別のtick数を指定します。
+fn animation_from_times(times: &[u32]) -> AnimationData {
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for &time in times {
+        *counts.entry(time).or_default() += 1;
+    }
+    let frametime = counts
+        .into_iter()
+        .max_by_key(|&(time, count)| (count, std::cmp::Reverse(time)))
+        .map_or(1, |(time, _)| time);
+
+    let mut animation = AnimationData::with_frametime(frametime);
+    if times.iter().any(|&time| time != frametime) {
+        animation.frames = Some(
+            (0..)
+                .zip(times)
+                .map(|(index, &time)| {
+                    if time == frametime {
+                        AnimationFrame::Index(index)
+                    } else {
+                        AnimationFrame::Timed { index, time }
+                    }
+                })
+                .collect(),
+        );
+    }
+    animation
+}
+
+/// ファイル名の数字部分を数値として比較するためのキー（`frame_2` < `frame_10`）
+fn natural_key(path: &Path) -> (String, u64, PathBuf) {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let prefix = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = stem[prefix.len()..].parse().unwrap_or(0);
+    (prefix.to_string(), number, path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_to_ticks() {
+        assert_eq!(delay_to_ticks(100.0), 2);
+        assert_eq!(delay_to_ticks(70.0), 1);
+        assert_eq!(delay_to_ticks(0.0), 1);
+    }
+
+    #[test]
+    fn test_animation_from_times() {
+        let animation = animation_from_times(&[2, 2, 2]);
+        assert_eq!(animation, AnimationData::with_frametime(2));
+
+        let animation = animation_from_times(&[2, 10, 2]);
+        assert_eq!(animation.frametime, 2);
+        assert_eq!(
+            animation.frames,
+            Some(vec![
+                AnimationFrame::Index(0),
+                AnimationFrame::Timed { index: 1, time: 10 },
+                AnimationFrame::Index(2),
+            ])
+        );
+        assert_eq!(animation.total_duration(3), 14);
+    }
+
+    #[test]
+    fn test_stitch_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        for (i, name) in ["frame_10", "frame_2", "frame_1"].iter().enumerate() {
+            let value = u8::try_from(i).unwrap();
+            RgbaImage::from_pixel(16, 16, image::Rgba([value, 0, 0, 255]))
+                .save(dir.path().join(format!("{name}.png")))
+                .unwrap();
+        }
+
+        let stitched = StitchedAnimation::from_dir(dir.path()).unwrap();
+        assert_eq!(stitched.image.dimensions(), (16, 48));
+        assert_eq!(stitched.frame_count(), 3);
+        // frame_1, frame_2, frame_10 の順に並ぶ
+        assert_eq!(stitched.image.get_pixel(0, 0)[0], 2);
+        assert_eq!(stitched.image.get_pixel(0, 16)[0], 1);
+        assert_eq!(stitched.image.get_pixel(0, 32)[0], 0);
+    }
+
+    #[test]
+    fn test_stitch_from_gif() {
+        let file = tempfile::Builder::new().suffix(".gif").tempfile().unwrap();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(file.reopen().unwrap());
+            for delay in [100, 500, 100] {
+                let frame = image::Frame::from_parts(
+                    RgbaImage::from_pixel(16, 16, image::Rgba([255, 0, 0, 255])),
+                    0,
+                    0,
+                    image::Delay::from_numer_denom_ms(delay, 1),
+                );
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+
+        let stitched = StitchedAnimation::from_gif(file.path()).unwrap();
+        assert_eq!(stitched.image.dimensions(), (16, 48));
+        assert_eq!(stitched.animation.frametime, 2);
+        assert_eq!(stitched.animation.total_duration(3), 14);
+    }
+
+    #[test]
+    fn test_stitch_rejects_mismatched_sizes() {
+        let frames = vec![RgbaImage::new(16, 16), RgbaImage::new(32, 32)];
+        assert!(StitchedAnimation::stitch(frames, AnimationData::with_frametime(1)).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! 画像形式の変換
+//!
+//! WebP・GIF・BMP・JPEG の画像を、リソースパックで使えるRGBAのPNGに変換します。
+//! GIFは最初のフレームのみを変換します（アニメーションは `frame_stitcher` で結合します）。
+
+use std::path::Path;
+
+use anyhow::Context;
+use image::ImageFormat;
+
+/// PNGに変換できる入力形式
+pub const SUPPORTED_FORMATS: [ImageFormat; 5] = [
+    ImageFormat::Png,
+    ImageFormat::WebP,
+    ImageFormat::Gif,
+    ImageFormat::Bmp,
+    ImageFormat::Jpeg,
+];
+
+/// 変換元の画像
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceImage {
+    /// 検出された形式
+    pub format: ImageFormat,
+    /// 非可逆圧縮の画像か（JPEG、非可逆の WebP）
+    pub lossy: bool,
+}
+
+impl SourceImage {
+    /// 画像データの形式を判定
+    ///
+    /// # Errors
+    ///
+    /// 形式を判定できない、または対応していない形式の場合
+    pub fn detect(data: &[u8]) -> anyhow::Result<Self> {
+        let format = image::guess_format(data).context("画像形式を判定できませんでした")?;
+        if !SUPPORTED_FORMATS.contains(&format) {
+            anyhow::bail!(
+                "対応していない画像形式です（検出された形式: {format:?}、対応形式: PNG, WebP, GIF, BMP, JPEG）"
+            );
+        }
+        let lossy = match format {
+            ImageFormat::Jpeg => true,
+            ImageFormat::WebP => is_lossy_webp(data),
+            _ => false,
+        };
+        Ok(Self { format, lossy })
+    }
+
+    /// 形式の表示名
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self.format {
+            ImageFormat::Png => "PNG",
+            ImageFormat::WebP => "WebP",
+            ImageFormat::Gif => "GIF",
+            ImageFormat::Bmp => "BMP",
+            ImageFormat::Jpeg => "JPEG",
+            _ => "不明",
+        }
+    }
+}
+
+/// 画像ファイルをRGBAのPNGに変換して書き出す
+///
+/// # Returns
+///
+/// 変換元の画像の情報
+///
+/// # Errors
+///
+/// 画像の読み込み・デコード、またはPNGの書き込みに失敗した場合
+pub fn convert_to_png(input: &Path, output: &Path) -> anyhow::Result<SourceImage> {
+    let data = std::fs::read(input)
+        .with_context(|| format!("画像ファイルの読み込みに失敗しました: {}", input.display()))?;
+    let source = SourceImage::detect(&data)?;
+    let image = image::load_from_memory_with_format(&data, source.format)
+        .with_context(|| format!("画像のデコードに失敗しました: {}", input.display()))?
+        .to_rgba8();
+    image
+        .save_with_format(output, ImageFormat::Png)
+        .with_context(|| format!("PNGの書き込みに失敗しました: {}", output.display()))?;
+    Ok(source)
+}
+
+/// 非可逆圧縮（`VP8 ` チャンク）の WebP か
+///
+/// 拡張形式（`VP8X`）の場合は後続のチャンクから判定します。
+fn is_lossy_webp(data: &[u8]) -> bool {
+    let mut offset = 12;
+    while let Some(header) = data.get(offset..offset + 8) {
+        match &header[..4] {
+            b"VP8 " => return true,
+            b"VP8L" => return false,
+            _ => {}
+        }
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        offset += 8 + size + size % 2;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    fn webp_with_chunks(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut body = b"WEBP".to_vec();
+        for (fourcc, data) in chunks {
+            body.extend_from_slice(*fourcc);
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        let mut out = b"RIFF".to_vec();
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend(body);
+        out
+    }
+
+    #[test]
+    fn test_is_lossy_webp() {
+        assert!(is_lossy_webp(&webp_with_chunks(&[(b"VP8 ", &[0; 4])])));
+        assert!(!is_lossy_webp(&webp_with_chunks(&[(b"VP8L", &[0; 4])])));
+        assert!(is_lossy_webp(&webp_with_chunks(&[
+            (b"VP8X", &[0; 10]),
+            (b"ALPH", &[0; 3]),
+            (b"VP8 ", &[0; 4]),
+        ])));
+        assert!(!is_lossy_webp(&webp_with_chunks(&[
+            (b"VP8X", &[0; 10]),
+            (b"VP8L", &[0; 4]),
+        ])));
+    }
+
+    #[test]
+    fn test_convert_to_png() {
+        let image = RgbaImage::from_fn(4, 4, |x, y| Rgba([x as u8 * 60, y as u8 * 60, 0, 255]));
+        let dir = tempfile::tempdir().unwrap();
+
+        for (format, lossy) in [
+            (ImageFormat::Png, false),
+            (ImageFormat::Bmp, false),
+            (ImageFormat::Gif, false),
+            (ImageFormat::WebP, false),
+        ] {
+            let input = dir
+                .path()
+                .join(format!("input.{}", format.extensions_str()[0]));
+            image.save_with_format(&input, format).unwrap();
+            let output = dir.path().join("output.png");
+
+            let source = convert_to_png(&input, &output).unwrap();
+            assert_eq!(source, SourceImage { format, lossy });
+            let converted = image::open(&output).unwrap();
+            assert_eq!(
+                image::guess_format(&std::fs::read(&output).unwrap()).unwrap(),
+                ImageFormat::Png
+            );
+            assert_eq!(converted.to_rgba8(), image, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_convert_jpeg_is_lossy() {
+        let image = image::RgbImage::from_pixel(8, 8, image::Rgb([200, 100, 50]));
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.jpg");
+        image.save_with_format(&input, ImageFormat::Jpeg).unwrap();
+
+        let source = convert_to_png(&input, &dir.path().join("output.png")).unwrap();
+        assert_eq!(source.format, ImageFormat::Jpeg);
+        assert!(source.lossy);
+        assert_eq!(source.label(), "JPEG");
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        assert!(SourceImage::detect(b"not an image").is_err());
+    }
+}
@@ -1,61 +1,773 @@
 use anyhow::{Context, Result, bail};
+use image::{AnimationDecoder, GenericImageView, ImageFormat, codecs::gif::GifDecoder};
 use std::fs;
-use std::path::Path;
-use ureq::Agent;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use ureq::{Agent, Body, http::Response};
 
-use crate::pipeline::image_validator::ImageValidator;
+use crate::pipeline::{image_converter::SourceImage, image_validator::ImageValidator};
+use crate::utils::format::format_size;
+
+/// Download limits and retry policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadConfig {
+    /// Maximum response body size in bytes
+    pub max_bytes: u64,
+    /// Timeout for establishing the connection (including the TLS handshake)
+    pub connect_timeout: Duration,
+    /// Timeout for receiving the response headers, and again for the body
+    pub read_timeout: Duration,
+    /// How many times a 5xx or 429 response is retried
+    pub max_retries: u32,
+    /// Wait before the first retry; doubled on every further retry
+    pub retry_backoff: Duration,
+    /// Maximum number of redirects to follow
+    pub max_redirects: u32,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 16 * 1024 * 1024,
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(1),
+            max_redirects: 5,
+        }
+    }
+}
+
+/// Upper bound for a server-provided `Retry-After`
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Why a download failed
+///
+/// The messages are shown to the Issue author as-is, so each one says what to fix.
+#[derive(Debug, thiserror::Error)]
+pub enum DownloadError {
+    #[error("URLが不正です: {0}")]
+    InvalidUrl(String),
+
+    #[error("サーバーに接続できませんでした（URLのホスト名が正しいか確認してください）: {0}")]
+    Connection(String),
+
+    #[error("ダウンロードがタイムアウトしました（{0}）。時間をおいて新しいIssueを作成してください")]
+    Timeout(String),
+
+    #[error(
+        "リダイレクトが多すぎます（上限: {limit} 回）。画像ファイルの直接URLを指定してください"
+    )]
+    TooManyRedirects { limit: u32 },
+
+    #[error("{}", http_status_message(*status, *attempts))]
+    HttpStatus { status: u16, attempts: u32 },
+
+    #[error(
+        "ファイルが大きすぎます（{}上限: {}）",
+        size.map(|size| format!("{}、", format_size(size))).unwrap_or_default(),
+        format_size(*limit)
+    )]
+    TooLarge { limit: u64, size: Option<u64> },
+
+    #[error(
+        "{content_type} が返されました。ページのURLではなく、ファイルの直接URLを指定してください"
+    )]
+    UnexpectedContentType { content_type: String },
+
+    #[error(
+        "ダウンロードしたデータが対応している画像形式（PNG, WebP, GIF, BMP, JPEG）ではありません"
+    )]
+    UnrecognizedImage,
+
+    #[error("レスポンスの読み取りに失敗しました: {0}")]
+    Read(String),
+}
+
+impl DownloadError {
+    /// The kind of failure, without the details
+    pub const fn kind(&self) -> DownloadErrorKind {
+        match self {
+            Self::InvalidUrl(_) => DownloadErrorKind::InvalidUrl,
+            Self::Connection(_) => DownloadErrorKind::Connection,
+            Self::Timeout(_) => DownloadErrorKind::Timeout,
+            Self::TooManyRedirects { .. } => DownloadErrorKind::TooManyRedirects,
+            Self::HttpStatus { .. } => DownloadErrorKind::HttpStatus,
+            Self::TooLarge { .. } => DownloadErrorKind::TooLarge,
+            Self::UnexpectedContentType { .. } => DownloadErrorKind::UnexpectedContentType,
+            Self::UnrecognizedImage => DownloadErrorKind::UnrecognizedImage,
+            Self::Read(_) => DownloadErrorKind::Read,
+        }
+    }
+}
+
+/// Prefix of the line that `process-issue` prints when a download failed
+///
+/// `post-failure` only receives the command output, so the kind is passed on as text.
+pub const DOWNLOAD_ERROR_KIND_PREFIX: &str = "ダウンロードエラーの種類: ";
+
+/// Kind of a [`DownloadError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadErrorKind {
+    InvalidUrl,
+    Connection,
+    Timeout,
+    TooManyRedirects,
+    HttpStatus,
+    TooLarge,
+    UnexpectedContentType,
+    UnrecognizedImage,
+    Read,
+}
+
+impl DownloadErrorKind {
+    const ALL: [Self; 9] = [
+        Self::InvalidUrl,
+        Self::Connection,
+        Self::Timeout,
+        Self::TooManyRedirects,
+        Self::HttpStatus,
+        Self::TooLarge,
+        Self::UnexpectedContentType,
+        Self::UnrecognizedImage,
+        Self::Read,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidUrl => "invalid_url",
+            Self::Connection => "connection",
+            Self::Timeout => "timeout",
+            Self::TooManyRedirects => "too_many_redirects",
+            Self::HttpStatus => "http_status",
+            Self::TooLarge => "too_large",
+            Self::UnexpectedContentType => "unexpected_content_type",
+            Self::UnrecognizedImage => "unrecognized_image",
+            Self::Read => "read",
+        }
+    }
+
+    /// What the Issue author can do about the failure
+    pub const fn advice(&self) -> &'static str {
+        match self {
+            Self::InvalidUrl => {
+                "URLが正しい形式か（`https://` から始まっているか）確認してください。"
+            }
+            Self::Connection => {
+                "ダウンロード先のサーバーに接続できませんでした。URLのホスト名を確認するか、画像をIssueに直接アップロードしてください。"
+            }
+            Self::Timeout => {
+                "ダウンロード先のサーバーの応答が遅すぎます。画像をIssueに直接アップロードするなど、安定した場所のURLを指定してください。"
+            }
+            Self::TooManyRedirects => {
+                "URLが何度も転送されています。短縮URLや共有ページのURLではなく、ファイルの直接URLを指定してください。"
+            }
+            Self::HttpStatus => {
+                "ダウンロード先のサーバーがエラーを返しました。URLが公開されていて、ログインなしでアクセスできるか確認してください。"
+            }
+            Self::TooLarge => {
+                "ファイルがダウンロードできるサイズの上限を超えています。画像を圧縮するか、解像度を下げてからアップロードし直してください。"
+            }
+            Self::UnexpectedContentType => {
+                "URLが画像ではなくWebページを返しています。画像を右クリックして「画像アドレスをコピー」したURLか、Issueに直接アップロードした画像のURLを指定してください。"
+            }
+            Self::UnrecognizedImage => {
+                "ダウンロードしたファイルが画像ではありません。PNG, WebP, GIF, BMP, JPEG のいずれかの画像を指定してください。"
+            }
+            Self::Read => {
+                "ダウンロード中に通信が途切れました。時間をおいて新しいIssueを作成してください。"
+            }
+        }
+    }
+
+    /// Find the kind reported in the command output (the last report wins)
+    pub fn find_in(output: &str) -> Option<Self> {
+        output
+            .lines()
+            .rev()
+            .find_map(|line| line.trim().strip_prefix(DOWNLOAD_ERROR_KIND_PREFIX))
+            .and_then(|kind| kind.trim().parse().ok())
+    }
+}
+
+impl FromStr for DownloadErrorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("'{}' is not a valid DownloadErrorKind", s))
+    }
+}
+
+impl From<ureq::Error> for DownloadError {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::BadUri(uri) => Self::InvalidUrl(uri),
+            ureq::Error::Timeout(timeout) => Self::Timeout(timeout.to_string()),
+            ureq::Error::HostNotFound | ureq::Error::ConnectionFailed => {
+                Self::Connection(error.to_string())
+            }
+            ureq::Error::Io(ref io) if io.kind() == std::io::ErrorKind::TimedOut => {
+                Self::Timeout(error.to_string())
+            }
+            ureq::Error::Io(_) | ureq::Error::Tls(_) | ureq::Error::Rustls(_) => {
+                Self::Connection(error.to_string())
+            }
+            other => Self::Read(other.to_string()),
+        }
+    }
+}
+
+fn http_status_message(status: u16, attempts: u32) -> String {
+    match status {
+        404 | 410 => format!(
+            "ファイルが見つかりません（HTTP {status}）。URLが正しいか、削除されていないか確認してください"
+        ),
+        401 | 403 => format!(
+            "アクセスが拒否されました（HTTP {status}）。ログインなしで誰でもアクセスできる公開URLを指定してください"
+        ),
+        429 => format!(
+            "リクエストが多すぎます（HTTP 429、{attempts} 回試行）。時間をおいて新しいIssueを作成してください"
+        ),
+        500..=599 => format!(
+            "ダウンロード先のサーバーでエラーが発生しました（HTTP {status}、{attempts} 回試行）。時間をおいて新しいIssueを作成してください"
+        ),
+        _ => format!("ダウンロードに失敗しました（HTTP {status}）"),
+    }
+}
+
+/// What the downloaded body is expected to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExpectedContent {
+    /// An image in one of the supported formats
+    Image,
+    /// Any non-HTML file (model JSON, Blockbench project)
+    File,
+}
 
 /// Image downloader and validator
 pub struct ImageDownloader {
     client: Agent,
+    config: DownloadConfig,
 }
 
 impl ImageDownloader {
     pub fn new() -> Result<Self> {
+        Self::with_config(DownloadConfig::default())
+    }
+
+    pub fn with_config(config: DownloadConfig) -> Result<Self> {
         let client = Agent::config_builder()
             .user_agent("OfroPack-Image-Downloader")
+            .http_status_as_error(false)
+            .max_redirects(config.max_redirects)
+            .timeout_connect(Some(config.connect_timeout))
+            .timeout_recv_response(Some(config.read_timeout))
+            .timeout_recv_body(Some(config.read_timeout))
             .build()
             .into();
 
-        Ok(Self { client })
+        Ok(Self { client, config })
     }
 
     /// Download image from URL and validate it's a PNG
     pub fn download(&self, url: &str, output_path: &Path) -> Result<()> {
-        println!("画像をダウンロード中: {}", url);
+        let bytes = self.fetch(url, ExpectedContent::Image)?;
+
+        // Reject other formats before anything is written to disk
+        let format = image::guess_format(&bytes).context("画像形式を判定できませんでした")?;
+        if format != ImageFormat::Png {
+            bail!("PNG形式の画像のみ対応しています（検出された形式: {format:?}）");
+        }
+        write_file(output_path, &bytes)?;
+
+        // Validate it's a PNG using image crate
+        ImageValidator::new_png(output_path)?;
+
+        println!("✓ 画像のダウンロードと検証が完了しました");
+        Ok(())
+    }
+
+    /// Download a non-image file (model JSON, Blockbench project) without validation
+    pub fn download_file(&self, url: &str, output_path: &Path) -> Result<()> {
+        let bytes = self.fetch(url, ExpectedContent::File)?;
+        write_file(output_path, &bytes)?;
+
+        println!("✓ ファイルのダウンロードが完了しました");
+        Ok(())
+    }
+
+    /// Download a texture that may be a still image or an animated GIF
+    ///
+    /// GIFs with more than one frame are saved as `<name>.gif` in `dir` so that their
+    /// frames can be stitched. Other images (PNG, WebP, BMP, JPEG and single-frame GIFs)
+    /// are saved with their own extension and converted to PNG when the model is added.
+    pub fn download_texture(&self, url: &str, dir: &Path, name: &str) -> Result<DownloadedTexture> {
+        let bytes = self.fetch(url, ExpectedContent::Image)?;
+
+        let source = SourceImage::detect(&bytes)?;
+        let path = dir.join(format!("{}.{}", name, source.format.extensions_str()[0]));
+        write_file(&path, &bytes)?;
+
+        // Make sure the image can actually be decoded before handing it over
+        let (width, height) = image::load_from_memory_with_format(&bytes, source.format)
+            .context("画像のデコードに失敗しました")?
+            .dimensions();
+        println!("画像形式: {}, 画像サイズ: {width}x{height}", source.label());
+
+        let texture = if source.format == ImageFormat::Gif && gif_frame_count(&bytes)? > 1 {
+            DownloadedTexture::Gif(path)
+        } else {
+            DownloadedTexture::Image(path)
+        };
+
+        println!("✓ 画像のダウンロードと検証が完了しました");
+        Ok(texture)
+    }
 
-        let mut response = self
-            .client
-            .get(url)
-            .call()
-            .context("画像のダウンロードに失敗しました")?;
+    /// Fetch the body, retrying 5xx and 429 responses with exponential backoff
+    ///
+    /// The size, `Content-Type` and leading bytes are checked before the body is
+    /// returned, so nothing unexpected reaches the disk.
+    fn fetch(&self, url: &str, expected: ExpectedContent) -> Result<Vec<u8>, DownloadError> {
+        println!("ダウンロード中: {}", url);
 
-        let status = response.status();
+        let mut attempts = 0;
+        let mut response = loop {
+            attempts += 1;
+            let response = self.client.get(url).call().map_err(|error| match error {
+                ureq::Error::TooManyRedirects => DownloadError::TooManyRedirects {
+                    limit: self.config.max_redirects,
+                },
+                other => other.into(),
+            })?;
+            let status = response.status().as_u16();
+            if response.status().is_success() {
+                break response;
+            }
+            if !is_retryable_status(status) || attempts > self.config.max_retries {
+                return Err(DownloadError::HttpStatus { status, attempts });
+            }
 
-        if !status.is_success() {
-            bail!(
-                "画像のダウンロードに失敗しました（HTTPステータス: {}）",
-                status
+            let wait = retry_after(&response)
+                .unwrap_or_else(|| self.config.retry_backoff * 2u32.saturating_pow(attempts - 1));
+            println!(
+                "  ⏳ HTTP {} のため {:.1} 秒後に再試行します（{}/{}）",
+                status,
+                wait.as_secs_f64(),
+                attempts,
+                self.config.max_retries
             );
+            std::thread::sleep(wait);
+        };
+
+        // Reject oversized bodies up front when the server announces the size
+        let content_length = response
+            .headers()
+            .get("content-length")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        if let Some(size) = content_length
+            && size > self.config.max_bytes
+        {
+            return Err(DownloadError::TooLarge {
+                limit: self.config.max_bytes,
+                size: Some(size),
+            });
         }
 
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        check_content_type(content_type.as_deref(), expected)?;
+
         let bytes = response
             .body_mut()
+            .with_config()
+            .limit(self.config.max_bytes)
             .read_to_vec()
-            .context("レスポンスの読み取りに失敗しました")?;
+            .map_err(|error| match error {
+                ureq::Error::BodyExceedsLimit(_) => DownloadError::TooLarge {
+                    limit: self.config.max_bytes,
+                    size: None,
+                },
+                other => other.into(),
+            })?;
 
-        // Save to file
-        fs::write(output_path, &bytes).with_context(|| {
-            format!(
-                "ファイルの書き込みに失敗しました: {}",
-                output_path.display()
-            )
-        })?;
+        sniff(&bytes, expected)?;
+        println!("  ✓ {} を受信しました", format_size(bytes.len() as u64));
+        Ok(bytes)
+    }
+}
 
-        // Validate it's a PNG using image crate
-        ImageValidator::new_png(output_path)?;
+/// Whether the status is worth retrying
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..=599).contains(&status)
+}
 
-        println!("✓ 画像のダウンロードと検証が完了しました");
+/// `Retry-After` in seconds, capped at [`MAX_RETRY_AFTER`]
+///
+/// HTTP dates are ignored and fall back to the configured backoff.
+fn retry_after(response: &Response<Body>) -> Option<Duration> {
+    response
+        .headers()
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let seconds = value.trim().parse::<u64>().ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Check the `Content-Type` header against the expected content
+///
+/// A missing header and generic binary types are accepted; the body is sniffed afterwards.
+fn check_content_type(
+    content_type: Option<&str>,
+    expected: ExpectedContent,
+) -> Result<(), DownloadError> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    let accepted = match expected {
+        ExpectedContent::Image => {
+            mime.starts_with("image/")
+                || mime == "application/octet-stream"
+                || mime == "binary/octet-stream"
+        }
+        ExpectedContent::File => mime != "text/html" && mime != "application/xhtml+xml",
+    };
+    if accepted {
         Ok(())
+    } else {
+        Err(DownloadError::UnexpectedContentType {
+            content_type: content_type.to_string(),
+        })
+    }
+}
+
+/// Check the leading bytes of the body against the expected content
+fn sniff(bytes: &[u8], expected: ExpectedContent) -> Result<(), DownloadError> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(64)]).to_ascii_lowercase();
+    let head = head.trim_start();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        return Err(DownloadError::UnexpectedContentType {
+            content_type: "text/html".to_string(),
+        });
+    }
+    if expected == ExpectedContent::Image && SourceImage::detect(bytes).is_err() {
+        return Err(DownloadError::UnrecognizedImage);
+    }
+    Ok(())
+}
+
+/// Count the frames of a GIF
+fn gif_frame_count(bytes: &[u8]) -> Result<usize> {
+    let decoder =
+        GifDecoder::new(std::io::Cursor::new(bytes)).context("GIFのデコードに失敗しました")?;
+    let mut count = 0;
+    for frame in decoder.into_frames() {
+        frame.context("GIFのフレームのデコードに失敗しました")?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Downloaded texture file
+#[derive(Debug)]
+pub enum DownloadedTexture {
+    /// A single still image (converted to PNG when the model is added)
+    Image(PathBuf),
+    /// An animated GIF whose frames need to be stitched
+    Gif(PathBuf),
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    fs::write(path, bytes)
+        .with_context(|| format!("ファイルの書き込みに失敗しました: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{
+            Arc,
+            atomic::{AtomicU32, Ordering},
+        },
+    };
+
+    use super::*;
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    /// Serve the given responses in order (the last one repeats) and count the requests
+    fn serve(responses: Vec<Vec<u8>>) -> (String, Arc<AtomicU32>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/texture.png", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&count);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let index = counter.fetch_add(1, Ordering::SeqCst) as usize;
+                let response = &responses[index.min(responses.len() - 1)];
+                let _ = stream.write_all(response);
+            }
+        });
+        (url, count)
+    }
+
+    fn response(status: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+        let mut out = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn downloader(config: DownloadConfig) -> ImageDownloader {
+        ImageDownloader::with_config(DownloadConfig {
+            retry_backoff: Duration::from_millis(1),
+            ..config
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_retry_on_server_error() {
+        let (url, count) = serve(vec![
+            response("503 Service Unavailable", "text/plain", b"busy"),
+            response("429 Too Many Requests", "text/plain", b"slow down"),
+            response("200 OK", "image/png", PNG_HEADER),
+        ]);
+        let bytes = downloader(DownloadConfig::default())
+            .fetch(&url, ExpectedContent::Image)
+            .unwrap();
+        assert_eq!(bytes, PNG_HEADER);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let (url, count) = serve(vec![response("502 Bad Gateway", "text/plain", b"")]);
+        let error = downloader(DownloadConfig {
+            max_retries: 2,
+            ..DownloadConfig::default()
+        })
+        .fetch(&url, ExpectedContent::Image)
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            DownloadError::HttpStatus {
+                status: 502,
+                attempts: 3
+            }
+        ));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_client_error_is_not_retried() {
+        let (url, count) = serve(vec![response("404 Not Found", "text/plain", b"")]);
+        let error = downloader(DownloadConfig::default())
+            .fetch(&url, ExpectedContent::Image)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DownloadError::HttpStatus {
+                status: 404,
+                attempts: 1
+            }
+        ));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let (url, _) = serve(vec![response("200 OK", "image/png", &[0; 64])]);
+        let error = downloader(DownloadConfig {
+            max_bytes: 16,
+            ..DownloadConfig::default()
+        })
+        .fetch(&url, ExpectedContent::Image)
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            DownloadError::TooLarge {
+                limit: 16,
+                size: Some(64)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_redirect_limit() {
+        let (url, count) = serve(vec![
+            b"HTTP/1.1 302 Found\r\nLocation: /texture.png\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_vec(),
+        ]);
+        let error = downloader(DownloadConfig {
+            max_redirects: 2,
+            ..DownloadConfig::default()
+        })
+        .fetch(&url, ExpectedContent::Image)
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            DownloadError::TooManyRedirects { limit: 2 }
+        ));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_html_page_is_rejected() {
+        let (url, _) = serve(vec![response(
+            "200 OK",
+            "text/html; charset=utf-8",
+            b"<!DOCTYPE html><html></html>",
+        )]);
+        let error = downloader(DownloadConfig::default())
+            .fetch(&url, ExpectedContent::Image)
+            .unwrap_err();
+        assert!(matches!(error, DownloadError::UnexpectedContentType { .. }));
+    }
+
+    #[test]
+    fn test_check_content_type() {
+        let image = ExpectedContent::Image;
+        let file = ExpectedContent::File;
+        assert!(check_content_type(None, image).is_ok());
+        assert!(check_content_type(Some("image/webp"), image).is_ok());
+        assert!(check_content_type(Some("application/octet-stream"), image).is_ok());
+        assert!(check_content_type(Some("text/plain; charset=utf-8"), image).is_err());
+        assert!(check_content_type(Some("text/plain; charset=utf-8"), file).is_ok());
+        assert!(check_content_type(Some("Text/HTML"), file).is_err());
+    }
+
+    #[test]
+    fn test_sniff() {
+        let image = ExpectedContent::Image;
+        let file = ExpectedContent::File;
+        assert!(sniff(PNG_HEADER, image).is_ok());
+        assert!(matches!(
+            sniff(b"{\"elements\": []}", image),
+            Err(DownloadError::UnrecognizedImage)
+        ));
+        assert!(sniff(b"{\"elements\": []}", file).is_ok());
+        assert!(matches!(
+            sniff(b"\n  <html><body>", file),
+            Err(DownloadError::UnexpectedContentType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_download_error_kind() {
+        let error = DownloadError::TooLarge {
+            limit: 16,
+            size: Some(64),
+        };
+        let output = format!(
+            "📥 画像をダウンロード中...\n{DOWNLOAD_ERROR_KIND_PREFIX}{}\nエラー: 画像のダウンロードに失敗しました: {error}",
+            error.kind().as_str()
+        );
+        assert_eq!(
+            DownloadErrorKind::find_in(&output),
+            Some(DownloadErrorKind::TooLarge)
+        );
+        assert_eq!(
+            DownloadErrorKind::find_in("エラー: 画像サイズが不正です"),
+            None
+        );
+        assert_eq!(
+            DownloadErrorKind::find_in(&format!("{DOWNLOAD_ERROR_KIND_PREFIX}unknown")),
+            None
+        );
+        for kind in DownloadErrorKind::ALL {
+            assert_eq!(kind.as_str().parse(), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("2"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_retry_after("3600"), Some(MAX_RETRY_AFTER));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_http_status_message() {
+        let message = DownloadError::HttpStatus {
+            status: 404,
+            attempts: 1,
+        }
+        .to_string();
+        assert!(message.contains("HTTP 404"));
+
+        let message = DownloadError::TooLarge {
+            limit: 1024,
+            size: Some(2048),
+        }
+        .to_string();
+        assert!(message.contains(&format_size(2048)));
+        assert!(message.contains(&format_size(1024)));
+    }
+
+    fn gif(frames: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(&mut bytes);
+            for i in 0..frames {
+                let pixel = image::Rgba([u8::try_from(i * 64).unwrap(), 0, 0, 255]);
+                let frame = image::RgbaImage::from_pixel(4, 4, pixel);
+                encoder.encode_frame(image::Frame::new(frame)).unwrap();
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_single_frame_gif_is_still_image() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (url, _) = serve(vec![response("200 OK", "image/gif", &gif(1))]);
+        let texture = downloader(DownloadConfig::default())
+            .download_texture(&url, dir.path(), "still")
+            .unwrap();
+        assert!(
+            matches!(&texture, DownloadedTexture::Image(path) if path.ends_with("still.gif")),
+            "{texture:?}"
+        );
+
+        let (url, _) = serve(vec![response("200 OK", "image/gif", &gif(3))]);
+        let texture = downloader(DownloadConfig::default())
+            .download_texture(&url, dir.path(), "animated")
+            .unwrap();
+        assert!(
+            matches!(&texture, DownloadedTexture::Gif(path) if path.ends_with("animated.gif")),
+            "{texture:?}"
+        );
     }
 }
@@ -0,0 +1,224 @@
+//! 画像の自動調整
+//!
+//! 正方形でない画像や、幅が2の累乗でない画像を2Dモデルのテクスチャとして
+//! 使える形に調整します。透明な余白を追加して正方形にしてから、
+//! 最も近い2の累乗のサイズにニアレストネイバーで拡大縮小します。
+
+use std::{fmt, path::Path};
+
+use anyhow::Context;
+use image::{RgbaImage, imageops::FilterType};
+
+/// 自動調整で行った変更
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizeChange {
+    /// 透明な余白を追加して正方形にした
+    Padded {
+        /// 元のサイズ（幅, 高さ）
+        from: (u32, u32),
+        /// 正方形の一辺
+        to: u32,
+    },
+    /// 2の累乗のサイズに拡大縮小した
+    Scaled {
+        /// 元の一辺
+        from: u32,
+        /// 拡大縮小後の一辺
+        to: u32,
+    },
+}
+
+impl fmt::Display for NormalizeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Padded {
+                from: (width, height),
+                to,
+            } => write!(
+                f,
+                "透明な余白を追加して正方形に調整: {width}x{height} → {to}x{to}"
+            ),
+            Self::Scaled { from, to } => write!(
+                f,
+                "2の累乗のサイズに{}（ニアレストネイバー）: {from}x{from} → {to}x{to}",
+                if to > from { "拡大" } else { "縮小" }
+            ),
+        }
+    }
+}
+
+/// 自動調整した画像
+pub struct Normalized {
+    /// 調整後の画像
+    pub image: RgbaImage,
+    /// 行った変更（調整が不要だった場合は空）
+    pub changes: Vec<NormalizeChange>,
+}
+
+impl Normalized {
+    /// 画像を正方形・2の累乗のサイズに調整
+    ///
+    /// 余白は画像が中央に来るように追加します（奇数の場合は右と下が1ピクセル多くなります）。
+    /// 最も近い2の累乗が2つある場合は、画素が失われないよう大きい方を選び、
+    /// `max_size` を超える場合は `max_size` 以下の最大の2の累乗に縮小します。
+    #[must_use]
+    pub fn new(image: RgbaImage, max_size: u32) -> Self {
+        let mut changes = Vec::new();
+        let (width, height) = image.dimensions();
+
+        let side = width.max(height);
+        let mut image = if width == height {
+            image
+        } else {
+            let mut square = RgbaImage::new(side, side);
+            image::imageops::replace(
+                &mut square,
+                &image,
+                i64::from((side - width) / 2),
+                i64::from((side - height) / 2),
+            );
+            changes.push(NormalizeChange::Padded {
+                from: (width, height),
+                to: side,
+            });
+            square
+        };
+
+        let target = nearest_pow_of_two(side).min(floor_pow_of_two(max_size));
+        if target != side {
+            image = image::imageops::resize(&image, target, target, FilterType::Nearest);
+            changes.push(NormalizeChange::Scaled {
+                from: side,
+                to: target,
+            });
+        }
+
+        Self { image, changes }
+    }
+
+    /// PNG画像ファイルを調整して書き出す
+    ///
+    /// 調整が不要な場合も `output` に書き出します。
+    ///
+    /// # Errors
+    ///
+    /// 画像の読み込み、または書き込みに失敗した場合
+    pub fn convert_file(
+        input: &Path,
+        output: &Path,
+        max_size: u32,
+    ) -> anyhow::Result<Vec<NormalizeChange>> {
+        let image = image::open(input)
+            .with_context(|| format!("画像ファイルの読み込みに失敗しました: {}", input.display()))?
+            .to_rgba8();
+        let normalized = Self::new(image, max_size);
+        normalized
+            .image
+            .save_with_format(output, image::ImageFormat::Png)
+            .with_context(|| {
+                format!("調整した画像の書き込みに失敗しました: {}", output.display())
+            })?;
+        Ok(normalized.changes)
+    }
+}
+
+/// 最も近い2の累乗（等距離の場合は大きい方）
+fn nearest_pow_of_two(n: u32) -> u32 {
+    let lower = floor_pow_of_two(n);
+    if lower == n {
+        return n;
+    }
+    let upper = lower.saturating_mul(2);
+    if n - lower < upper - n { lower } else { upper }
+}
+
+/// `n` 以下の最大の2の累乗（`n` が0の場合は1）
+fn floor_pow_of_two(n: u32) -> u32 {
+    if n == 0 { 1 } else { 1 << n.ilog2() }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::*;
+
+    #[test]
+    fn test_nearest_pow_of_two() {
+        assert_eq!(nearest_pow_of_two(1), 1);
+        assert_eq!(nearest_pow_of_two(16), 16);
+        assert_eq!(nearest_pow_of_two(20), 16);
+        assert_eq!(nearest_pow_of_two(24), 32);
+        assert_eq!(nearest_pow_of_two(30), 32);
+        assert_eq!(nearest_pow_of_two(100), 128);
+    }
+
+    #[test]
+    fn test_already_normalized() {
+        let image = RgbaImage::from_pixel(16, 16, Rgba([1, 2, 3, 255]));
+        let normalized = Normalized::new(image.clone(), 256);
+        assert!(normalized.changes.is_empty());
+        assert_eq!(normalized.image, image);
+    }
+
+    #[test]
+    fn test_pad_to_square() {
+        let image = RgbaImage::from_pixel(14, 16, Rgba([255, 0, 0, 255]));
+        let normalized = Normalized::new(image, 256);
+        assert_eq!(
+            normalized.changes,
+            vec![NormalizeChange::Padded {
+                from: (14, 16),
+                to: 16
+            }]
+        );
+        assert_eq!(normalized.image.dimensions(), (16, 16));
+        assert_eq!(normalized.image.get_pixel(0, 0)[3], 0);
+        assert_eq!(normalized.image.get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(normalized.image.get_pixel(14, 15), &Rgba([255, 0, 0, 255]));
+        assert_eq!(normalized.image.get_pixel(15, 0)[3], 0);
+    }
+
+    #[test]
+    fn test_pad_and_scale() {
+        let image = RgbaImage::from_fn(24, 12, |x, _| {
+            if x < 12 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        });
+        let normalized = Normalized::new(image, 256);
+        assert_eq!(
+            normalized.changes,
+            vec![
+                NormalizeChange::Padded {
+                    from: (24, 12),
+                    to: 24
+                },
+                NormalizeChange::Scaled { from: 24, to: 32 }
+            ]
+        );
+        assert_eq!(normalized.image.dimensions(), (32, 32));
+        // ニアレストネイバーなので色は混ざらない
+        assert!(normalized.image.pixels().all(|p| {
+            [
+                Rgba([0, 0, 0, 0]),
+                Rgba([255, 0, 0, 255]),
+                Rgba([0, 0, 255, 255]),
+            ]
+            .contains(p)
+        }));
+    }
+
+    #[test]
+    fn test_downscale_to_max_size() {
+        let image = RgbaImage::from_pixel(512, 512, Rgba([0, 0, 0, 255]));
+        let normalized = Normalized::new(image, 256);
+        assert_eq!(
+            normalized.changes,
+            vec![NormalizeChange::Scaled { from: 512, to: 256 }]
+        );
+        assert_eq!(normalized.image.dimensions(), (256, 256));
+    }
+}
@@ -1,8 +1,12 @@
 //! 画像ファイルの検証
 //!
-//! PNG画像の形式とサイズを検証する機能を提供します。
+//! PNG画像の形式とサイズ、内容を検証する機能を提供します。
 
-use std::path::{Path, PathBuf};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use anyhow::Context;
 use image::GenericImageView;
@@ -36,6 +40,19 @@ impl ImageValidator {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn new_png(path: &Path) -> anyhow::Result<Self> {
+        let validator = Self::open_png(path)?;
+        let (width, height) = validator.dimensions;
+        println!("画像サイズ: {width}x{height}");
+        Ok(validator)
+    }
+
+    /// PNG画像ファイルを開いて検証する（標準出力に何も表示しない）
+    ///
+    /// # Errors
+    ///
+    /// - 画像ファイルが読み込めない場合
+    /// - PNG形式でない場合
+    pub fn open_png(path: &Path) -> anyhow::Result<Self> {
         let img = image::open(path)
             .with_context(|| format!("画像ファイルの読み込みに失敗しました: {}", path.display()))?;
 
@@ -48,14 +65,18 @@ impl ImageValidator {
             anyhow::bail!("PNG形式の画像のみ対応しています（検出された形式: {format:?}）");
         }
 
-        let (width, height) = img.dimensions();
-        println!("画像サイズ: {width}x{height}");
         Ok(Self {
             path: path.to_path_buf(),
-            dimensions: (width, height),
+            dimensions: img.dimensions(),
         })
     }
 
+    /// 画像サイズ（幅, 高さ）を取得
+    #[must_use]
+    pub const fn dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
     /// 画像ファイルのパスを取得
     #[must_use]
     pub const fn path(&self) -> &PathBuf {
@@ -66,28 +87,441 @@ impl ImageValidator {
     ///
     /// # Errors
     ///
-    /// - 画像の幅または高さが2の累乗でない場合
-    /// - アニメーションの場合、高さが幅の倍数でない場合
+    /// - 画像の幅（アニメーションの場合はフレームの幅）が2の累乗でない場合
+    /// - アニメーションの場合、画像サイズがフレームサイズで割り切れない場合
+    /// - アニメーションの場合、`frames` のフレーム番号が画像のフレーム数を超える場合
     /// - 静止画の場合、正方形でない場合
     pub fn should_model(&self, info: Option<&AnimationInfo>) -> anyhow::Result<()> {
+        let frame_count = self.check_model(info)?;
+        if let Some(animation_info) = info {
+            let animation = &animation_info.animation;
+            println!(
+                "アニメーションフレーム数: {frame_count}, 指定されたフレームタイム: {}, 合計: {} tick",
+                animation.frametime,
+                animation.total_duration(frame_count)
+            );
+        }
+        Ok(())
+    }
+
+    /// 2Dモデル用の画像として妥当かチェック（標準出力に何も表示しない）
+    ///
+    /// # Returns
+    ///
+    /// フレーム数（静止画の場合は1）
+    ///
+    /// # Errors
+    ///
+    /// [`Self::should_model`] と同じ条件でエラーを返します。
+    pub fn check_model(&self, info: Option<&AnimationInfo>) -> anyhow::Result<u32> {
         let (width, height) = self.dimensions;
-        if !is_pow_of_two(width) {
-            anyhow::bail!("画像の幅は2の累乗である必要があります (16,32,64)");
+        match info {
+            Some(info) => {
+                let animation = &info.animation;
+                let (frame_width, _) = animation.frame_size(width, height);
+                if !is_pow_of_two(frame_width) {
+                    anyhow::bail!("フレームの幅は2の累乗である必要があります (16,32,64)");
+                }
+                let frame_count = animation.frame_count(width, height)?;
+                animation.validate(frame_count)?;
+                Ok(frame_count)
+            }
+            None => {
+                if !is_pow_of_two(width) {
+                    anyhow::bail!("画像の幅は2の累乗である必要があります (16,32,64)");
+                }
+                if width != height {
+                    anyhow::bail!("静止画の場合、画像は正方形である必要があります");
+                }
+                Ok(1)
+            }
         }
-        if let Some(animation_info) = info {
-            let frame_count = height / width;
-            if frame_count == 0 {
-                anyhow::bail!("アニメーション画像の高さが幅の倍数ではありません");
+    }
+
+    /// 画像の内容をチェック
+    ///
+    /// 解像度・透明度・カラープロファイル・インターレースを [`ContentRules`] に従って検査し、
+    /// 見つかった問題をすべて [`ContentReport`] にまとめて返します。
+    /// 問題があってもエラーにはならないため、[`ContentReport::ensure`] で判定してください。
+    ///
+    /// # Errors
+    ///
+    /// 画像ファイルの読み込み、またはデコードに失敗した場合
+    pub fn check_content(
+        &self,
+        rules: &ContentRules,
+        usage: TextureUsage,
+    ) -> anyhow::Result<ContentReport> {
+        let data = std::fs::read(&self.path).with_context(|| {
+            format!(
+                "画像ファイルの読み込みに失敗しました: {}",
+                self.path.display()
+            )
+        })?;
+        let reader = png::Decoder::new(std::io::Cursor::new(&data))
+            .read_info()
+            .with_context(|| format!("PNGの読み込みに失敗しました: {}", self.path.display()))?;
+        let info = reader.info();
+        let pixels = image::load_from_memory_with_format(&data, image::ImageFormat::Png)
+            .with_context(|| format!("PNGのデコードに失敗しました: {}", self.path.display()))?
+            .to_rgba8();
+
+        let mut report = ContentReport::default();
+        let mut push = |check: ContentCheck, severity: Option<Severity>, message: String| {
+            if let Some(severity) = severity {
+                report.issues.push(ContentIssue {
+                    path: self.path.clone(),
+                    check,
+                    severity,
+                    message,
+                });
             }
-            println!(
-                "アニメーションフレーム数: {frame_count}, 指定されたフレームタイム: {}",
-                animation_info.animation.frametime
+        };
+
+        let (width, height) = self.dimensions;
+        if usage != TextureUsage::Animated && width.max(height) > rules.max_static_size {
+            push(
+                ContentCheck::Oversized,
+                rules.oversized,
+                format!(
+                    "画像サイズ {width}x{height} が上限の {0}x{0} を超えています",
+                    rules.max_static_size
+                ),
             );
-        } else if width != height {
-            anyhow::bail!("静止画の場合、画像は正方形である必要があります");
         }
 
-        Ok(())
+        if pixels.pixels().all(|p| p[3] == 0) {
+            push(
+                ContentCheck::FullyTransparent,
+                rules.fully_transparent,
+                "すべてのピクセルが完全に透明です".to_string(),
+            );
+        } else if usage != TextureUsage::Layer {
+            let count = pixels.pixels().filter(|p| p[3] != 0 && p[3] != 255).count();
+            if count > 0 {
+                push(
+                    ContentCheck::SemiTransparent,
+                    rules.semi_transparent,
+                    format!(
+                        "半透明のピクセルが {count} 個あります（generated のモデルでは正しく描画されません）"
+                    ),
+                );
+            }
+        }
+
+        if info.srgb.is_none() {
+            if let Some(profile) = &info.icc_profile {
+                if !icc_profile_description(profile).is_some_and(|desc| desc.contains("sRGB")) {
+                    push(
+                        ContentCheck::ColorProfile,
+                        rules.color_profile,
+                        "sRGB 以外のICCプロファイルが埋め込まれています（色が変わって見える場合があります）"
+                            .to_string(),
+                    );
+                }
+            } else if let Some(gamma) = info.gamma()
+                && (gamma.into_value() - SRGB_GAMMA).abs() > 0.01
+            {
+                push(
+                    ContentCheck::ColorProfile,
+                    rules.color_profile,
+                    format!(
+                        "ガンマ値 {:.5} が sRGB（{SRGB_GAMMA}）と異なります",
+                        gamma.into_value()
+                    ),
+                );
+            }
+        }
+
+        if info.interlaced {
+            push(
+                ContentCheck::Interlaced,
+                rules.interlaced,
+                "インターレースPNGです（インターレースなしで保存し直してください）".to_string(),
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+/// sRGB 相当のガンマ値（1/2.2）
+const SRGB_GAMMA: f32 = 0.454_55;
+
+/// 内容チェックの重大度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// 追加はできるが確認が必要
+    Warning,
+    /// 追加できない
+    Error,
+}
+
+/// 内容チェックの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentCheck {
+    /// 解像度が上限を超えている
+    Oversized,
+    /// すべてのピクセルが透明
+    FullyTransparent,
+    /// 半透明のピクセルがある
+    SemiTransparent,
+    /// sRGB 以外のカラープロファイル
+    ColorProfile,
+    /// インターレースPNG
+    Interlaced,
+    /// 非可逆圧縮の画像（JPEG など）から変換した
+    LossySource,
+}
+
+impl ContentCheck {
+    pub const ALL: [Self; 6] = [
+        Self::Oversized,
+        Self::FullyTransparent,
+        Self::SemiTransparent,
+        Self::ColorProfile,
+        Self::Interlaced,
+        Self::LossySource,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Oversized => "oversized",
+            Self::FullyTransparent => "fully_transparent",
+            Self::SemiTransparent => "semi_transparent",
+            Self::ColorProfile => "color_profile",
+            Self::Interlaced => "interlaced",
+            Self::LossySource => "lossy_source",
+        }
+    }
+}
+
+impl FromStr for ContentCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|check| check.as_str() == s)
+            .ok_or_else(|| {
+                let checks: Vec<_> = Self::ALL.iter().map(Self::as_str).collect();
+                format!(
+                    "'{}' は内容チェックの種類ではありません（{}）",
+                    s,
+                    checks.join(", ")
+                )
+            })
+    }
+}
+
+/// 内容チェックの重大度の上書き
+///
+/// `<チェック>=<error|warning|off>` の形式で指定します。`off` はチェックを無効にします。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleOverride {
+    pub check: ContentCheck,
+    pub severity: Option<Severity>,
+}
+
+impl FromStr for RuleOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (check, severity) = s.split_once('=').ok_or_else(|| {
+            format!(
+                "'{}' は <チェック>=<error|warning|off> の形式ではありません（例: semi_transparent=off）",
+                s
+            )
+        })?;
+        let severity = match severity.trim() {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            "off" => None,
+            other => {
+                return Err(format!(
+                    "'{}' は重大度ではありません（error, warning, off）",
+                    other
+                ));
+            }
+        };
+        Ok(Self {
+            check: check.trim().parse()?,
+            severity,
+        })
+    }
+}
+
+/// テクスチャの用途
+///
+/// 用途によって適用する内容チェックが変わります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureUsage {
+    /// 2Dモデルの静止画テクスチャ
+    Item,
+    /// 2Dモデルのアニメーションテクスチャ（解像度の上限を適用しない）
+    Animated,
+    /// 3Dモデルのレイヤー画像（半透明のピクセルを許可する）
+    Layer,
+}
+
+/// 内容チェックのルール
+///
+/// 各チェックの重大度を `None` にすると、そのチェックを無効にします。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentRules {
+    /// 静止画の幅・高さの上限（ピクセル）
+    pub max_static_size: u32,
+    /// 解像度が上限を超えている場合
+    pub oversized: Option<Severity>,
+    /// すべてのピクセルが透明な場合
+    pub fully_transparent: Option<Severity>,
+    /// 2Dモデルに半透明のピクセルがある場合
+    pub semi_transparent: Option<Severity>,
+    /// sRGB 以外のカラープロファイルの場合
+    pub color_profile: Option<Severity>,
+    /// インターレースPNGの場合
+    pub interlaced: Option<Severity>,
+    /// 非可逆圧縮の画像から変換した場合
+    pub lossy_source: Option<Severity>,
+}
+
+impl Default for ContentRules {
+    fn default() -> Self {
+        Self {
+            max_static_size: 256,
+            oversized: Some(Severity::Error),
+            fully_transparent: Some(Severity::Error),
+            semi_transparent: Some(Severity::Warning),
+            color_profile: Some(Severity::Warning),
+            interlaced: Some(Severity::Warning),
+            lossy_source: Some(Severity::Warning),
+        }
+    }
+}
+
+impl ContentRules {
+    /// チェックの重大度を変更
+    pub fn set_severity(&mut self, check: ContentCheck, severity: Option<Severity>) {
+        let target = match check {
+            ContentCheck::Oversized => &mut self.oversized,
+            ContentCheck::FullyTransparent => &mut self.fully_transparent,
+            ContentCheck::SemiTransparent => &mut self.semi_transparent,
+            ContentCheck::ColorProfile => &mut self.color_profile,
+            ContentCheck::Interlaced => &mut self.interlaced,
+            ContentCheck::LossySource => &mut self.lossy_source,
+        };
+        *target = severity;
+    }
+}
+
+/// 内容チェックで見つかった問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentIssue {
+    /// 画像ファイルのパス
+    pub path: PathBuf,
+    /// チェックの種類
+    pub check: ContentCheck,
+    /// 重大度
+    pub severity: Severity,
+    /// 問題の説明
+    pub message: String,
+}
+
+impl fmt::Display for ContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.path.file_name().map_or_else(
+            || self.path.to_string_lossy(),
+            |name| name.to_string_lossy(),
+        );
+        write!(f, "{name}: {}", self.message)
+    }
+}
+
+/// 内容チェックの結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentReport {
+    /// 見つかった問題
+    pub issues: Vec<ContentIssue>,
+}
+
+impl ContentReport {
+    /// 警告の一覧
+    pub fn warnings(&self) -> impl Iterator<Item = &ContentIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// エラーの一覧
+    pub fn errors(&self) -> impl Iterator<Item = &ContentIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// 別の結果を追加
+    pub fn extend(&mut self, other: Self) {
+        self.issues.extend(other.issues);
+    }
+
+    /// 警告を表示し、エラーがあれば失敗させる
+    ///
+    /// # Errors
+    ///
+    /// 重大度が [`Severity::Error`] の問題が1つ以上ある場合（すべての問題を列挙します）
+    pub fn ensure(self) -> anyhow::Result<Self> {
+        for warning in self.warnings() {
+            println!("⚠️  {warning}");
+        }
+        let errors: Vec<_> = self.errors().map(ToString::to_string).collect();
+        if !errors.is_empty() {
+            anyhow::bail!("テクスチャの内容が不正です:\n- {}", errors.join("\n- "));
+        }
+        Ok(self)
+    }
+}
+
+/// ICCプロファイルの説明（`desc` タグ）を取得
+///
+/// ICC v2 の `desc` 型と v4 の `mluc` 型（最初のレコード）に対応します。
+/// `sRGB IEC61966-2.1` のように、sRGB のプロファイルは説明に `sRGB` を含みます。
+fn icc_profile_description(profile: &[u8]) -> Option<String> {
+    let u32_at = |data: &[u8], offset: usize| -> Option<usize> {
+        let bytes = data.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?) as usize)
+    };
+
+    let tag_count = u32_at(profile, 128)?;
+    let tag = (0..tag_count).find_map(|i| {
+        let entry = profile.get(132 + i * 12..144 + i * 12)?;
+        if &entry[..4] != b"desc" {
+            return None;
+        }
+        let offset = u32_at(entry, 4)?;
+        profile.get(offset..offset.checked_add(u32_at(entry, 8)?)?)
+    })?;
+
+    match tag.get(..4)? {
+        b"desc" => {
+            let count = u32_at(tag, 8)?;
+            let text = tag.get(12..12usize.checked_add(count)?)?;
+            let text = text.split(|b| *b == 0).next()?;
+            Some(String::from_utf8_lossy(text).into_owned())
+        }
+        b"mluc" => {
+            if u32_at(tag, 8)? == 0 {
+                return None;
+            }
+            let length = u32_at(tag, 20)?;
+            let offset = u32_at(tag, 24)?;
+            let text = tag.get(offset..offset.checked_add(length)?)?;
+            let units: Vec<u16> = text
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        }
+        _ => None,
     }
 }
 
@@ -153,4 +587,277 @@ mod tests {
 
         // temp_file will be dropped and deleted here
     }
+
+    #[test]
+    fn test_animation_frame_validation() {
+        use crate::schema::animation::{AnimationData, AnimationFrame};
+
+        let validator = ImageValidator {
+            path: PathBuf::from("animated.png"),
+            dimensions: (16, 48),
+        };
+        let mut info = AnimationInfo {
+            animation: AnimationData::with_frametime(2),
+        };
+        assert_eq!(validator.check_model(Some(&info)).unwrap(), 3);
+
+        info.animation.frames = Some(vec![AnimationFrame::Index(2), AnimationFrame::Index(3)]);
+        assert!(validator.check_model(Some(&info)).is_err());
+
+        info.animation.frames = None;
+        info.animation.height = Some(10);
+        assert!(validator.check_model(Some(&info)).is_err());
+    }
+
+    fn write_png(
+        image: &image::RgbaImage,
+        configure: impl FnOnce(&mut png::Encoder<&mut Vec<u8>>),
+    ) -> (tempfile::NamedTempFile, ImageValidator) {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, image.width(), image.height());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        configure(&mut encoder);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(image.as_raw()).unwrap();
+        writer.finish().unwrap();
+
+        let file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        std::fs::write(file.path(), data).unwrap();
+        let validator = ImageValidator::open_png(file.path()).unwrap();
+        (file, validator)
+    }
+
+    fn checks(report: &ContentReport) -> Vec<(ContentCheck, Severity)> {
+        report
+            .issues
+            .iter()
+            .map(|issue| (issue.check, issue.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_rule_override() {
+        let rule: RuleOverride = "semi_transparent=error".parse().unwrap();
+        assert_eq!(rule.check, ContentCheck::SemiTransparent);
+        assert_eq!(rule.severity, Some(Severity::Error));
+
+        let rule: RuleOverride = "oversized=off".parse().unwrap();
+        assert_eq!(rule.check, ContentCheck::Oversized);
+        assert_eq!(rule.severity, None);
+
+        assert!("oversized".parse::<RuleOverride>().is_err());
+        assert!("oversized=fatal".parse::<RuleOverride>().is_err());
+        assert!("unknown=off".parse::<RuleOverride>().is_err());
+    }
+
+    #[test]
+    fn test_check_content_clean_texture() {
+        let image = image::RgbaImage::from_pixel(16, 16, image::Rgba([255, 0, 0, 255]));
+        let (_file, validator) = write_png(&image, |_| {});
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Item)
+            .unwrap();
+        assert!(report.issues.is_empty());
+        assert!(report.ensure().is_ok());
+    }
+
+    #[test]
+    fn test_check_content_oversized() {
+        let image = image::RgbaImage::from_pixel(512, 512, image::Rgba([0, 0, 0, 255]));
+        let (_file, validator) = write_png(&image, |_| {});
+        let rules = ContentRules::default();
+
+        let report = validator.check_content(&rules, TextureUsage::Item).unwrap();
+        assert_eq!(
+            checks(&report),
+            vec![(ContentCheck::Oversized, Severity::Error)]
+        );
+        assert!(report.ensure().is_err());
+
+        let report = validator
+            .check_content(&rules, TextureUsage::Animated)
+            .unwrap();
+        assert!(report.issues.is_empty());
+
+        let rules = ContentRules {
+            max_static_size: 512,
+            ..ContentRules::default()
+        };
+        let report = validator.check_content(&rules, TextureUsage::Item).unwrap();
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_check_content_transparency() {
+        let image = image::RgbaImage::from_pixel(16, 16, image::Rgba([0, 0, 0, 0]));
+        let (_file, validator) = write_png(&image, |_| {});
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Item)
+            .unwrap();
+        assert_eq!(
+            checks(&report),
+            vec![(ContentCheck::FullyTransparent, Severity::Error)]
+        );
+
+        let image = image::RgbaImage::from_fn(16, 16, |x, _| image::Rgba([0, 0, 0, x as u8 * 16]));
+        let (_file, validator) = write_png(&image, |_| {});
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Item)
+            .unwrap();
+        assert_eq!(
+            checks(&report),
+            vec![(ContentCheck::SemiTransparent, Severity::Warning)]
+        );
+        assert_eq!(report.warnings().count(), 1);
+        assert!(report.ensure().is_ok());
+
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Layer)
+            .unwrap();
+        assert!(report.issues.is_empty());
+
+        let rules = ContentRules {
+            semi_transparent: None,
+            ..ContentRules::default()
+        };
+        let report = validator.check_content(&rules, TextureUsage::Item).unwrap();
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_check_content_color_profile_and_interlace() {
+        let image = image::RgbaImage::from_pixel(16, 16, image::Rgba([255, 255, 255, 255]));
+        let (_file, validator) = write_png(&image, |encoder| {
+            encoder.set_source_gamma(png::ScaledFloat::new(1.0));
+        });
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Item)
+            .unwrap();
+        assert_eq!(
+            checks(&report),
+            vec![(ContentCheck::ColorProfile, Severity::Warning)]
+        );
+
+        let (_file, validator) = write_png(&image, |encoder| {
+            encoder.set_source_gamma(png::ScaledFloat::new(SRGB_GAMMA));
+        });
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Item)
+            .unwrap();
+        assert!(report.issues.is_empty());
+
+        // 1x1 の画像は Adam7 でも画素データが同じになるため、IHDR だけを書き換える
+        let pixel = image::RgbaImage::from_pixel(1, 1, image::Rgba([255, 255, 255, 255]));
+        let (file, _) = write_png(&pixel, |_| {});
+        let mut data = std::fs::read(file.path()).unwrap();
+        data[28] = 1;
+        let crc = crc32(&data[12..29]);
+        data[29..33].copy_from_slice(&crc.to_be_bytes());
+        std::fs::write(file.path(), data).unwrap();
+        let validator = ImageValidator::open_png(file.path()).unwrap();
+        let report = validator
+            .check_content(&ContentRules::default(), TextureUsage::Item)
+            .unwrap();
+        assert_eq!(
+            checks(&report),
+            vec![(ContentCheck::Interlaced, Severity::Warning)]
+        );
+    }
+
+    /// 説明のタグだけを持つICCプロファイル
+    fn icc_profile(desc_tag: &[u8]) -> Vec<u8> {
+        let mut profile = vec![0; 128];
+        profile.extend_from_slice(&1u32.to_be_bytes());
+        profile.extend_from_slice(b"desc");
+        profile.extend_from_slice(&144u32.to_be_bytes());
+        profile.extend_from_slice(&(desc_tag.len() as u32).to_be_bytes());
+        profile.extend_from_slice(desc_tag);
+        profile
+    }
+
+    /// ICC v2 の `desc` 型の説明
+    fn desc_tag(description: &str) -> Vec<u8> {
+        let mut tag = b"desc\0\0\0\0".to_vec();
+        tag.extend_from_slice(&(description.len() as u32 + 1).to_be_bytes());
+        tag.extend_from_slice(description.as_bytes());
+        tag.push(0);
+        tag
+    }
+
+    /// ICC v4 の `mluc` 型の説明（レコード1件）
+    fn mluc_tag(description: &str) -> Vec<u8> {
+        let text: Vec<u8> = description
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+        let mut tag = b"mluc\0\0\0\0".to_vec();
+        tag.extend_from_slice(&1u32.to_be_bytes());
+        tag.extend_from_slice(&12u32.to_be_bytes());
+        tag.extend_from_slice(b"enUS");
+        tag.extend_from_slice(&(text.len() as u32).to_be_bytes());
+        tag.extend_from_slice(&28u32.to_be_bytes());
+        tag.extend_from_slice(&text);
+        tag
+    }
+
+    #[test]
+    fn test_icc_profile_description() {
+        assert_eq!(
+            icc_profile_description(&icc_profile(&desc_tag("sRGB IEC61966-2.1"))).as_deref(),
+            Some("sRGB IEC61966-2.1")
+        );
+        assert_eq!(
+            icc_profile_description(&icc_profile(&mluc_tag("sRGB built-in"))).as_deref(),
+            Some("sRGB built-in")
+        );
+        assert_eq!(icc_profile_description(&[0; 64]), None);
+    }
+
+    #[test]
+    fn test_check_content_icc_profile() {
+        let image = image::RgbaImage::from_pixel(16, 16, image::Rgba([255, 255, 255, 255]));
+        let open = |profile: Vec<u8>| {
+            let mut info = png::Info::with_size(16, 16);
+            info.color_type = png::ColorType::Rgba;
+            info.bit_depth = png::BitDepth::Eight;
+            info.icc_profile = Some(profile.into());
+            let mut data = Vec::new();
+            let mut writer = png::Encoder::with_info(&mut data, info)
+                .unwrap()
+                .write_header()
+                .unwrap();
+            writer.write_image_data(image.as_raw()).unwrap();
+            writer.finish().unwrap();
+
+            let file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+            std::fs::write(file.path(), data).unwrap();
+            let report = ImageValidator::open_png(file.path())
+                .unwrap()
+                .check_content(&ContentRules::default(), TextureUsage::Item)
+                .unwrap();
+            checks(&report)
+        };
+
+        assert!(open(icc_profile(&desc_tag("sRGB IEC61966-2.1"))).is_empty());
+        assert_eq!(
+            open(icc_profile(&mluc_tag("Display P3"))),
+            vec![(ContentCheck::ColorProfile, Severity::Warning)]
+        );
+    }
+
+    fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for byte in bytes {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
 }
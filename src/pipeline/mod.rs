@@ -1,5 +1,9 @@
+pub mod bbmodel;
+pub mod frame_stitcher;
 pub mod github_client;
+pub mod image_converter;
 pub mod image_downloader;
+pub mod image_normalizer;
 pub mod image_validator;
 pub mod pr_creator;
 pub mod preview_generator;
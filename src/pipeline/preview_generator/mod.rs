@@ -0,0 +1,319 @@
+use anyhow::{Context, Result};
+use image::{
+    Delay, Frame, ImageFormat, ImageReader, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+};
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    paths::Paths,
+    schema::{
+        animation::{AnimationData, AnimationInfo},
+        models::ItemModel,
+    },
+    utils::json::read_json,
+};
+
+mod render;
+
+/// Preview size in pixels (square)
+const PREVIEW_SIZE: u32 = 256;
+
+/// Milliseconds per game tick
+const MS_PER_TICK: u32 = 50;
+
+pub struct PreviewGenerator;
+
+/// Generated preview images
+pub struct Preview {
+    /// Static 256x256 PNG (first frame for animated textures, isometric render for 3D models)
+    pub still: PathBuf,
+    /// Animated 256x256 GIF, only for textures with a `.png.mcmeta`
+    pub animated: Option<PathBuf>,
+}
+
+impl Preview {
+    /// The preview to show in PRs and comments (animated one if available)
+    #[must_use]
+    pub fn display_path(&self) -> &Path {
+        self.animated.as_deref().unwrap_or(&self.still)
+    }
+}
+
+impl PreviewGenerator {
+    /// Generate 256x256 preview from source texture with nearest-neighbor interpolation
+    ///
+    /// If the texture has a `.png.mcmeta` next to it, the strip is cut into frames and
+    /// an animated GIF is generated in addition to a static preview of the first frame.
+    pub fn generate(source: &Path, model_name: &str) -> Result<Preview> {
+        if !source.exists() {
+            anyhow::bail!("元テクスチャが見つかりません: {}", source.display());
+        }
+
+        let preview_path = prepare_preview_path(model_name)?;
+        let animated_path = Paths::animated_preview_path(model_name);
+
+        // Load source image
+        let img = ImageReader::open(source)
+            .with_context(|| format!("画像の読み込みに失敗しました: {}", source.display()))?
+            .decode()
+            .context("画像のデコードに失敗しました")?
+            .to_rgba8();
+
+        let frames = match read_animation(source)? {
+            Some(animation) => animation_frames(&img, &animation)?,
+            None => Vec::new(),
+        };
+
+        // Static preview: the whole texture, or the first displayed frame when animated
+        let still = frames.first().map_or(&img, |(frame, _)| frame);
+        resize(still)
+            .save_with_format(&preview_path, ImageFormat::Png)
+            .with_context(|| {
+                format!("プレビューの保存に失敗しました: {}", preview_path.display())
+            })?;
+        println!("✓ プレビューを生成しました: {}", preview_path.display());
+
+        let animated = if frames.is_empty() {
+            remove_stale_animated_preview(model_name)?;
+            None
+        } else {
+            write_gif(&animated_path, &frames)?;
+            println!(
+                "✓ アニメーションプレビューを生成しました: {}",
+                animated_path.display()
+            );
+            Some(animated_path)
+        };
+
+        Ok(Preview {
+            still: preview_path,
+            animated,
+        })
+    }
+
+    /// Render a 256x256 isometric preview of a 3D model JSON
+    ///
+    /// Uses the model's `display.gui` transform (or the vanilla block one when absent)
+    /// and the layer textures it references in the resource pack.
+    pub fn generate_3d(model_path: &Path, model_name: &str) -> Result<Preview> {
+        let model = read_json::<ItemModel>(model_path).with_context(|| {
+            format!(
+                "モデルJSONの読み込みに失敗しました: {}",
+                model_path.display()
+            )
+        })?;
+
+        let preview_path = prepare_preview_path(model_name)?;
+        render::render(&model, &render::load_textures(&model), PREVIEW_SIZE)
+            .save_with_format(&preview_path, ImageFormat::Png)
+            .with_context(|| {
+                format!("プレビューの保存に失敗しました: {}", preview_path.display())
+            })?;
+        remove_stale_animated_preview(model_name)?;
+        println!("✓ 3Dプレビューを生成しました: {}", preview_path.display());
+
+        Ok(Preview {
+            still: preview_path,
+            animated: None,
+        })
+    }
+}
+
+/// Preview PNG path, with its directory created
+fn prepare_preview_path(model_name: &str) -> Result<PathBuf> {
+    let preview_path = Paths::preview_path(model_name);
+
+    let preview_dir = preview_path
+        .parent()
+        .context("プレビューディレクトリの取得に失敗しました")?;
+
+    std::fs::create_dir_all(preview_dir).with_context(|| {
+        format!(
+            "プレビューディレクトリの作成に失敗しました: {}",
+            preview_dir.display()
+        )
+    })?;
+
+    Ok(preview_path)
+}
+
+/// Remove an animated preview left from a previous texture
+fn remove_stale_animated_preview(model_name: &str) -> Result<()> {
+    let animated_path = Paths::animated_preview_path(model_name);
+    if animated_path.is_file() {
+        std::fs::remove_file(&animated_path).with_context(|| {
+            format!(
+                "古いプレビューの削除に失敗しました: {}",
+                animated_path.display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Read `<texture>.mcmeta` if it exists
+fn read_animation(source: &Path) -> Result<Option<AnimationData>> {
+    let mut mcmeta = OsString::from(source.as_os_str());
+    mcmeta.push(".mcmeta");
+    let mcmeta = PathBuf::from(mcmeta);
+    if !mcmeta.is_file() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&mcmeta)
+        .with_context(|| format!("ファイルの読み込みに失敗しました: {}", mcmeta.display()))?;
+    let info: AnimationInfo = serde_json::from_str(&content)
+        .with_context(|| format!("JSONの解析に失敗しました: {}", mcmeta.display()))?;
+    Ok(Some(info.animation))
+}
+
+/// Cut the strip into the displayed frames, each paired with its duration in ticks
+///
+/// With `interpolate`, each tick becomes its own frame blended towards the next one.
+fn animation_frames(img: &RgbaImage, animation: &AnimationData) -> Result<Vec<(RgbaImage, u32)>> {
+    let (width, height) = img.dimensions();
+    let (frame_width, frame_height) = animation.frame_size(width, height);
+    let frame_count = animation.frame_count(width, height)?;
+    animation.validate(frame_count)?;
+
+    let columns = width / frame_width;
+    let frame = |index: u32| {
+        image::imageops::crop_imm(
+            img,
+            (index % columns) * frame_width,
+            (index / columns) * frame_height,
+            frame_width,
+            frame_height,
+        )
+        .to_image()
+    };
+
+    let sequence = animation.frame_sequence(frame_count);
+    if animation.interpolate != Some(true) {
+        return Ok(sequence
+            .into_iter()
+            .map(|(index, time)| (frame(index), time))
+            .collect());
+    }
+
+    let mut frames = Vec::new();
+    for (i, &(index, time)) in sequence.iter().enumerate() {
+        let current = frame(index);
+        let next = frame(sequence[(i + 1) % sequence.len()].0);
+        for tick in 0..time {
+            let ratio = f64::from(tick) / f64::from(time);
+            frames.push((blend(&current, &next, ratio), 1));
+        }
+    }
+    Ok(frames)
+}
+
+/// Linear blend between two frames, like Minecraft's `interpolate`
+fn blend(from: &RgbaImage, to: &RgbaImage, ratio: f64) -> RgbaImage {
+    RgbaImage::from_fn(from.width(), from.height(), |x, y| {
+        let (a, b) = (from.get_pixel(x, y), to.get_pixel(x, y));
+        // Blended channels stay within 0..=255
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let channel =
+            |i: usize| (f64::from(a[i]) * (1.0 - ratio) + f64::from(b[i]) * ratio).round() as u8;
+        image::Rgba([channel(0), channel(1), channel(2), a[3]])
+    })
+}
+
+/// Resize to 256x256 with nearest-neighbor (pixel-perfect for Minecraft textures)
+fn resize(img: &RgbaImage) -> RgbaImage {
+    image::imageops::resize(img, PREVIEW_SIZE, PREVIEW_SIZE, FilterType::Nearest)
+}
+
+fn write_gif(path: &Path, frames: &[(RgbaImage, u32)]) -> Result<()> {
+    let file = File::create(path)
+        .with_context(|| format!("プレビューの保存に失敗しました: {}", path.display()))?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .context("GIFの設定に失敗しました")?;
+    for (frame, ticks) in frames {
+        let delay = Delay::from_numer_denom_ms(ticks * MS_PER_TICK, 1);
+        encoder
+            .encode_frame(Frame::from_parts(resize(frame), 0, 0, delay))
+            .context("GIFのエンコードに失敗しました")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::animation::AnimationFrame;
+
+    /// 16x48 strip whose frames are filled with red = 0, 1, 2
+    fn strip() -> RgbaImage {
+        RgbaImage::from_fn(16, 48, |_, y| {
+            image::Rgba([u8::try_from(y / 16).unwrap(), 0, 0, 255])
+        })
+    }
+
+    #[test]
+    fn test_animation_frames_follow_sequence() {
+        let mut animation = AnimationData::with_frametime(3);
+        animation.frames = Some(vec![
+            AnimationFrame::Index(2),
+            AnimationFrame::Timed { index: 0, time: 10 },
+        ]);
+
+        let frames = animation_frames(&strip(), &animation).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].0.dimensions(), (16, 16));
+        assert_eq!(frames[0].0.get_pixel(0, 0)[0], 2);
+        assert_eq!(frames[0].1, 3);
+        assert_eq!(frames[1].0.get_pixel(0, 0)[0], 0);
+        assert_eq!(frames[1].1, 10);
+    }
+
+    #[test]
+    fn test_interpolated_frames_are_per_tick() {
+        let mut animation = AnimationData::with_frametime(2);
+        animation.interpolate = Some(true);
+
+        let frames = animation_frames(&strip(), &animation).unwrap();
+        assert_eq!(frames.len(), 6);
+        assert!(frames.iter().all(|(_, ticks)| *ticks == 1));
+        // halfway between frame 0 and frame 1
+        assert_eq!(frames[1].0.get_pixel(0, 0)[0], 1);
+    }
+
+    #[test]
+    fn test_gif_preview_timing() {
+        use image::AnimationDecoder;
+
+        let animation = AnimationData::with_frametime(4);
+        let frames = animation_frames(&strip(), &animation).unwrap();
+        let file = tempfile::Builder::new().suffix(".gif").tempfile().unwrap();
+        write_gif(file.path(), &frames).unwrap();
+
+        let decoder =
+            image::codecs::gif::GifDecoder::new(std::io::BufReader::new(file.reopen().unwrap()))
+                .unwrap();
+        let decoded = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(
+            decoded[0].buffer().dimensions(),
+            (PREVIEW_SIZE, PREVIEW_SIZE)
+        );
+        assert_eq!(decoded[0].delay().numer_denom_ms(), (200, 1));
+    }
+
+    #[test]
+    fn test_invalid_frame_index_is_error() {
+        let mut animation = AnimationData::with_frametime(1);
+        animation.frames = Some(vec![AnimationFrame::Index(5)]);
+        assert!(animation_frames(&strip(), &animation).is_err());
+    }
+}
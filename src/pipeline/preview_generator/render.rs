@@ -0,0 +1,430 @@
+//! Software renderer for 3D model previews
+//!
+//! Rasterizes the `elements` of a Blockbench-style model JSON with an
+//! orthographic projection, the same way the item is drawn in an inventory slot.
+
+use std::collections::HashMap;
+
+use image::{Rgba, RgbaImage};
+
+use crate::{
+    paths::Paths,
+    schema::models::{Axis, Direction, DisplayTransform, Element, ItemModel},
+};
+
+type Vec3 = [f64; 3];
+
+/// Size of an inventory slot in model units
+const SLOT_SIZE: f64 = 16.0;
+
+/// GUI transform used when the model has no `display.gui`
+/// (same as vanilla `block/block`)
+const DEFAULT_GUI: Transform = Transform {
+    rotation: [30.0, 225.0, 0.0],
+    translation: [0.0, 0.0, 0.0],
+    scale: [0.625, 0.625, 0.625],
+};
+
+/// Resolved GUI transform
+#[derive(Debug, Clone, Copy)]
+struct Transform {
+    rotation: Vec3,
+    translation: Vec3,
+    scale: Vec3,
+}
+
+/// Load the textures referenced by the model from the resource pack
+///
+/// The result is keyed by texture variable (e.g. `0` for `#0`).
+/// Textures outside the pack (vanilla textures) are left out and drawn
+/// as the missing texture.
+pub fn load_textures(model: &ItemModel) -> HashMap<String, RgbaImage> {
+    model
+        .textures
+        .iter()
+        .filter_map(|(key, _)| {
+            let path = Paths::texture_reference_path(model.textures.resolve(key)?)?;
+            let image = image::open(path).ok()?.to_rgba8();
+            Some((key.to_string(), image))
+        })
+        .collect()
+}
+
+/// Render the model as seen in an inventory slot into a `size` x `size` image
+pub fn render(model: &ItemModel, textures: &HashMap<String, RgbaImage>, size: u32) -> RgbaImage {
+    let gui = model.gui_transform().map_or(DEFAULT_GUI, Transform::from);
+    let mut canvas = Canvas::new(size);
+
+    for element in model.elements() {
+        for (direction, face) in element.faces.iter() {
+            let corners = corners(direction, element.from, element.to)
+                .map(|corner| canvas.project(gui.apply(rotate_element(element, corner))));
+
+            // Back-face culling: skip faces pointing away from the viewer
+            let normal = cross(sub(corners[3], corners[0]), sub(corners[1], corners[0]));
+            if normal[2] >= 0.0 {
+                continue;
+            }
+
+            let uv = face
+                .uv
+                .unwrap_or_else(|| default_uv(direction, element.from, element.to));
+            let shade = if element.shade() {
+                shade(direction)
+            } else {
+                1.0
+            };
+            let texture = textures.get(face.texture.trim_start_matches('#'));
+            canvas.draw_quad(&corners, face_uvs(uv, face.rotation()), shade, texture);
+        }
+    }
+
+    canvas.image
+}
+
+/// Apply the element rotation around its origin
+fn rotate_element(element: &Element, point: Vec3) -> Vec3 {
+    let Some(rotation) = &element.rotation else {
+        return point;
+    };
+    let mut p = rotate(sub(point, rotation.origin), rotation.axis, rotation.angle);
+    if rotation.rescale == Some(true) {
+        let factor = 1.0 / rotation.angle.to_radians().cos();
+        for (i, value) in p.iter_mut().enumerate() {
+            if i != rotation.axis.index() {
+                *value *= factor;
+            }
+        }
+    }
+    add(p, rotation.origin)
+}
+
+impl From<&DisplayTransform> for Transform {
+    fn from(transform: &DisplayTransform) -> Self {
+        Self {
+            rotation: transform.rotation(),
+            translation: transform.translation(),
+            scale: transform.scale(),
+        }
+    }
+}
+
+impl Transform {
+    /// Center the model and apply scale, rotation (Z, Y, then X) and translation
+    fn apply(&self, point: Vec3) -> Vec3 {
+        let centered = sub(point, [SLOT_SIZE / 2.0; 3]);
+        let scaled = [
+            centered[0] * self.scale[0],
+            centered[1] * self.scale[1],
+            centered[2] * self.scale[2],
+        ];
+        let rotated = rotate(
+            rotate(
+                rotate(scaled, Axis::Z, self.rotation[2]),
+                Axis::Y,
+                self.rotation[1],
+            ),
+            Axis::X,
+            self.rotation[0],
+        );
+        add(rotated, self.translation)
+    }
+}
+
+/// Corners of the face seen from outside: top-left, top-right, bottom-right, bottom-left
+fn corners(direction: Direction, from: Vec3, to: Vec3) -> [Vec3; 4] {
+    let [x0, y0, z0] = from;
+    let [x1, y1, z1] = to;
+    match direction {
+        Direction::North => [[x1, y1, z0], [x0, y1, z0], [x0, y0, z0], [x1, y0, z0]],
+        Direction::South => [[x0, y1, z1], [x1, y1, z1], [x1, y0, z1], [x0, y0, z1]],
+        Direction::West => [[x0, y1, z0], [x0, y1, z1], [x0, y0, z1], [x0, y0, z0]],
+        Direction::East => [[x1, y1, z1], [x1, y1, z0], [x1, y0, z0], [x1, y0, z1]],
+        Direction::Up => [[x0, y1, z0], [x1, y1, z0], [x1, y1, z1], [x0, y1, z1]],
+        Direction::Down => [[x0, y0, z1], [x1, y0, z1], [x1, y0, z0], [x0, y0, z0]],
+    }
+}
+
+/// UV used when the face omits `uv` (derived from the element bounds)
+fn default_uv(direction: Direction, from: Vec3, to: Vec3) -> [f64; 4] {
+    let [x0, y0, z0] = from;
+    let [x1, y1, z1] = to;
+    match direction {
+        Direction::North => [16.0 - x1, 16.0 - y1, 16.0 - x0, 16.0 - y0],
+        Direction::South => [x0, 16.0 - y1, x1, 16.0 - y0],
+        Direction::West => [z0, 16.0 - y1, z1, 16.0 - y0],
+        Direction::East => [16.0 - z1, 16.0 - y1, 16.0 - z0, 16.0 - y0],
+        Direction::Up => [x0, z0, x1, z1],
+        Direction::Down => [x0, 16.0 - z1, x1, 16.0 - z0],
+    }
+}
+
+/// Brightness of the face, like Minecraft's directional block shading
+const fn shade(direction: Direction) -> f64 {
+    match direction {
+        Direction::Up => 1.0,
+        Direction::Down => 0.5,
+        Direction::North | Direction::South => 0.8,
+        Direction::West | Direction::East => 0.6,
+    }
+}
+
+/// UVs for each corner (top-left, top-right, bottom-right, bottom-left),
+/// rotated clockwise by the face `rotation`
+fn face_uvs([u0, v0, u1, v1]: [f64; 4], rotation: u32) -> [[f64; 2]; 4] {
+    let base = [[u0, v0], [u1, v0], [u1, v1], [u0, v1]];
+    // Face rotation is a multiple of 90 degrees
+    let steps = (rotation / 90 % 4) as usize;
+    std::array::from_fn(|i| base[(i + 4 - steps) % 4])
+}
+
+struct Canvas {
+    image: RgbaImage,
+    depth: Vec<f64>,
+    size: u32,
+}
+
+impl Canvas {
+    fn new(size: u32) -> Self {
+        Self {
+            image: RgbaImage::new(size, size),
+            depth: vec![f64::NEG_INFINITY; (size * size) as usize],
+            size,
+        }
+    }
+
+    /// Model space to screen space (x, y in pixels; z towards the viewer)
+    fn project(&self, point: Vec3) -> Vec3 {
+        let size = f64::from(self.size);
+        let scale = size / SLOT_SIZE;
+        [
+            size / 2.0 + point[0] * scale,
+            size / 2.0 - point[1] * scale,
+            point[2],
+        ]
+    }
+
+    /// Fill a parallelogram face with nearest-neighbor texture sampling
+    fn draw_quad(
+        &mut self,
+        corners: &[Vec3; 4],
+        uvs: [[f64; 2]; 4],
+        shade: f64,
+        texture: Option<&RgbaImage>,
+    ) {
+        let origin = corners[0];
+        let right = sub(corners[1], origin);
+        let down = sub(corners[3], origin);
+        let det = right[0] * down[1] - right[1] * down[0];
+        if det.abs() < f64::EPSILON {
+            // Edge-on face
+            return;
+        }
+
+        let (min_x, max_x) = self.pixel_range(corners.iter().map(|c| c[0]));
+        let (min_y, max_y) = self.pixel_range(corners.iter().map(|c| c[1]));
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let dx = f64::from(x) + 0.5 - origin[0];
+                let dy = f64::from(y) + 0.5 - origin[1];
+                let s = (dx * down[1] - dy * down[0]) / det;
+                let t = (right[0] * dy - right[1] * dx) / det;
+                if !(0.0..=1.0).contains(&s) || !(0.0..=1.0).contains(&t) {
+                    continue;
+                }
+
+                let z = origin[2] + s * right[2] + t * down[2];
+                let index = (y * self.size + x) as usize;
+                if z <= self.depth[index] {
+                    continue;
+                }
+
+                let u = lerp(
+                    lerp(uvs[0][0], uvs[1][0], s),
+                    lerp(uvs[3][0], uvs[2][0], s),
+                    t,
+                );
+                let v = lerp(
+                    lerp(uvs[0][1], uvs[1][1], s),
+                    lerp(uvs[3][1], uvs[2][1], s),
+                    t,
+                );
+                let texel = texture.map_or_else(|| missing_texel(u, v), |tex| sample(tex, u, v));
+                if texel[3] == 0 {
+                    continue;
+                }
+
+                let color = shaded(texel, shade);
+                if color[3] == u8::MAX {
+                    self.depth[index] = z;
+                    self.image.put_pixel(x, y, color);
+                } else {
+                    let below = *self.image.get_pixel(x, y);
+                    self.image.put_pixel(x, y, blend_over(color, below));
+                }
+            }
+        }
+    }
+
+    /// Pixel range covering the given screen coordinates, clamped to the canvas
+    fn pixel_range(&self, values: impl Iterator<Item = f64> + Clone) -> (u32, u32) {
+        let min = values.clone().fold(f64::INFINITY, f64::min).floor();
+        let max = values.fold(f64::NEG_INFINITY, f64::max).ceil();
+        let size = f64::from(self.size);
+        // Clamped to the canvas, so the values fit in u32
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let range = (min.clamp(0.0, size) as u32, max.clamp(0.0, size) as u32);
+        range
+    }
+}
+
+/// Sample the first animation frame of a texture with UV in 0..16
+fn sample(texture: &RgbaImage, u: f64, v: f64) -> Rgba<u8> {
+    let width = texture.width();
+    let height = texture.height().min(width);
+    let texel = |coord: f64, size: u32| {
+        // Clamped to the texture size, so the value fits in u32
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let texel = (coord / 16.0 * f64::from(size))
+            .floor()
+            .clamp(0.0, f64::from(size - 1)) as u32;
+        texel
+    };
+    *texture.get_pixel(texel(u, width), texel(v, height))
+}
+
+/// Magenta and black checkerboard, like Minecraft's missing texture
+fn missing_texel(u: f64, v: f64) -> Rgba<u8> {
+    if (u < 8.0) == (v < 8.0) {
+        Rgba([248, 0, 248, 255])
+    } else {
+        Rgba([0, 0, 0, 255])
+    }
+}
+
+fn shaded(color: Rgba<u8>, shade: f64) -> Rgba<u8> {
+    // Shade is within 0..=1, so the channels stay within u8
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let channel = |value: u8| (f64::from(value) * shade).round() as u8;
+    Rgba([
+        channel(color[0]),
+        channel(color[1]),
+        channel(color[2]),
+        color[3],
+    ])
+}
+
+/// Source-over alpha compositing
+fn blend_over(src: Rgba<u8>, dst: Rgba<u8>) -> Rgba<u8> {
+    let src_alpha = f64::from(src[3]) / 255.0;
+    let dst_alpha = f64::from(dst[3]) / 255.0 * (1.0 - src_alpha);
+    let alpha = src_alpha + dst_alpha;
+    if alpha == 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    // Weighted averages of u8 values stay within u8
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let channel = |i: usize| {
+        ((f64::from(src[i]) * src_alpha + f64::from(dst[i]) * dst_alpha) / alpha).round() as u8
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let alpha = (alpha * 255.0).round() as u8;
+    Rgba([channel(0), channel(1), channel(2), alpha])
+}
+
+fn rotate(p: Vec3, axis: Axis, degrees: f64) -> Vec3 {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let [x, y, z] = p;
+    match axis {
+        Axis::X => [x, y * cos - z * sin, y * sin + z * cos],
+        Axis::Y => [x * cos + z * sin, y, -x * sin + z * cos],
+        Axis::Z => [x * cos - y * sin, x * sin + y * cos, z],
+    }
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube(display: serde_json::Value) -> ItemModel {
+        serde_json::from_value(serde_json::json!({
+            "textures": {"0": "item/cube/0", "particle": "#0"},
+            "elements": [{
+                "from": [0, 0, 0],
+                "to": [16, 16, 16],
+                "faces": {
+                    "north": {"uv": [0, 0, 16, 16], "texture": "#0"},
+                    "east": {"uv": [0, 0, 16, 16], "texture": "#0"},
+                    "south": {"uv": [0, 0, 16, 16], "texture": "#0"},
+                    "west": {"uv": [0, 0, 16, 16], "texture": "#0"},
+                    "up": {"uv": [0, 0, 16, 16], "texture": "#0"},
+                    "down": {"uv": [0, 0, 16, 16], "texture": "#0"}
+                }
+            }],
+            "display": display
+        }))
+        .unwrap()
+    }
+
+    fn white() -> HashMap<String, RgbaImage> {
+        HashMap::from([(
+            "0".to_string(),
+            RgbaImage::from_pixel(16, 16, Rgba([255, 255, 255, 255])),
+        )])
+    }
+
+    #[test]
+    fn test_front_view_fills_slot() {
+        let model = cube(serde_json::json!({"gui": {"rotation": [0, 0, 0]}}));
+        let image = render(&model, &white(), 32);
+        // Only the south face is visible, shaded like a side face
+        assert_eq!(*image.get_pixel(16, 16), Rgba([204, 204, 204, 255]));
+        assert_eq!(image.get_pixel(0, 0)[3], 255);
+    }
+
+    #[test]
+    fn test_isometric_view_shades_faces() {
+        let image = render(&cube(serde_json::json!({})), &white(), 256);
+        // Top face is unshaded, corners of the slot stay transparent
+        assert_eq!(*image.get_pixel(128, 70), Rgba([255, 255, 255, 255]));
+        assert_eq!(image.get_pixel(0, 0)[3], 0);
+        let left = image.get_pixel(80, 170)[0];
+        let right = image.get_pixel(176, 170)[0];
+        assert!(left < 255 && right < 255 && left != right);
+    }
+
+    #[test]
+    fn test_missing_texture_is_checkerboard() {
+        let model = cube(serde_json::json!({"gui": {"rotation": [0, 0, 0]}}));
+        let image = render(&model, &HashMap::new(), 32);
+        assert_ne!(image.get_pixel(4, 4), image.get_pixel(28, 4));
+    }
+
+    #[test]
+    fn test_face_rotation() {
+        let uvs = face_uvs([0.0, 0.0, 16.0, 16.0], 90);
+        assert_eq!(uvs[0], [0.0, 16.0]);
+        assert_eq!(uvs[1], [0.0, 0.0]);
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result, bail};
 
 use crate::{
     schema::animation::{AnimationData, AnimationInfo},
-    types::{IssueType, ItemModelParent},
+    types::{IssueType, ItemModelParent, Tint},
     validation::should_snake_case,
 };
 
@@ -14,6 +14,8 @@ pub enum ParsedIssue {
         image_url: String,
         animation: Option<AnimationInfo>,
         parent: ItemModelParent,
+        tints: Vec<Tint>,
+        normalize: bool,
     },
     Model3d {
         materials: Vec<String>,
@@ -25,16 +27,27 @@ pub enum ParsedIssue {
         materials: Vec<String>,
         custom_model_data: String,
     },
+    Unextend {
+        materials: Vec<String>,
+        custom_model_data: String,
+    },
 }
 
 pub struct IssueParser;
 
+/// Blockbench プロジェクト（`.bbmodel`）のURLか
+pub fn is_bbmodel_url(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.to_ascii_lowercase().ends_with(".bbmodel")
+}
+
 impl IssueParser {
     pub fn parse(body: &str, issue_type: IssueType) -> Result<ParsedIssue> {
         match issue_type {
             IssueType::Model => Self::parse_model(body),
             IssueType::Model3d => Self::parse_model3d(body),
             IssueType::Extend => Self::parse_extend(body),
+            IssueType::Unextend => Self::parse_unextend(body),
         }
     }
 
@@ -100,17 +113,30 @@ impl IssueParser {
                     None
                 } else {
                     s.parse::<u32>().ok().map(|frametime| AnimationInfo {
-                        animation: AnimationData { frametime },
+                        animation: AnimationData::with_frametime(frametime),
                     })
                 }
             });
 
+        let tints = match Self::extract_field(body, "ティント（任意）") {
+            Some(s) if s != "_No response_" => Tint::parse_list(&s)
+                .map_err(anyhow::Error::msg)
+                .context("ティントの指定が不正です")?,
+            _ => Vec::new(),
+        };
+
+        // チェックボックスは `- [X] <ラベル>` の形式になる
+        let normalize = Self::extract_field(body, "画像の自動調整（任意）")
+            .is_some_and(|s| s.to_ascii_lowercase().starts_with("- [x]"));
+
         Ok(ParsedIssue::Model {
             materials,
             custom_model_data,
             image_url,
             animation,
             parent,
+            tints,
+            normalize,
         })
     }
 
@@ -124,18 +150,17 @@ impl IssueParser {
             bail!("モデルJSONのURLは必須項目です");
         }
 
+        // .bbmodel はテクスチャを埋め込んでいるため、レイヤー画像は不要
         let layer_image_urls = Self::extract_field(body, "レイヤー画像のURLリスト")
-            .context("レイヤー画像のURLリールドフィールドが見つかりません")?;
-        if layer_image_urls == "_No response_" || layer_image_urls.is_empty() {
-            bail!("レイヤー画像のURLは必須項目です");
-        }
+            .filter(|urls| urls != "_No response_")
+            .unwrap_or_default();
         let layer_image_urls: Vec<String> = layer_image_urls
             .lines()
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
             .collect();
-        if layer_image_urls.is_empty() {
-            bail!("少なくとも1つのレイヤー画像のURLを指定してください");
+        if layer_image_urls.is_empty() && !is_bbmodel_url(&model_json_url) {
+            bail!("レイヤー画像のURLは必須項目です（.bbmodel の場合は省略できます）");
         }
 
         Ok(ParsedIssue::Model3d {
@@ -156,6 +181,16 @@ impl IssueParser {
         })
     }
 
+    fn parse_unextend(body: &str) -> Result<ParsedIssue> {
+        let materials = Self::parse_materials(body)?;
+        let custom_model_data = Self::parse_custom_model_data(body)?;
+
+        Ok(ParsedIssue::Unextend {
+            materials,
+            custom_model_data,
+        })
+    }
+
     fn extract_field(body: &str, field_name: &str) -> Option<String> {
         let pattern = format!(
             r"###\s*{}\s*\n\s*(.+?)(?:\n\n###|\n\s*\n|$)",
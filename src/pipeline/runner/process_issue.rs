@@ -2,14 +2,23 @@ use anyhow::{Context, Result};
 use tempfile::tempdir;
 
 use crate::{
-    cmd::{Run, add, extend},
+    cmd::{
+        Run,
+        add::{
+            self, ContentOptions,
+            model::{ModelReport, TextureSource},
+        },
+        extend, unextend,
+    },
     config::{REPO_NAME, REPO_OWNER},
     pipeline::{
         github_client::GitHubClient,
-        image_downloader::ImageDownloader,
+        image_downloader::{DownloadErrorKind, DownloadedTexture, ImageDownloader},
+        image_normalizer::NormalizeChange,
+        image_validator::ContentReport,
         pr_creator::PrCreator,
         preview_generator::PreviewGenerator,
-        runner::issue_parser::{IssueParser, ParsedIssue},
+        runner::issue_parser::{IssueParser, ParsedIssue, is_bbmodel_url},
     },
     types::{GithubReaction, IssueType},
 };
@@ -18,6 +27,7 @@ pub struct IssueProcessor {
     github_client: GitHubClient,
     image_downloader: ImageDownloader,
     pr_creator: PrCreator,
+    content: ContentOptions,
 }
 
 impl IssueProcessor {
@@ -26,9 +36,17 @@ impl IssueProcessor {
             github_client: GitHubClient::from_env()?,
             image_downloader: ImageDownloader::new()?,
             pr_creator: PrCreator::new()?,
+            content: ContentOptions::default(),
         })
     }
 
+    /// モデル追加時のテクスチャの内容チェックの設定
+    #[must_use]
+    pub fn with_content_options(mut self, content: ContentOptions) -> Self {
+        self.content = content;
+        self
+    }
+
     fn format_materials(materials: &[String]) -> String {
         materials
             .iter()
@@ -37,6 +55,54 @@ impl IssueProcessor {
             .join(", ")
     }
 
+    /// テクスチャの内容チェックの警告を Markdown のセクションにする（警告がなければ空）
+    fn format_warnings(report: &ContentReport) -> String {
+        let warnings: Vec<_> = report
+            .warnings()
+            .map(|warning| format!("- {}", warning))
+            .collect();
+        if warnings.is_empty() {
+            return String::new();
+        }
+        format!(
+            "\n### ⚠️ テクスチャの警告\n\n{}\n\n追加は完了していますが、ゲーム内の見た目を確認してください。\n",
+            warnings.join("\n")
+        )
+    }
+
+    /// 画像の自動調整の内容を Markdown のセクションにする（調整していなければ空）
+    fn format_normalize_changes(changes: &[NormalizeChange]) -> String {
+        if changes.is_empty() {
+            return String::new();
+        }
+        let changes: Vec<_> = changes
+            .iter()
+            .map(|change| format!("- {}", change))
+            .collect();
+        format!("\n### 📐 画像の自動調整\n\n{}\n", changes.join("\n"))
+    }
+
+    /// 書き込んだアニメーション設定を PR 本文の行にする（アニメーションでなければ空）
+    fn format_animation(report: &ModelReport) -> String {
+        let Some(animation) = &report.animation else {
+            return String::new();
+        };
+        let mut line = format!("- **アニメーション:** frametime = {}", animation.frametime);
+        if let Some(frames) = &animation.frames {
+            let frames: Vec<_> = frames.iter().map(ToString::to_string).collect();
+            line.push_str(&format!("、フレーム順序 = `{}`", frames.join(", ")));
+        }
+        if animation.interpolate == Some(true) {
+            line.push_str("、補間あり");
+        }
+        if report.gif_timing_overridden {
+            line.push_str(
+                "\n- ⚠️ 指定した frametime でGIFのフレームごとの表示時間を上書きしています",
+            );
+        }
+        line
+    }
+
     fn format_pr_branch(prefix: &str, issue_number: u64) -> String {
         std::env::var("PR_BRANCH")
             .unwrap_or_else(|_| format!("{}-{}/issue-{}", prefix, prefix, issue_number))
@@ -81,6 +147,8 @@ impl IssueProcessor {
                 image_url,
                 animation,
                 parent,
+                tints,
+                normalize,
             } => {
                 println!("  タイプ: Model");
                 println!("  マテリアル: {}", materials.join(", "));
@@ -90,53 +158,79 @@ impl IssueProcessor {
                 if let Some(anim) = &animation {
                     println!("  Frametime: {}", anim.animation.frametime);
                 }
+                let tint_names: Vec<_> = tints.iter().map(ToString::to_string).collect();
+                if !tints.is_empty() {
+                    println!("  ティント: {}", tint_names.join(", "));
+                }
 
                 let dir = tempdir()?;
-                let image_path = dir.path().join(format!("{}.png", custom_model_data));
-
-                self.image_downloader
-                    .download(&image_url, &image_path)
-                    .context("画像のダウンロードに失敗しました")?;
+                let texture = match self
+                    .image_downloader
+                    .download_texture(&image_url, dir.path(), &custom_model_data)
+                    .context("画像のダウンロードに失敗しました")?
+                {
+                    DownloadedTexture::Image(path) => TextureSource::Image(path),
+                    DownloadedTexture::Gif(path) => TextureSource::Frames(path),
+                };
 
-                let frametime = animation.as_ref().map(|a| a.animation.frametime);
+                // アニメーションテクスチャは自動調整しない
+                let is_animated =
+                    animation.is_some() || matches!(texture, TextureSource::Frames(_));
+                if normalize && is_animated {
+                    println!("\n💡 アニメーションテクスチャのため、画像の自動調整は行いません");
+                }
 
                 let add_cmd = add::model::Model::new(
                     materials.clone(),
                     Some(custom_model_data.clone()),
-                    frametime,
-                    image_path,
+                    animation.as_ref().map(|a| a.animation.clone()),
+                    texture,
                     parent,
-                );
-                add_cmd.run()?;
+                    None,
+                    tints,
+                )
+                .with_normalize(normalize && !is_animated)
+                .with_content_options(self.content.clone());
+                let report = add_cmd.run_with_report()?;
+                let normalize_line = Self::format_normalize_changes(&report.normalize_changes);
+                let warnings = Self::format_warnings(&report.content);
 
                 println!("\n🖼️  プレビュー画像を生成中...");
                 let texture_path = crate::paths::Paths::texture_path(&custom_model_data);
-                let preview_path = PreviewGenerator::generate(&texture_path, &custom_model_data)
+                let preview = PreviewGenerator::generate(&texture_path, &custom_model_data)
                     .context("プレビュー画像の生成に失敗しました")?;
 
                 let pr_branch = Self::format_pr_branch("model", issue_number);
 
-                let preview_url = format!(
-                    "https://raw.githubusercontent.com/{}/{}/{}/{}",
-                    REPO_OWNER,
-                    REPO_NAME,
-                    pr_branch,
-                    preview_path.to_string_lossy()
-                );
+                let raw_url = |path: &std::path::Path| {
+                    format!(
+                        "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                        REPO_OWNER,
+                        REPO_NAME,
+                        pr_branch,
+                        path.to_string_lossy()
+                    )
+                };
+                let preview_url = raw_url(preview.display_path());
+                let still_line = if preview.animated.is_some() {
+                    format!(
+                        "\n[静止画（最初のフレーム）]({})\n",
+                        raw_url(&preview.still)
+                    )
+                } else {
+                    String::new()
+                };
 
                 println!("✓ プレビュー画像の生成が完了しました");
 
                 // 4. Create PR
                 let pr_title = format!("✨ 2Dモデルを追加: {}", custom_model_data);
-                let animation_line = animation
-                    .as_ref()
-                    .map(|a| {
-                        format!(
-                            "- **アニメーション:** frametime = {}",
-                            a.animation.frametime
-                        )
-                    })
-                    .unwrap_or_default();
+                let animation_line = Self::format_animation(&report);
+                let tint_line = if tint_names.is_empty() {
+                    String::new()
+                } else {
+                    format!("- **ティント:** `{}`", tint_names.join("`, `"))
+                };
 
                 let pr_body = format!(
                     r"## 📦 2Dカスタムモデルの追加
@@ -149,11 +243,12 @@ Resolves #{}
 - **マテリアル:** {}
 - **モデル親:** `{}`
 {}
+{}
 
 ### プレビュー（256×256、ピクセルパーフェクト）
 
 ![Custom Model Preview]({})
-
+{}{}{}
 ---
 
 このPRは自動生成されました。",
@@ -162,7 +257,11 @@ Resolves #{}
                     Self::format_materials(&materials),
                     parent.as_str(),
                     animation_line,
-                    preview_url
+                    tint_line,
+                    preview_url,
+                    still_line,
+                    normalize_line,
+                    warnings
                 );
 
                 let pr_number = self.pr_creator.create_pr(
@@ -182,9 +281,9 @@ Resolves #{}
 ### プレビュー（256×256、ピクセルパーフェクト）
 
 ![Custom Model Preview]({})
-
+{}{}{}
 このカスタムモデルをリソースパックに追加するため、PRをレビューしてマージしてください。",
-                    pr_number, preview_url
+                    pr_number, preview_url, still_line, normalize_line, warnings
                 );
 
                 self.github_client
@@ -210,30 +309,76 @@ Resolves #{}
                 println!("  レイヤー画像 URL: {}", layer_image_urls.join("\n"));
 
                 let dir = tempdir()?;
-                let model_json_path = dir.path().join(format!("{}.json", custom_model_data));
-                self.image_downloader
-                    .download(&model_json_url, &model_json_path)
-                    .context("モデルJSONのダウンロードに失敗しました")?;
-
-                let mut layer_image_paths = Vec::new();
-                for (i, url) in layer_image_urls.iter().enumerate() {
-                    let image_path = dir.path().join(format!("{}_{}.png", custom_model_data, i));
+                let (layer_count, report) = if is_bbmodel_url(&model_json_url) {
+                    let bbmodel_path = dir.path().join(format!("{}.bbmodel", custom_model_data));
                     self.image_downloader
-                        .download(url, &image_path)
-                        .context(format!("レイヤー画像 {} のダウンロードに失敗しました", i))?;
-                    layer_image_paths.push(image_path);
-                }
+                        .download_file(&model_json_url, &bbmodel_path)
+                        .context("Blockbench プロジェクトのダウンロードに失敗しました")?;
+
+                    let add_cmd = add::bbmodel::Bbmodel::new(
+                        materials.clone(),
+                        custom_model_data.clone(),
+                        bbmodel_path,
+                        None,
+                    )
+                    .with_content_options(self.content.clone());
+                    let report = add_cmd.run_with_report()?;
+
+                    let layer_count = std::fs::read_dir(crate::paths::Paths::texture_path_dir(
+                        &custom_model_data,
+                    ))
+                    .map(|entries| entries.count())
+                    .unwrap_or_default();
+                    (layer_count, report)
+                } else {
+                    let model_json_path = dir.path().join(format!("{}.json", custom_model_data));
+                    self.image_downloader
+                        .download_file(&model_json_url, &model_json_path)
+                        .context("モデルJSONのダウンロードに失敗しました")?;
+
+                    let mut layer_image_paths = Vec::new();
+                    for (i, url) in layer_image_urls.iter().enumerate() {
+                        let image_path =
+                            dir.path().join(format!("{}_{}.png", custom_model_data, i));
+                        self.image_downloader
+                            .download(url, &image_path)
+                            .context(format!("レイヤー画像 {} のダウンロードに失敗しました", i))?;
+                        layer_image_paths.push(image_path);
+                    }
+
+                    let add_cmd = add::model3d::Model3D::new(
+                        materials.clone(),
+                        custom_model_data.clone(),
+                        model_json_path,
+                        layer_image_paths,
+                        Vec::new(),
+                        None,
+                    )
+                    .with_content_options(self.content.clone());
+                    let report = add_cmd.run_with_report()?;
+
+                    (layer_image_urls.len(), report)
+                };
+                let warnings = Self::format_warnings(&report);
 
-                let add_cmd = add::model3d::Model3D::new(
-                    materials.clone(),
-                    custom_model_data.clone(),
-                    model_json_path,
-                    layer_image_paths,
+                println!("\n🖼️  プレビュー画像を生成中...");
+                let model_path = crate::paths::Paths::model_path(&custom_model_data);
+                let preview = PreviewGenerator::generate_3d(&model_path, &custom_model_data)
+                    .context("プレビュー画像の生成に失敗しました")?;
+
+                let pr_branch = Self::format_pr_branch("model3d", issue_number);
+
+                let preview_url = format!(
+                    "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                    REPO_OWNER,
+                    REPO_NAME,
+                    pr_branch,
+                    preview.display_path().to_string_lossy()
                 );
-                add_cmd.run()?;
+
+                println!("✓ プレビュー画像の生成が完了しました");
 
                 // 4. Create PR
-                let pr_branch = Self::format_pr_branch("model3d", issue_number);
 
                 let pr_title = format!("✨ 3Dモデルを追加: {}", custom_model_data);
                 let pr_body = format!(
@@ -247,13 +392,19 @@ Resolves #{}
 - **マテリアル:** {}
 - **レイヤー数:** {}
 
+### プレビュー（256×256、インベントリ表示）
+
+![Custom Model Preview]({})
+{}
 ---
 
 このPRは自動生成されました。",
                     issue_number,
                     custom_model_data,
                     Self::format_materials(&materials),
-                    layer_image_urls.len()
+                    layer_count,
+                    preview_url,
+                    warnings
                 );
 
                 let pr_number = self.pr_creator.create_pr(
@@ -270,8 +421,12 @@ Resolves #{}
 
 **Pull Request:** #{}
 
+### プレビュー（256×256、インベントリ表示）
+
+![Custom Model Preview]({})
+{}
 3Dモデルがリソースパックに追加されました。PRをレビューしてマージしてください。",
-                    pr_number
+                    pr_number, preview_url, warnings
                 );
 
                 self.github_client
@@ -295,6 +450,7 @@ Resolves #{}
                 let extend_cmd = extend::Extend {
                     materials: materials.clone(),
                     custom_model_data: custom_model_data.clone(),
+                    index: None,
                 };
                 extend_cmd.run()?;
 
@@ -360,6 +516,101 @@ Resolves #{}
                 // 6. ThumbsUp reaction
                 self.add_success_reaction(issue_number)?;
 
+                println!("\n=== 処理が正常に完了しました ===\n");
+                Ok(())
+            }
+            ParsedIssue::Unextend {
+                materials,
+                custom_model_data,
+            } => {
+                println!("  タイプ: Unextend");
+                println!("  外すマテリアル: {}", materials.join(", "));
+                println!("  カスタムモデルデータ: {}", custom_model_data);
+
+                let unextend_cmd = unextend::Unextend {
+                    materials: materials.clone(),
+                    custom_model_data: custom_model_data.clone(),
+                };
+                let removed = unextend_cmd.execute()?;
+                let not_present: Vec<String> = materials
+                    .iter()
+                    .filter(|m| !removed.contains(m))
+                    .cloned()
+                    .collect();
+
+                // 4. Create PR
+                let pr_branch = Self::format_pr_branch("unextend", issue_number);
+
+                let pr_title = format!("✂️ マテリアルを取り外し: {}", custom_model_data);
+                let materials_list = removed
+                    .iter()
+                    .map(|m| format!("  - `{}`", m))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let not_present_line = if not_present.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "- **適用されていなかったマテリアル:** {}",
+                        Self::format_materials(&not_present)
+                    )
+                };
+
+                let pr_body = format!(
+                    r"## 📦 マテリアルの取り外し
+
+Resolves #{}
+
+### 変更内容
+
+- **カスタムモデルデータ:** `{}`
+- **取り外されたマテリアル:**
+{}
+{}
+
+---
+
+このPRは自動生成されました。",
+                    issue_number, custom_model_data, materials_list, not_present_line
+                );
+
+                let pr_number = self.pr_creator.create_pr(
+                    &pr_branch,
+                    &pr_title,
+                    &pr_body,
+                    actor,
+                    actor_email,
+                )?;
+
+                // 5. Post success comment
+                let materials_list_comment = removed
+                    .iter()
+                    .map(|m| format!("- `{}`", m))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                let comment = format!(
+                    r"## ✅ マテリアルの取り外しが完了しました！
+
+**Pull Request:** #{}
+
+### 取り外されたマテリアル
+
+{}
+
+{}
+
+既存のカスタムモデルから上記のマテリアルが取り外されました。PRをレビューしてマージしてください。",
+                    pr_number, materials_list_comment, not_present_line
+                );
+
+                self.github_client
+                    .comment_issue(issue_number, &comment)
+                    .context("成功コメントの投稿に失敗しました")?;
+
+                // 6. ThumbsUp reaction
+                self.add_success_reaction(issue_number)?;
+
                 println!("\n=== 処理が正常に完了しました ===\n");
                 Ok(())
             }
@@ -372,12 +623,23 @@ Resolves #{}
         error_message: &str,
         workflow_url: &str,
     ) -> Result<()> {
+        // ログ全体の中から、CLIが最後に出力したエラー行を原因として目立たせる
+        let cause = error_message
+            .lines()
+            .rev()
+            .find_map(|line| line.trim().strip_prefix("エラー: "))
+            .map(|cause| format!("### 原因\n\n> {}\n\n", cause))
+            .unwrap_or_default();
+        // ダウンロードの失敗は、種類ごとに対処方法を説明する
+        let advice = DownloadErrorKind::find_in(error_message)
+            .map(|kind| format!("### 対処方法\n\n{}\n\n", kind.advice()))
+            .unwrap_or_default();
         let comment = format!(
             r"## ❌ カスタムモデルの処理に失敗しました
 
 ワークフローでエラーが発生しました。詳細は[ワークフローログ]({})を確認してください。
 
-### エラー内容
+{}{}### エラー内容
 
 ```
 {}
@@ -390,7 +652,7 @@ Resolves #{}
 3. **新しいIssueを作成する**（正しい情報を入力）
 
 ⚠️ **注意:** このIssueを編集しても再実行されません。新しいIssueを作成してください。",
-            workflow_url, error_message
+            workflow_url, cause, advice, error_message
         );
 
         self.github_client
@@ -3,20 +3,214 @@
 //! Minecraftのアニメーションテクスチャ用の `.mcmeta` ファイルの
 //! スキーマを定義します。
 
+use std::{fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
+/// `frametime` を省略した場合の既定値
+const DEFAULT_FRAMETIME: u32 = 1;
+
 /// アニメーション情報（.mcmetaファイルの最上位構造）
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct AnimationInfo {
     /// アニメーション設定
     pub animation: AnimationData,
 }
 
 /// アニメーション設定
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct AnimationData {
     /// フレームごとのtick数（1 tick = 1/20秒）
+    #[serde(default = "default_frametime")]
     pub frametime: u32,
+    /// フレーム間を補間するか
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpolate: Option<bool>,
+    /// 表示するフレームの順序（省略時は画像の上から順にすべて）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frames: Option<Vec<AnimationFrame>>,
+    /// フレームの幅（ピクセル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// フレームの高さ（ピクセル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+}
+
+/// `frames` の要素
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum AnimationFrame {
+    /// フレーム番号のみ（`frametime` だけ表示）
+    Index(u32),
+    /// フレーム番号と表示するtick数
+    Timed { index: u32, time: u32 },
+}
+
+const fn default_frametime() -> u32 {
+    DEFAULT_FRAMETIME
+}
+
+impl AnimationData {
+    /// `frametime` のみを指定したアニメーション設定を作成
+    #[must_use]
+    pub const fn with_frametime(frametime: u32) -> Self {
+        Self {
+            frametime,
+            interpolate: None,
+            frames: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    /// 画像サイズから1フレームのサイズ（幅, 高さ）を求める
+    ///
+    /// `width` と `height` を省略した場合は、画像の幅と高さのうち
+    /// 小さい方を一辺とする正方形になります。
+    #[must_use]
+    pub fn frame_size(&self, image_width: u32, image_height: u32) -> (u32, u32) {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, image_height),
+            (None, Some(height)) => (image_width, height),
+            (None, None) => {
+                let size = image_width.min(image_height);
+                (size, size)
+            }
+        }
+    }
+
+    /// 画像に含まれるフレーム数を求める
+    ///
+    /// # Errors
+    ///
+    /// 画像サイズがフレームサイズで割り切れない場合
+    pub fn frame_count(&self, image_width: u32, image_height: u32) -> anyhow::Result<u32> {
+        let (width, height) = self.frame_size(image_width, image_height);
+        if width == 0 || height == 0 {
+            anyhow::bail!("フレームの幅と高さは1以上である必要があります");
+        }
+        if !image_width.is_multiple_of(width) || !image_height.is_multiple_of(height) {
+            anyhow::bail!(
+                "画像サイズ({image_width}x{image_height})がフレームサイズ({width}x{height})で割り切れません"
+            );
+        }
+        Ok((image_width / width) * (image_height / height))
+    }
+
+    /// 表示するフレームの（フレーム番号, tick数）の一覧
+    #[must_use]
+    pub fn frame_sequence(&self, frame_count: u32) -> Vec<(u32, u32)> {
+        match &self.frames {
+            Some(frames) => frames
+                .iter()
+                .map(|frame| (frame.index(), frame.time().unwrap_or(self.frametime)))
+                .collect(),
+            None => (0..frame_count).map(|i| (i, self.frametime)).collect(),
+        }
+    }
+
+    /// すべてのフレームを `frametime` tickで表示するか
+    #[must_use]
+    pub fn is_uniform(&self, frametime: u32) -> bool {
+        self.frametime == frametime
+            && self
+                .frames
+                .iter()
+                .flatten()
+                .all(|frame| frame.time().is_none_or(|time| time == frametime))
+    }
+
+    /// アニメーション1周の合計tick数
+    #[must_use]
+    pub fn total_duration(&self, frame_count: u32) -> u32 {
+        self.frame_sequence(frame_count)
+            .iter()
+            .map(|(_, time)| time)
+            .sum()
+    }
+
+    /// フレーム番号とtick数が妥当か検証
+    ///
+    /// # Errors
+    ///
+    /// - `frametime` またはフレームごとのtick数が0の場合
+    /// - `frames` が空、または画像のフレーム数を超える番号を含む場合
+    pub fn validate(&self, frame_count: u32) -> anyhow::Result<()> {
+        if self.frametime == 0 {
+            anyhow::bail!("frametime は1以上である必要があります");
+        }
+        if let Some(frames) = &self.frames {
+            if frames.is_empty() {
+                anyhow::bail!("frames に少なくとも1つのフレームを指定してください");
+            }
+            for frame in frames {
+                if frame.index() >= frame_count {
+                    anyhow::bail!(
+                        "フレーム番号 {} は画像のフレーム数({frame_count})を超えています",
+                        frame.index()
+                    );
+                }
+                if frame.time() == Some(0) {
+                    anyhow::bail!(
+                        "フレーム {} のtick数は1以上である必要があります",
+                        frame.index()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AnimationFrame {
+    /// フレーム番号
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        match self {
+            Self::Index(index) | Self::Timed { index, .. } => *index,
+        }
+    }
+
+    /// 個別に指定されたtick数
+    #[must_use]
+    pub const fn time(&self) -> Option<u32> {
+        match self {
+            Self::Index(_) => None,
+            Self::Timed { time, .. } => Some(*time),
+        }
+    }
+}
+
+/// `<番号>` または `<番号>:<tick数>` の形式で解析
+impl FromStr for AnimationFrame {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("'{}' はフレームとして解釈できません（例: 0, 1:10）", s))
+        };
+        match s.split_once(':') {
+            Some((index, time)) => Ok(Self::Timed {
+                index: parse(index)?,
+                time: parse(time)?,
+            }),
+            None => Ok(Self::Index(parse(s)?)),
+        }
+    }
+}
+
+impl fmt::Display for AnimationFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Timed { index, time } => write!(f, "{index}:{time}"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -26,13 +220,11 @@ mod tests {
     #[test]
     fn test_animation_info_serialization() {
         let info = AnimationInfo {
-            animation: AnimationData { frametime: 5 },
+            animation: AnimationData::with_frametime(5),
         };
 
         let json = serde_json::to_string(&info).unwrap();
-        assert!(json.contains("animation"));
-        assert!(json.contains("frametime"));
-        assert!(json.contains("5"));
+        assert_eq!(json, r#"{"animation":{"frametime":5}}"#);
     }
 
     #[test]
@@ -42,4 +234,59 @@ mod tests {
 
         assert_eq!(info.animation.frametime, 10);
     }
+
+    #[test]
+    fn test_full_animation_round_trip() {
+        let json = serde_json::json!({
+            "animation": {
+                "frametime": 2,
+                "interpolate": true,
+                "frames": [0, {"index": 2, "time": 10}, 1],
+                "width": 16,
+                "height": 8
+            }
+        });
+        let info: AnimationInfo = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(info.animation.frames.as_ref().unwrap()[1].time(), Some(10));
+        assert_eq!(serde_json::to_value(&info).unwrap(), json);
+
+        let info: AnimationInfo = serde_json::from_str(r#"{"animation":{}}"#).unwrap();
+        assert_eq!(info.animation.frametime, 1);
+    }
+
+    #[test]
+    fn test_is_uniform() {
+        let mut animation = AnimationData::with_frametime(2);
+        assert!(animation.is_uniform(2));
+        assert!(!animation.is_uniform(3));
+
+        animation.frames = Some(vec![
+            AnimationFrame::Index(0),
+            AnimationFrame::Timed { index: 1, time: 2 },
+        ]);
+        assert!(animation.is_uniform(2));
+
+        animation.frames = Some(vec![
+            AnimationFrame::Index(0),
+            AnimationFrame::Timed { index: 1, time: 5 },
+        ]);
+        assert!(!animation.is_uniform(2));
+    }
+
+    #[test]
+    fn test_frame_count_and_duration() {
+        let mut data = AnimationData::with_frametime(2);
+        assert_eq!(data.frame_count(16, 64).unwrap(), 4);
+        assert_eq!(data.total_duration(4), 8);
+
+        data.height = Some(8);
+        assert_eq!(data.frame_size(16, 64), (16, 8));
+        assert_eq!(data.frame_count(16, 64).unwrap(), 8);
+        assert!(data.frame_count(16, 60).is_err());
+
+        data.frames = Some(vec!["0".parse().unwrap(), "3:10".parse().unwrap()]);
+        assert_eq!(data.total_duration(8), 12);
+        assert!(data.validate(8).is_ok());
+        assert!(data.validate(3).is_err());
+    }
 }
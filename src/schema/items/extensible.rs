@@ -0,0 +1,82 @@
+//! 未知の種類を保持できる列挙型
+//!
+//! アイテムモデル定義は将来のバージョンやMODで種類が増えるため、
+//! 既知の種類は型付きで扱い、未知の種類は元のJSONをそのまま保持します。
+
+/// タグ付き列挙型に `Serialize` / `Deserialize` を実装する
+///
+/// 対象の型は `#[serde(remote = "Self", tag = ...)]` で派生し、
+/// `#[serde(skip)] Unknown(serde_json::Map<String, serde_json::Value>)`
+/// バリアントを持つ必要があります。
+///
+/// タグの値が既知のID（`minecraft:` 名前空間の有無は問わない）であれば型付きで
+/// 読み込み、そうでなければ `Unknown` として元のJSONを保持します。
+///
+/// `restore = path` を指定すると、型付きで読み込んだ直後に
+/// `path(&mut value, &元のJSON)` を呼び出します。書き出しは一度 `serde_json::Value` を
+/// 経由するため、`restore` で `#[serde(flatten)]` したフィールドに元の表記を残すと、
+/// 派生実装が書き出した同じキーの値を（位置を保ったまま）上書きできます。
+macro_rules! extensible_enum {
+    ($ty:ident, $tag:literal, [$($known:literal),* $(,)?] $(,)?) => {
+        extensible_enum!(@impl $ty, $tag, [$($known),*], |_, _| {});
+    };
+    ($ty:ident, $tag:literal, [$($known:literal),* $(,)?], restore = $restore:path $(,)?) => {
+        extensible_enum!(@impl $ty, $tag, [$($known),*], $restore);
+    };
+    (@impl $ty:ident, $tag:literal, [$($known:literal),*], $restore:expr) => {
+        impl $ty {
+            /// 既知の種類のID（`minecraft:` 名前空間を除く）
+            const KNOWN: &'static [&'static str] = &[$($known),*];
+        }
+
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Self::Unknown(map) => serde::Serialize::serialize(map, serializer),
+                    known => $ty::serialize(known, serde_json::value::Serializer)
+                        .map_err(serde::ser::Error::custom)?
+                        .serialize(serializer),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let map = <serde_json::Map<String, serde_json::Value> as serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+                let is_known = map
+                    .get($tag)
+                    .and_then(serde_json::Value::as_str)
+                    .is_none_or(|id| Self::KNOWN.contains(&id.strip_prefix("minecraft:").unwrap_or(id)));
+                if is_known {
+                    let mut value = $ty::deserialize(serde_json::Value::Object(map.clone()))
+                        .map_err(serde::de::Error::custom)?;
+                    let restore: fn(&mut $ty, &serde_json::Map<String, serde_json::Value>) = $restore;
+                    restore(&mut value, &map);
+                    Ok(value)
+                } else {
+                    Ok(Self::Unknown(map))
+                }
+            }
+        }
+    };
+}
+
+pub(super) use extensible_enum;
+
+/// `minecraft:` 名前空間なしで書かれたIDを `extra` に残す
+///
+/// `extra` は `#[serde(flatten)]` で書き出されるため、派生実装が書き出した
+/// 正規のIDを同じ位置で上書きし、読み込んだときと同じ表記になります。
+pub(super) fn keep_short_id(
+    extra: &mut serde_json::Map<String, serde_json::Value>,
+    key: &str,
+    original: &serde_json::Map<String, serde_json::Value>,
+) {
+    if let Some(serde_json::Value::String(id)) = original.get(key)
+        && !id.contains(':')
+    {
+        extra.insert(key.to_string(), serde_json::Value::String(id.clone()));
+    }
+}
@@ -0,0 +1,860 @@
+//! アイテムリソース定義
+//!
+//! Minecraft 1.21.4以降のアイテム定義ファイル（`assets/minecraft/items/*.json`）の
+//! スキーマを定義します。すべてのモデルの種類・プロパティ・ティントソースを型付きで扱い、
+//! 未知の種類は元のJSONのまま保持します。
+//!
+//! このツールが管理するカスタムモデルデータは、次の形で登録します。
+//!
+//! - 文字列の `custom_model_data`: 最上位の `minecraft:select` のケース
+//! - 数値の `custom_model_data`: その `fallback` に入れ子にした `minecraft:range_dispatch` のエントリー
+//!
+//! 手書きのアイテム定義にケースを追加する場合は、既存の定義をフォールバックとして包みます。
+
+mod extensible;
+mod model;
+mod number;
+mod property;
+mod special;
+mod tint;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use model::has_unknown_fields;
+pub use model::{
+    BundleSelectedItemModel, CompositeModel, ConditionModel, EmptyModel, ItemCase, ItemModelNode,
+    ItemRangeEntry, ModelReference, RangeDispatchModel, SelectModel, SelectWhen, SpecialModel,
+};
+pub use number::Float;
+pub use property::{ConditionProperty, NumericProperty, SelectProperty};
+pub use special::SpecialModelType;
+pub use tint::{Color, TintSource};
+
+/// アイテムリソース（アイテム定義ファイルの最上位構造）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemResource {
+    /// アイテムモデル
+    pub model: ItemModelNode,
+    /// 持ち替え時のアニメーションを再生するか
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hand_animation_on_swap: Option<bool>,
+    /// GUI上でスロットからはみ出して描画するか
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oversized_in_gui: Option<bool>,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ItemResource {
+    /// フォールバックモデルを指定して新しいアイテムリソースを作成
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use processor::schema::items::ItemResource;
+    ///
+    /// let resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+    /// ```
+    pub fn new_with_fallback(fallback_model: &str) -> Self {
+        Self::with_fallback_node(ItemModelNode::model(fallback_model))
+    }
+
+    /// フォールバックのモデル定義を指定して新しいアイテムリソースを作成
+    ///
+    /// 弓など、バニラのアイテム定義が条件分岐を含む場合に使用します。
+    pub fn with_fallback_node(fallback: ItemModelNode) -> Self {
+        Self {
+            model: ItemModelNode::Select(SelectModel {
+                property: SelectProperty::CustomModelData { index: None },
+                cases: Vec::new(),
+                fallback: Some(Box::new(fallback)),
+                extra: Map::new(),
+            }),
+            hand_animation_on_swap: None,
+            oversized_in_gui: None,
+            extra: Map::new(),
+        }
+    }
+
+    /// カスタムモデルケースの一覧（文字列）
+    pub fn cases(&self) -> &[ItemCase] {
+        as_custom_select(&self.model).map_or(&[], |select| &select.cases)
+    }
+
+    /// 数値エントリーの一覧（しきい値の昇順）
+    pub fn entries(&self) -> &[ItemRangeEntry] {
+        let dispatch = match as_custom_select(&self.model) {
+            Some(select) => select.fallback.as_deref().and_then(as_custom_dispatch),
+            None => as_custom_dispatch(&self.model),
+        };
+        dispatch.map_or(&[], |dispatch| &dispatch.entries)
+    }
+
+    /// カスタムモデルケースを追加
+    pub fn add_case(&mut self, case: ItemCase) {
+        ensure_custom_select(&mut self.model).cases.push(case);
+    }
+
+    /// 数値エントリーを追加
+    ///
+    /// エントリーはしきい値の昇順に並べます。
+    pub fn add_entry(&mut self, entry: ItemRangeEntry) {
+        let select = ensure_custom_select(&mut self.model);
+        let entries = &mut ensure_custom_dispatch(&mut select.fallback).entries;
+        let position =
+            entries.partition_point(|existing| existing.threshold.get() <= entry.threshold.get());
+        entries.insert(position, entry);
+    }
+
+    /// 指定したしきい値の数値エントリーを取得
+    ///
+    /// しきい値は [`same_threshold`] で比較します。
+    pub fn find_entry(&self, threshold: f64) -> Option<&ItemRangeEntry> {
+        self.entries()
+            .iter()
+            .find(|entry| same_threshold(entry.threshold.get(), threshold))
+    }
+
+    /// カスタムモデルデータが残っておらず、単純なモデル参照のみか確認
+    ///
+    /// この状態のアイテム定義ファイルは削除しても表示が変わりません。
+    pub fn is_fallback_only(&self) -> bool {
+        self.hand_animation_on_swap.is_none()
+            && self.oversized_in_gui.is_none()
+            && self.extra.is_empty()
+            && matches!(
+                &self.model,
+                ItemModelNode::Model(reference)
+                    if reference.tints.is_none() && !has_unknown_fields(&reference.extra)
+            )
+    }
+
+    /// 指定したカスタムモデルデータのケースを削除
+    ///
+    /// そのカスタムモデルデータのモデルのみを参照する数値エントリーも削除し、
+    /// 空になった `select` / `range_dispatch` はフォールバックに置き換えます。
+    ///
+    /// # Returns
+    ///
+    /// 削除した `when` の値と数値エントリーの数
+    pub fn remove_case(&mut self, custom_model_data: &str) -> usize {
+        let mut removed = 0;
+
+        if let Some(select) = as_custom_select_mut(&mut self.model) {
+            select.cases.retain_mut(|case| match &mut case.when {
+                SelectWhen::Single(value) if value == custom_model_data => {
+                    removed += 1;
+                    false
+                }
+                SelectWhen::Multiple(values) => {
+                    let before = values.len();
+                    values.retain(|value| value != custom_model_data);
+                    removed += before - values.len();
+                    values.len() == before || !values.is_empty()
+                }
+                _ => true,
+            });
+        }
+
+        if let Some(dispatch) = self.custom_dispatch_mut() {
+            let before = dispatch.entries.len();
+            dispatch
+                .entries
+                .retain(|entry| !entry.model.refers_to_item(custom_model_data));
+            removed += before - dispatch.entries.len();
+        }
+
+        if removed > 0 {
+            self.prune();
+        }
+        removed
+    }
+
+    /// カスタムモデルデータのケースを別名に書き換える
+    ///
+    /// `when` の値と、木構造内のすべての既定のモデルパス（`item/<名前>` と
+    /// `item/<名前>/...`）を書き換えます。
+    ///
+    /// # Returns
+    ///
+    /// 書き換えた `when` の値とモデルパスの数
+    pub fn rename_case(&mut self, old: &str, new: &str) -> usize {
+        let mut renamed = 0;
+
+        if let Some(select) = as_custom_select_mut(&mut self.model) {
+            for case in &mut select.cases {
+                let values = match &mut case.when {
+                    SelectWhen::Single(value) => std::slice::from_mut(value),
+                    SelectWhen::Multiple(values) => values.as_mut_slice(),
+                    SelectWhen::Other(_) => &mut [],
+                };
+                for value in values.iter_mut().filter(|value| *value == old) {
+                    *value = new.to_string();
+                    renamed += 1;
+                }
+            }
+        }
+
+        renamed += self
+            .model
+            .rewrite_model_references(&mut |path| rename_resource_path(path, old, new));
+
+        renamed
+    }
+
+    /// 指定したカスタムモデルデータのケースが存在するか確認
+    pub fn has_case(&self, custom_model_data: &str) -> bool {
+        self.cases()
+            .iter()
+            .any(|case| case.when.contains(custom_model_data))
+    }
+
+    /// 木構造内のすべてのモデルパス参照を列挙
+    pub fn model_references(&self) -> Vec<&str> {
+        self.model.model_references()
+    }
+
+    fn custom_dispatch_mut(&mut self) -> Option<&mut RangeDispatchModel> {
+        if as_custom_select(&self.model).is_some() {
+            as_custom_select_mut(&mut self.model)?
+                .fallback
+                .as_deref_mut()
+                .and_then(as_custom_dispatch_mut)
+        } else {
+            as_custom_dispatch_mut(&mut self.model)
+        }
+    }
+
+    /// 空になった `select` / `range_dispatch` をフォールバックに置き換える
+    fn prune(&mut self) {
+        if let Some(select) = as_custom_select_mut(&mut self.model) {
+            if let Some(fallback) = select.fallback.as_deref_mut() {
+                unwrap_empty_dispatch(fallback);
+            }
+            if select.cases.is_empty()
+                && !has_unknown_fields(&select.extra)
+                && let Some(fallback) = select.fallback.take()
+            {
+                self.model = *fallback;
+            }
+        }
+        unwrap_empty_dispatch(&mut self.model);
+    }
+}
+
+fn as_custom_select(node: &ItemModelNode) -> Option<&SelectModel> {
+    match node {
+        ItemModelNode::Select(select) if select.property.is_custom_model_data() => Some(select),
+        _ => None,
+    }
+}
+
+fn as_custom_select_mut(node: &mut ItemModelNode) -> Option<&mut SelectModel> {
+    match node {
+        ItemModelNode::Select(select) if select.property.is_custom_model_data() => Some(select),
+        _ => None,
+    }
+}
+
+fn as_custom_dispatch(node: &ItemModelNode) -> Option<&RangeDispatchModel> {
+    match node {
+        ItemModelNode::RangeDispatch(dispatch) if dispatch.property.is_custom_model_data() => {
+            Some(dispatch)
+        }
+        _ => None,
+    }
+}
+
+fn as_custom_dispatch_mut(node: &mut ItemModelNode) -> Option<&mut RangeDispatchModel> {
+    match node {
+        ItemModelNode::RangeDispatch(dispatch) if dispatch.property.is_custom_model_data() => {
+            Some(dispatch)
+        }
+        _ => None,
+    }
+}
+
+/// `custom_model_data` の `select` でなければ、既存のモデルをフォールバックとして包む
+fn ensure_custom_select(node: &mut ItemModelNode) -> &mut SelectModel {
+    if as_custom_select(node).is_none() {
+        let fallback = std::mem::replace(node, ItemModelNode::empty());
+        *node = ItemModelNode::Select(SelectModel {
+            property: SelectProperty::CustomModelData { index: None },
+            cases: Vec::new(),
+            fallback: Some(Box::new(fallback)),
+            extra: Map::new(),
+        });
+    }
+    match node {
+        ItemModelNode::Select(select) => select,
+        _ => unreachable!("直前に select で包んでいます"),
+    }
+}
+
+/// `custom_model_data` の `range_dispatch` でなければ、既存のモデルをフォールバックとして包む
+fn ensure_custom_dispatch(slot: &mut Option<Box<ItemModelNode>>) -> &mut RangeDispatchModel {
+    if slot.as_deref().and_then(as_custom_dispatch).is_none() {
+        let fallback = slot.take();
+        *slot = Some(Box::new(ItemModelNode::RangeDispatch(RangeDispatchModel {
+            property: NumericProperty::CustomModelData { index: None },
+            scale: None,
+            entries: Vec::new(),
+            fallback,
+            extra: Map::new(),
+        })));
+    }
+    match slot.as_deref_mut() {
+        Some(ItemModelNode::RangeDispatch(dispatch)) => dispatch,
+        _ => unreachable!("直前に range_dispatch で包んでいます"),
+    }
+}
+
+/// エントリーが空の `custom_model_data` の `range_dispatch` をフォールバックに置き換える
+fn unwrap_empty_dispatch(node: &mut ItemModelNode) {
+    if let Some(dispatch) = as_custom_dispatch_mut(node)
+        && dispatch.entries.is_empty()
+        && !has_unknown_fields(&dispatch.extra)
+        && let Some(fallback) = dispatch.fallback.take()
+    {
+        *node = *fallback;
+    }
+}
+
+/// `range_dispatch` のしきい値が同じ値として扱われるか
+///
+/// ゲームはしきい値と `custom_model_data` の値を32ビット浮動小数点数として読み込むため、
+/// `f32` に丸めた値が一致すれば同じしきい値になります（例: `1.0` と `1.000_000_01`）。
+/// 丸めた値をビット列で比較し、`0.0` と `-0.0` は同じとみなします。
+/// NaN は [`validate_index`](crate::utils::add::validate_index) で事前に拒否されます。
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn same_threshold(a: f64, b: f64) -> bool {
+    let round = |value: f64| (value as f32 + 0.0).to_bits();
+    round(a) == round(b)
+}
+
+/// `item/<old>` または `item/<old>/...` を `<new>` に置き換えたパスを返す
+///
+/// `minecraft:` 名前空間の有無は維持します。
+#[must_use]
+pub fn rename_resource_path(path: &str, old: &str, new: &str) -> Option<String> {
+    let (namespace, rest) = match path.strip_prefix("minecraft:") {
+        Some(rest) => ("minecraft:", rest),
+        None => ("", path),
+    };
+    let rest = rest.strip_prefix("item/")?.strip_prefix(old)?;
+    (rest.is_empty() || rest.starts_with('/')).then(|| format!("{namespace}item/{new}{rest}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_resource_path() {
+        assert_eq!(
+            rename_resource_path("minecraft:item/old/0", "old", "new").as_deref(),
+            Some("minecraft:item/new/0")
+        );
+        assert_eq!(
+            rename_resource_path("item/old", "old", "new").as_deref(),
+            Some("item/new")
+        );
+        assert_eq!(rename_resource_path("item/older", "old", "new"), None);
+    }
+
+    #[test]
+    fn test_same_threshold() {
+        assert!(same_threshold(1.0, 1.0));
+        assert!(same_threshold(1.0, 1.000_000_01));
+        assert!(same_threshold(0.0, -0.0));
+        assert!(!same_threshold(1.0, 1.5));
+        assert!(!same_threshold(16_777_216.0, 16_777_218.0));
+    }
+
+    /// 手書きのアイテム定義の例（すべての種類を含む）
+    fn handcrafted() -> serde_json::Value {
+        serde_json::json!({
+            "model": {
+                "type": "minecraft:condition",
+                "property": "minecraft:using_item",
+                "on_true": {
+                    "type": "minecraft:range_dispatch",
+                    "property": "minecraft:use_duration",
+                    "remaining": false,
+                    "scale": 0.05,
+                    "entries": [
+                        {"threshold": 0.65, "model": {"type": "minecraft:model", "model": "minecraft:item/bow_pulling_1"}}
+                    ],
+                    "fallback": {"type": "minecraft:model", "model": "minecraft:item/bow_pulling_0"}
+                },
+                "on_false": {
+                    "type": "minecraft:composite",
+                    "models": [
+                        {
+                            "type": "minecraft:model",
+                            "model": "minecraft:item/leather_helmet",
+                            "tints": [
+                                {"type": "minecraft:dye", "default": -6265536},
+                                {"type": "minecraft:constant", "value": [1.0, 0.5, 0.0]},
+                                {"type": "minecraft:custom_model_data", "index": 1, "default": 16777215}
+                            ]
+                        },
+                        {
+                            "type": "minecraft:select",
+                            "property": "minecraft:local_time",
+                            "pattern": "MM-dd",
+                            "cases": [
+                                {"when": ["12-24", "12-25"], "model": {"type": "minecraft:empty"}}
+                            ]
+                        },
+                        {
+                            "type": "minecraft:select",
+                            "property": "minecraft:component",
+                            "component": "minecraft:damage",
+                            "cases": [
+                                {"when": 3, "model": {"type": "minecraft:bundle/selected_item"}}
+                            ]
+                        },
+                        {
+                            "type": "minecraft:special",
+                            "base": "minecraft:item/chest",
+                            "model": {"type": "minecraft:chest", "texture": "minecraft:normal", "openness": 0.5}
+                        },
+                        {"type": "mymod:sparkle", "speed": 3},
+                        {
+                            "type": "minecraft:condition",
+                            "property": "mymod:is_raining",
+                            "radius": 8,
+                            "on_true": {"type": "minecraft:empty"},
+                            "on_false": {"type": "minecraft:empty"}
+                        }
+                    ]
+                }
+            },
+            "hand_animation_on_swap": false
+        })
+    }
+
+    #[test]
+    fn test_item_case_new() {
+        let case = ItemCase::new("test_model");
+        assert_eq!(case.when, "test_model");
+        assert_eq!(case.model.model_path(), Some("item/test_model"));
+        assert!(matches!(case.model, ItemModelNode::Model(_)));
+    }
+
+    #[test]
+    fn test_item_resource_new_with_fallback() {
+        let resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        let ItemModelNode::Select(select) = &resource.model else {
+            panic!("select ではありません");
+        };
+        assert!(select.property.is_custom_model_data());
+        assert_eq!(
+            select
+                .fallback
+                .as_deref()
+                .and_then(ItemModelNode::model_path),
+            Some("minecraft:item/diamond_sword")
+        );
+        assert!(resource.cases().is_empty());
+    }
+
+    #[test]
+    fn test_item_resource_add_case() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        let case = ItemCase::new("custom_sword");
+
+        resource.add_case(case);
+
+        assert_eq!(resource.cases().len(), 1);
+        assert_eq!(resource.cases()[0].when, "custom_sword");
+    }
+
+    #[test]
+    fn test_item_resource_remove_case() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        resource.add_case(ItemCase::new("a"));
+        resource.add_case(ItemCase::new("b"));
+        resource.add_case(ItemCase::new("a"));
+
+        assert_eq!(resource.remove_case("a"), 2);
+        assert_eq!(resource.remove_case("missing"), 0);
+        assert!(resource.has_case("b"));
+        assert!(!resource.has_case("a"));
+    }
+
+    #[test]
+    fn test_item_resource_rename_case() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        resource.add_case(ItemCase::new("old_name"));
+        resource.add_case(ItemCase::new("other"));
+
+        // when とモデルパスの2か所
+        assert_eq!(resource.rename_case("old_name", "new_name"), 2);
+        assert_eq!(resource.cases()[0].when, "new_name");
+        assert_eq!(
+            resource.cases()[0].model.model_path(),
+            Some("item/new_name")
+        );
+        assert_eq!(resource.cases()[1].when, "other");
+        assert_eq!(resource.rename_case("old_name", "new_name"), 0);
+    }
+
+    #[test]
+    fn test_item_resource_without_entries_keeps_plain_fallback() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        resource.add_case(ItemCase::new("custom_sword"));
+
+        let value = serde_json::to_value(&resource).unwrap();
+        assert_eq!(
+            value["model"]["fallback"],
+            serde_json::json!({"type": "minecraft:model", "model": "minecraft:item/diamond_sword"})
+        );
+    }
+
+    #[test]
+    fn test_item_resource_entries_round_trip() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        resource.add_case(ItemCase::new("custom_sword"));
+        resource.add_entry(ItemRangeEntry::new(20.0, "custom_sword"));
+        resource.add_entry(ItemRangeEntry::new(10.0, "other_sword"));
+
+        let value = serde_json::to_value(&resource).unwrap();
+        let fallback = &value["model"]["fallback"];
+        assert_eq!(fallback["type"], "minecraft:range_dispatch");
+        assert_eq!(fallback["property"], "minecraft:custom_model_data");
+        assert_eq!(fallback["entries"][0]["threshold"], 10.0);
+        assert_eq!(
+            fallback["fallback"]["model"],
+            "minecraft:item/diamond_sword"
+        );
+
+        let parsed: ItemResource = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, resource);
+        assert_eq!(parsed.entries().len(), 2);
+        assert!(
+            parsed
+                .find_entry(20.0)
+                .unwrap()
+                .model
+                .is_item("custom_sword")
+        );
+        assert!(parsed.find_entry(30.0).is_none());
+    }
+
+    #[test]
+    fn test_item_resource_remove_and_rename_entries() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        resource.add_case(ItemCase::new("b"));
+        resource.add_entry(ItemRangeEntry::new(1.0, "a"));
+        resource.add_entry(ItemRangeEntry::new(2.0, "b"));
+
+        assert_eq!(resource.rename_case("a", "c"), 1);
+        assert!(resource.find_entry(1.0).unwrap().model.is_item("c"));
+
+        assert_eq!(resource.remove_case("c"), 1);
+        assert!(!resource.is_fallback_only());
+        assert_eq!(resource.remove_case("b"), 2);
+        assert!(resource.is_fallback_only());
+        assert_eq!(
+            resource.model.model_path(),
+            Some("minecraft:item/diamond_sword")
+        );
+    }
+
+    #[test]
+    fn test_handcrafted_item_round_trip() {
+        let value = handcrafted();
+        let resource: ItemResource = serde_json::from_value(value.clone()).unwrap();
+
+        let ItemModelNode::Condition(condition) = &resource.model else {
+            panic!("condition ではありません");
+        };
+        assert_eq!(condition.property, ConditionProperty::UsingItem);
+        let ItemModelNode::Composite(composite) = condition.on_false.as_ref() else {
+            panic!("composite ではありません");
+        };
+        assert!(matches!(composite.models[4], ItemModelNode::Unknown(_)));
+        let ItemModelNode::Condition(unknown) = &composite.models[5] else {
+            panic!("condition ではありません");
+        };
+        assert!(matches!(unknown.property, ConditionProperty::Unknown(_)));
+
+        assert_eq!(serde_json::to_value(&resource).unwrap(), value);
+    }
+
+    #[test]
+    fn test_real_item_files_round_trip_byte_for_byte() {
+        let mut count = 0;
+        for entry in std::fs::read_dir(crate::paths::Paths::ITEMS).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let original = std::fs::read_to_string(&path).unwrap();
+            let resource: ItemResource = serde_json::from_str(&original).unwrap();
+            let written = serde_json::to_string_pretty(&resource).unwrap();
+            assert_eq!(written, original, "{}", path.display());
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn test_unnamespaced_ids_are_accepted() {
+        let resource: ItemResource = serde_json::from_value(serde_json::json!({
+            "model": {"type": "model", "model": "item/stick", "tints": [{"type": "potion", "default": 0}]}
+        }))
+        .unwrap();
+        let ItemModelNode::Model(reference) = &resource.model else {
+            panic!("model ではありません");
+        };
+        assert!(matches!(
+            reference.tints.as_deref(),
+            Some([TintSource::Potion {
+                default: Color::Packed(0),
+                ..
+            }])
+        ));
+    }
+
+    /// 名前空間なしのID・整数・未知のフィールドを、ティントと特殊モデル、プロパティに含む例
+    fn handwritten_tints_and_specials() -> serde_json::Value {
+        serde_json::json!({
+            "model": {
+                "type": "composite",
+                "models": [
+                    {
+                        "type": "model",
+                        "model": "item/potion",
+                        "tints": [
+                            {"type": "potion", "default": 0},
+                            {"type": "minecraft:dye", "default": 1, "note": "x"},
+                            {"type": "constant", "value": [1, 0, 0.5]},
+                            {"type": "grass", "temperature": 1, "downfall": 0.5},
+                            {"type": "custom_model_data", "index": 1, "default": 2, "z": [1.0]}
+                        ]
+                    },
+                    {
+                        "type": "special",
+                        "base": "item/chest",
+                        "model": {"type": "chest", "texture": "normal", "openness": 1, "lid": "x"}
+                    },
+                    {
+                        "type": "special",
+                        "base": "item/head",
+                        "model": {"type": "minecraft:head", "kind": "zombie", "animation": 0}
+                    },
+                    {"type": "special", "base": "item/shield", "model": {"type": "shield", "note": 1}},
+                    {
+                        "type": "condition",
+                        "property": "component",
+                        "predicate": "damage",
+                        "value": {"min": 1},
+                        "on_true": {"type": "model", "model": "item/a"},
+                        "on_false": {"type": "model", "model": "item/b"},
+                        "note": 1.0
+                    },
+                    {
+                        "type": "select",
+                        "property": "local_time",
+                        "pattern": "HH",
+                        "fallback": {"type": "model", "model": "item/a"},
+                        "cases": [{"when": "01", "model": {"type": "model", "model": "item/b"}}]
+                    },
+                    {
+                        "type": "range_dispatch",
+                        "property": "use_cycle",
+                        "period": 1,
+                        "scale": 1,
+                        "entries": []
+                    }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn test_tints_specials_and_properties_round_trip() {
+        let value = handwritten_tints_and_specials();
+        let original = serde_json::to_string_pretty(&value).unwrap();
+        let resource: ItemResource = serde_json::from_str(&original).unwrap();
+
+        // 型付きで読み込む
+        let ItemModelNode::Composite(composite) = &resource.model else {
+            panic!("composite ではありません");
+        };
+        let ItemModelNode::Model(reference) = &composite.models[0] else {
+            panic!("model ではありません");
+        };
+        let tints = reference.tints.as_deref().unwrap();
+        assert!(matches!(tints[1], TintSource::Dye { .. }));
+        assert!(matches!(tints[3], TintSource::Grass { .. }));
+        let ItemModelNode::Special(chest) = &composite.models[1] else {
+            panic!("special ではありません");
+        };
+        assert!(matches!(chest.model, SpecialModelType::Chest { .. }));
+
+        // IDの表記・数値の表記・未知のフィールド・キーの順序がそのまま残る
+        assert_eq!(serde_json::to_value(&resource).unwrap(), value);
+        assert_eq!(serde_json::to_string_pretty(&resource).unwrap(), original);
+    }
+
+    /// 手書きのアイテム定義の例（名前空間なしのID、整数のしきい値、未知のフィールドを含む）
+    fn handwritten_short_form() -> serde_json::Value {
+        serde_json::json!({
+            "model": {
+                "type": "select",
+                "property": "custom_model_data",
+                "editor_note": "手書き",
+                "cases": [
+                    {"when": "a", "model": {"type": "model", "model": "item/a", "shade": true}, "comment": 1}
+                ],
+                "fallback": {
+                    "type": "range_dispatch",
+                    "property": "custom_model_data",
+                    "scale": 2,
+                    "entries": [
+                        {"threshold": 1, "model": {"type": "empty", "debug": "x"}},
+                        {"threshold": 1.0, "model": {"type": "bundle/selected_item"}},
+                        {"threshold": 2.5, "model": {"type": "minecraft:model", "model": "item/c"}, "note": "n"}
+                    ],
+                    "fallback": {
+                        "type": "condition",
+                        "property": "using_item",
+                        "on_true": {
+                            "type": "composite",
+                            "models": [{"type": "special", "base": "item/chest", "model": {"type": "minecraft:chest", "texture": "minecraft:normal"}, "z": 0}],
+                            "layer": 2
+                        },
+                        "on_false": {
+                            "type": "range_dispatch",
+                            "property": "use_cycle",
+                            "period": 10,
+                            "entries": [],
+                            "fallback": {"type": "model", "model": "minecraft:item/stick"}
+                        },
+                        "priority": 0.5
+                    }
+                }
+            },
+            "oversized_in_gui": true,
+            "mymod:extra": {"a": [1, 2.0]}
+        })
+    }
+
+    #[test]
+    fn test_short_form_round_trip() {
+        let value = handwritten_short_form();
+        let resource: ItemResource = serde_json::from_value(value.clone()).unwrap();
+
+        // 名前空間なしのIDでも型付きで読み込む
+        let ItemModelNode::Select(select) = &resource.model else {
+            panic!("select ではありません");
+        };
+        assert!(select.property.is_custom_model_data());
+        assert_eq!(resource.entries().len(), 3);
+        assert_eq!(resource.entries()[0].threshold.get(), 1.0);
+        assert!(resource.extra.contains_key("mymod:extra"));
+
+        // ID・数値の表記（1 と 1.0）・未知のフィールドをそのまま書き戻す
+        assert_eq!(serde_json::to_value(&resource).unwrap(), value);
+    }
+
+    #[test]
+    fn test_extra_fields_survive_edits() {
+        let mut resource: ItemResource = serde_json::from_value(handwritten_short_form()).unwrap();
+
+        resource.add_case(ItemCase::new("b"));
+        resource.add_entry(ItemRangeEntry::new(3.0, "b"));
+        assert_eq!(resource.remove_case("b"), 2);
+        assert_eq!(
+            serde_json::to_value(&resource).unwrap(),
+            handwritten_short_form()
+        );
+
+        // 未知のフィールドを持つ select はケースが空になっても残す
+        assert_eq!(resource.remove_case("a"), 1);
+        let value = serde_json::to_value(&resource).unwrap();
+        assert_eq!(value["model"]["type"], "select");
+        assert_eq!(value["model"]["editor_note"], "手書き");
+        assert_eq!(value["model"]["cases"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_short_form_fallback_only() {
+        let mut resource: ItemResource = serde_json::from_value(serde_json::json!({
+            "model": {
+                "type": "select",
+                "property": "custom_model_data",
+                "cases": [{"when": "a", "model": {"type": "model", "model": "item/a"}}],
+                "fallback": {"type": "model", "model": "item/stick"}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(resource.remove_case("a"), 1);
+        assert!(resource.is_fallback_only());
+        assert_eq!(
+            serde_json::to_value(&resource).unwrap(),
+            serde_json::json!({"model": {"type": "model", "model": "item/stick"}})
+        );
+
+        let with_extra: ItemResource = serde_json::from_value(serde_json::json!({
+            "model": {"type": "model", "model": "item/stick", "future_field": 1}
+        }))
+        .unwrap();
+        assert!(!with_extra.is_fallback_only());
+    }
+
+    #[test]
+    fn test_known_type_with_invalid_fields_is_error() {
+        let value = serde_json::json!({"model": {"type": "minecraft:model"}});
+        assert!(serde_json::from_value::<ItemResource>(value).is_err());
+    }
+
+    #[test]
+    fn test_add_case_wraps_handcrafted_model() {
+        let mut resource: ItemResource = serde_json::from_value(handcrafted()).unwrap();
+        let original = resource.model.clone();
+
+        resource.add_case(ItemCase::new("custom_bow"));
+        resource.add_entry(ItemRangeEntry::new(5.0, "custom_bow"));
+        assert!(resource.has_case("custom_bow"));
+        assert_eq!(resource.entries().len(), 1);
+        assert!(
+            resource
+                .model_references()
+                .contains(&"minecraft:item/bow_pulling_0")
+        );
+
+        assert_eq!(resource.remove_case("custom_bow"), 2);
+        assert_eq!(resource.model, original);
+        assert!(!resource.is_fallback_only());
+    }
+
+    #[test]
+    fn test_remove_case_from_multiple_when() {
+        let mut resource: ItemResource = serde_json::from_value(serde_json::json!({
+            "model": {
+                "type": "minecraft:select",
+                "property": "minecraft:custom_model_data",
+                "cases": [
+                    {"when": ["a", "b"], "model": {"type": "minecraft:model", "model": "item/shared"}}
+                ],
+                "fallback": {"type": "minecraft:model", "model": "minecraft:item/stick"}
+            }
+        }))
+        .unwrap();
+
+        assert!(resource.has_case("b"));
+        assert_eq!(resource.remove_case("a"), 1);
+        assert_eq!(resource.cases().len(), 1);
+        assert_eq!(resource.remove_case("b"), 1);
+        assert!(resource.is_fallback_only());
+    }
+}
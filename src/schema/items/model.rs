@@ -0,0 +1,494 @@
+//! アイテムモデルのノード定義
+//!
+//! アイテム定義ファイルの `model` に入る各種類のモデルを定義します。
+//! `composite` / `condition` / `select` / `range_dispatch` は子のモデルを持つため、
+//! 全体は木構造になります。
+//!
+//! 既知の種類も未知のフィールドを `extra` に保持し、読み込んだ定義を
+//! そのまま書き戻せるようにしています。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{
+    extensible::{extensible_enum, keep_short_id},
+    number::Float,
+    property::{ConditionProperty, NumericProperty, SelectProperty},
+    special::SpecialModelType,
+    tint::TintSource,
+};
+
+/// アイテムモデル
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
+pub enum ItemModelNode {
+    /// モデルファイルを描画
+    #[serde(rename = "minecraft:model", alias = "model")]
+    Model(ModelReference),
+    /// 複数のモデルを重ねて描画
+    #[serde(rename = "minecraft:composite", alias = "composite")]
+    Composite(CompositeModel),
+    /// 真偽値プロパティで切り替え
+    #[serde(rename = "minecraft:condition", alias = "condition")]
+    Condition(ConditionModel),
+    /// プロパティの値で切り替え
+    #[serde(rename = "minecraft:select", alias = "select")]
+    Select(SelectModel),
+    /// 数値プロパティのしきい値で切り替え
+    #[serde(rename = "minecraft:range_dispatch", alias = "range_dispatch")]
+    RangeDispatch(RangeDispatchModel),
+    /// 何も描画しない
+    #[serde(rename = "minecraft:empty", alias = "empty")]
+    Empty(EmptyModel),
+    /// バンドルで選択中のアイテムを描画
+    #[serde(
+        rename = "minecraft:bundle/selected_item",
+        alias = "bundle/selected_item"
+    )]
+    BundleSelectedItem(BundleSelectedItemModel),
+    /// ゲーム内で描画される特殊モデル
+    #[serde(rename = "minecraft:special", alias = "special")]
+    Special(SpecialModel),
+    /// 未知のモデル（元のJSONを保持）
+    #[serde(skip)]
+    Unknown(Map<String, Value>),
+}
+
+extensible_enum!(
+    ItemModelNode,
+    "type",
+    [
+        "model",
+        "composite",
+        "condition",
+        "select",
+        "range_dispatch",
+        "empty",
+        "bundle/selected_item",
+        "special",
+    ],
+    restore = ItemModelNode::restore
+);
+
+/// `minecraft:model` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModelReference {
+    /// モデルパス
+    pub model: String,
+    /// ティントソース一覧
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tints: Option<Vec<TintSource>>,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:composite` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CompositeModel {
+    /// 重ねて描画するモデル一覧
+    pub models: Vec<ItemModelNode>,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:condition` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConditionModel {
+    /// 判定するプロパティ
+    #[serde(flatten)]
+    pub property: ConditionProperty,
+    /// 真の場合のモデル
+    pub on_true: Box<ItemModelNode>,
+    /// 偽の場合のモデル
+    pub on_false: Box<ItemModelNode>,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:select` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SelectModel {
+    /// 選択プロパティ
+    #[serde(flatten)]
+    pub property: SelectProperty,
+    /// どのケースにも一致しない場合のモデル
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<Box<ItemModelNode>>,
+    /// ケース一覧
+    pub cases: Vec<ItemCase>,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:select` のケース
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemCase {
+    /// 一致させる値
+    pub when: SelectWhen,
+    /// 使用するモデル
+    pub model: ItemModelNode,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// ケースの一致条件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SelectWhen {
+    /// 単一の値
+    Single(String),
+    /// いずれかの値
+    Multiple(Vec<String>),
+    /// 文字列以外の値（`component` プロパティなど）
+    Other(Value),
+}
+
+/// `minecraft:range_dispatch` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RangeDispatchModel {
+    /// 数値プロパティ
+    #[serde(flatten)]
+    pub property: NumericProperty,
+    /// プロパティの値に掛ける倍率
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Float>,
+    /// エントリー一覧
+    pub entries: Vec<ItemRangeEntry>,
+    /// どのしきい値にも満たない場合のモデル
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<Box<ItemModelNode>>,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:range_dispatch` のエントリー
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemRangeEntry {
+    /// しきい値
+    pub threshold: Float,
+    /// 使用するモデル
+    pub model: ItemModelNode,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:special` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpecialModel {
+    /// パーティクルや表示位置に使うモデルパス
+    pub base: String,
+    /// 特殊モデルの種類
+    pub model: SpecialModelType,
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:empty` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EmptyModel {
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `minecraft:bundle/selected_item` の定義
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BundleSelectedItemModel {
+    /// 未知のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ItemModelNode {
+    /// モデルパスを参照する `minecraft:model` を作成
+    pub fn model(path: &str) -> Self {
+        Self::Model(ModelReference {
+            model: path.to_string(),
+            tints: None,
+            extra: Map::new(),
+        })
+    }
+
+    /// 何も描画しない `minecraft:empty` を作成
+    pub fn empty() -> Self {
+        Self::Empty(EmptyModel::default())
+    }
+
+    /// カスタムモデルデータの既定のモデルパス（`item/<名前>`）を参照する `minecraft:model` を作成
+    pub fn item(custom_model_data: &str) -> Self {
+        Self::model(&format!("item/{custom_model_data}"))
+    }
+
+    /// `minecraft:model` の場合はティントソースを設定
+    ///
+    /// 空の場合は `tints` を書き出しません。
+    pub fn set_tints(&mut self, tints: Vec<TintSource>) {
+        if let Self::Model(reference) = self {
+            reference.tints = (!tints.is_empty()).then_some(tints);
+        }
+    }
+
+    /// `minecraft:model` の場合はモデルパスを返す
+    pub fn model_path(&self) -> Option<&str> {
+        match self {
+            Self::Model(reference) => Some(&reference.model),
+            _ => None,
+        }
+    }
+
+    /// 既定のモデルパス（`item/<名前>`）からカスタムモデルデータ名を取得
+    pub fn item_name(&self) -> Option<&str> {
+        self.model_path()?
+            .trim_start_matches("minecraft:")
+            .strip_prefix("item/")
+    }
+
+    /// 既定のモデルパス（`item/<名前>`）を参照しているか確認
+    ///
+    /// `minecraft:` 名前空間の有無は問いません。
+    pub fn is_item(&self, custom_model_data: &str) -> bool {
+        self.item_name() == Some(custom_model_data)
+    }
+
+    /// 木構造内のモデルパスが、指定したカスタムモデルデータのモデルのみを参照しているか確認
+    ///
+    /// `item/<名前>` と、状態ごとのモデル `item/<名前>/<状態>` が対象です。
+    pub fn refers_to_item(&self, custom_model_data: &str) -> bool {
+        let references = self.model_references();
+        !references.is_empty()
+            && references
+                .iter()
+                .all(|reference| custom_model_name(reference) == Some(custom_model_data))
+    }
+
+    /// 木構造内で最初に参照されているカスタムモデルデータ名
+    pub fn custom_model_name(&self) -> Option<&str> {
+        self.model_references()
+            .into_iter()
+            .find_map(custom_model_name)
+    }
+
+    /// 木構造内のすべてのモデルパス参照を列挙
+    ///
+    /// `minecraft:model` の `model` と `minecraft:special` の `base` が対象です。
+    pub fn model_references(&self) -> Vec<&str> {
+        let mut references = Vec::new();
+        self.collect_references(&mut references);
+        references
+    }
+
+    fn collect_references<'a>(&'a self, references: &mut Vec<&'a str>) {
+        match self {
+            Self::Model(reference) => references.push(&reference.model),
+            Self::Special(special) => references.push(&special.base),
+            _ => {}
+        }
+        for child in self.children() {
+            child.collect_references(references);
+        }
+    }
+
+    /// 木構造内のすべてのモデルパス参照を書き換える
+    ///
+    /// # Returns
+    ///
+    /// 書き換えた参照の数
+    pub fn rewrite_model_references(
+        &mut self,
+        rewrite: &mut impl FnMut(&str) -> Option<String>,
+    ) -> usize {
+        let target = match self {
+            Self::Model(reference) => Some(&mut reference.model),
+            Self::Special(special) => Some(&mut special.base),
+            _ => None,
+        };
+        let mut rewritten = 0;
+        if let Some(path) = target
+            && let Some(new_path) = rewrite(path)
+        {
+            *path = new_path;
+            rewritten += 1;
+        }
+        for child in self.children_mut() {
+            rewritten += child.rewrite_model_references(rewrite);
+        }
+        rewritten
+    }
+
+    /// 読み込んだJSONの表記を復元する
+    ///
+    /// - `flatten` したプロパティのフィールドは `extra` にも読み込まれるため取り除く
+    /// - `minecraft:` 名前空間なしで書かれた種類・プロパティのIDは、
+    ///   同じ表記で書き出せるよう `extra` に残す（書き出し時に正規のIDを上書きします）
+    fn restore(&mut self, original: &Map<String, Value>) {
+        let (extra, property) = match self {
+            Self::Model(reference) => (&mut reference.extra, None),
+            Self::Composite(composite) => (&mut composite.extra, None),
+            Self::Condition(condition) => (
+                &mut condition.extra,
+                serde_json::to_value(&condition.property).ok(),
+            ),
+            Self::Select(select) => (
+                &mut select.extra,
+                serde_json::to_value(&select.property).ok(),
+            ),
+            Self::RangeDispatch(dispatch) => (
+                &mut dispatch.extra,
+                serde_json::to_value(&dispatch.property).ok(),
+            ),
+            Self::Empty(empty) => (&mut empty.extra, None),
+            Self::BundleSelectedItem(selected) => (&mut selected.extra, None),
+            Self::Special(special) => (&mut special.extra, None),
+            Self::Unknown(_) => return,
+        };
+
+        let has_property = property.is_some();
+        if let Some(Value::Object(property)) = property {
+            for key in property.keys() {
+                extra.remove(key);
+            }
+        }
+
+        for key in ["type", "property"] {
+            if key == "property" && !has_property {
+                continue;
+            }
+            keep_short_id(extra, key, original);
+        }
+    }
+
+    fn children(&self) -> Vec<&Self> {
+        match self {
+            Self::Composite(composite) => composite.models.iter().collect(),
+            Self::Condition(condition) => vec![&condition.on_true, &condition.on_false],
+            Self::Select(select) => select
+                .cases
+                .iter()
+                .map(|case| &case.model)
+                .chain(select.fallback.as_deref())
+                .collect(),
+            Self::RangeDispatch(dispatch) => dispatch
+                .entries
+                .iter()
+                .map(|entry| &entry.model)
+                .chain(dispatch.fallback.as_deref())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Self> {
+        match self {
+            Self::Composite(composite) => composite.models.iter_mut().collect(),
+            Self::Condition(condition) => vec![&mut condition.on_true, &mut condition.on_false],
+            Self::Select(select) => select
+                .cases
+                .iter_mut()
+                .map(|case| &mut case.model)
+                .chain(select.fallback.as_deref_mut())
+                .collect(),
+            Self::RangeDispatch(dispatch) => dispatch
+                .entries
+                .iter_mut()
+                .map(|entry| &mut entry.model)
+                .chain(dispatch.fallback.as_deref_mut())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// `extra` に未知のフィールドがあるか
+///
+/// [`ItemModelNode::restore`] で残したIDの表記は除きます。
+pub(super) fn has_unknown_fields(extra: &Map<String, Value>) -> bool {
+    extra.keys().any(|key| key != "type" && key != "property")
+}
+
+/// `item/<名前>` または `item/<名前>/...` から `<名前>` を取り出す
+fn custom_model_name(reference: &str) -> Option<&str> {
+    let path = reference
+        .trim_start_matches("minecraft:")
+        .strip_prefix("item/")?;
+    path.split('/').next().filter(|name| !name.is_empty())
+}
+
+impl SelectWhen {
+    /// 一致させる文字列の一覧
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            Self::Single(value) => vec![value],
+            Self::Multiple(values) => values.iter().map(String::as_str).collect(),
+            Self::Other(_) => Vec::new(),
+        }
+    }
+
+    /// 指定した文字列に一致するか確認
+    pub fn contains(&self, value: &str) -> bool {
+        self.values().contains(&value)
+    }
+}
+
+impl PartialEq<&str> for SelectWhen {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Self::Single(value) if value == other)
+    }
+}
+
+impl ItemCase {
+    /// 新しいカスタムモデルケースを作成
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use processor::schema::items::ItemCase;
+    ///
+    /// let case = ItemCase::new("my_custom_model");
+    /// assert_eq!(case.when, "my_custom_model");
+    /// ```
+    pub fn new(custom_model_data: &str) -> Self {
+        Self::with_model(custom_model_data, ItemModelNode::item(custom_model_data))
+    }
+
+    /// 任意のモデルを使うカスタムモデルケースを作成
+    pub fn with_model(custom_model_data: &str, model: ItemModelNode) -> Self {
+        Self {
+            when: SelectWhen::Single(custom_model_data.to_string()),
+            model,
+            extra: Map::new(),
+        }
+    }
+}
+
+impl ItemRangeEntry {
+    /// 新しい数値エントリーを作成
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use processor::schema::items::ItemRangeEntry;
+    ///
+    /// let entry = ItemRangeEntry::new(1001.0, "my_custom_model");
+    /// assert!(entry.model.is_item("my_custom_model"));
+    /// ```
+    pub fn new(threshold: f64, custom_model_data: &str) -> Self {
+        Self::with_model(threshold, ItemModelNode::item(custom_model_data))
+    }
+
+    /// 任意のモデルを使う数値エントリーを作成
+    pub fn with_model(threshold: f64, model: ItemModelNode) -> Self {
+        Self {
+            threshold: Float::new(threshold),
+            model,
+            extra: Map::new(),
+        }
+    }
+}
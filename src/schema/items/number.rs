@@ -0,0 +1,80 @@
+//! 表記を保持する数値
+//!
+//! しきい値などを `f64` で読み込んで書き戻すと、整数で書かれた `1` が `1.0` になり、
+//! 手書きのアイテム定義に差分が出てしまいます。読み込んだときの表記
+//! （整数か小数か）を保持し、同じ表記で書き出します。
+
+use serde::{Deserialize, Serialize, de::Error as _};
+
+/// 整数として表せる値の上限（`f64` で正確に表せる範囲）
+const MAX_EXACT: f64 = 9_007_199_254_740_992.0;
+
+/// 表記を保持する数値
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Float {
+    value: f64,
+    /// 整数として書かれていたか
+    integer: bool,
+}
+
+impl Float {
+    /// 小数として書き出す数値を作成
+    #[must_use]
+    pub const fn new(value: f64) -> Self {
+        Self {
+            value,
+            integer: false,
+        }
+    }
+
+    /// 数値
+    #[must_use]
+    pub const fn get(self) -> f64 {
+        self.value
+    }
+}
+
+impl From<f64> for Float {
+    fn from(value: f64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Serialize for Float {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.integer && self.value.fract() == 0.0 && self.value.abs() < MAX_EXACT {
+            // 範囲内の整数値なので i64 に収まる
+            #[allow(clippy::cast_possible_truncation)]
+            serializer.serialize_i64(self.value as i64)
+        } else {
+            serializer.serialize_f64(self.value)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Float {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let number = serde_json::Number::deserialize(deserializer)?;
+        let value = number
+            .as_f64()
+            .ok_or_else(|| D::Error::custom(format!("数値として読み込めません: {number}")))?;
+        Ok(Self {
+            value,
+            integer: !number.is_f64(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_representation_is_preserved() {
+        for json in ["1", "1.0", "0.65", "-3", "1001"] {
+            let number: Float = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_string(&number).unwrap(), json);
+        }
+        assert_eq!(serde_json::to_string(&Float::new(10.0)).unwrap(), "10.0");
+    }
+}
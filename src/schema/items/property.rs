@@ -0,0 +1,260 @@
+//! アイテムモデルのプロパティ定義
+//!
+//! `condition` / `select` / `range_dispatch` が参照するプロパティを定義します。
+//! プロパティ固有のフィールドは、親のモデル定義と同じJSONオブジェクトに並びます。
+//! 同じオブジェクトに並ぶ未知のフィールドと名前空間なしの `property` は、親のモデル定義の
+//! `extra` に残ります（[`ItemModelNode`](super::ItemModelNode) の読み込み時に復元します）。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{extensible::extensible_enum, number::Float};
+
+/// `minecraft:condition` の真偽値プロパティ
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "property")]
+pub enum ConditionProperty {
+    /// 耐久値が残りわずか
+    #[serde(rename = "minecraft:broken", alias = "broken")]
+    Broken,
+    /// バンドルでアイテムを選択中
+    #[serde(
+        rename = "minecraft:bundle/has_selected_item",
+        alias = "bundle/has_selected_item"
+    )]
+    BundleHasSelectedItem,
+    /// GUI上でカーソルに持っている
+    #[serde(rename = "minecraft:carried", alias = "carried")]
+    Carried,
+    /// コンポーネントが条件に一致する
+    #[serde(rename = "minecraft:component", alias = "component")]
+    Component { predicate: String, value: Value },
+    /// 耐久値が減っている
+    #[serde(rename = "minecraft:damaged", alias = "damaged")]
+    Damaged,
+    /// Shiftキーで詳細表示中
+    #[serde(rename = "minecraft:extended_view", alias = "extended_view")]
+    ExtendedView,
+    /// 釣り竿を投げている
+    #[serde(rename = "minecraft:fishing_rod/cast", alias = "fishing_rod/cast")]
+    FishingRodCast,
+    /// コンポーネントを持っている
+    #[serde(rename = "minecraft:has_component", alias = "has_component")]
+    HasComponent {
+        component: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ignore_default: Option<bool>,
+    },
+    /// キーが押されている
+    #[serde(rename = "minecraft:keybind_down", alias = "keybind_down")]
+    KeybindDown { keybind: String },
+    /// ホットバーで選択中
+    #[serde(rename = "minecraft:selected", alias = "selected")]
+    Selected,
+    /// 使用中
+    #[serde(rename = "minecraft:using_item", alias = "using_item")]
+    UsingItem,
+    /// カメラのエンティティが持ち主
+    #[serde(rename = "minecraft:view_entity", alias = "view_entity")]
+    ViewEntity,
+    /// `custom_model_data` の `flags` の値
+    #[serde(rename = "minecraft:custom_model_data", alias = "custom_model_data")]
+    CustomModelData {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index: Option<u32>,
+    },
+    /// 未知のプロパティ（元のJSONを保持）
+    #[serde(skip)]
+    Unknown(Map<String, Value>),
+}
+
+extensible_enum!(
+    ConditionProperty,
+    "property",
+    [
+        "broken",
+        "bundle/has_selected_item",
+        "carried",
+        "component",
+        "damaged",
+        "extended_view",
+        "fishing_rod/cast",
+        "has_component",
+        "keybind_down",
+        "selected",
+        "using_item",
+        "view_entity",
+        "custom_model_data",
+    ]
+);
+
+/// `minecraft:select` の選択プロパティ
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "property")]
+pub enum SelectProperty {
+    /// ブロックステートの値
+    #[serde(rename = "minecraft:block_state", alias = "block_state")]
+    BlockState { block_state_property: String },
+    /// クロスボウの装填物
+    #[serde(rename = "minecraft:charge_type", alias = "charge_type")]
+    ChargeType,
+    /// コンポーネントの値
+    #[serde(rename = "minecraft:component", alias = "component")]
+    Component { component: String },
+    /// 現在のディメンション
+    #[serde(rename = "minecraft:context_dimension", alias = "context_dimension")]
+    ContextDimension,
+    /// 持ち主のエンティティの種類
+    #[serde(
+        rename = "minecraft:context_entity_type",
+        alias = "context_entity_type"
+    )]
+    ContextEntityType,
+    /// 表示コンテキスト
+    #[serde(rename = "minecraft:display_context", alias = "display_context")]
+    DisplayContext,
+    /// 現在時刻
+    #[serde(rename = "minecraft:local_time", alias = "local_time")]
+    LocalTime {
+        pattern: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        locale: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        time_zone: Option<String>,
+    },
+    /// 利き手
+    #[serde(rename = "minecraft:main_hand", alias = "main_hand")]
+    MainHand,
+    /// 装飾の素材
+    #[serde(rename = "minecraft:trim_material", alias = "trim_material")]
+    TrimMaterial,
+    /// `custom_model_data` の `strings` の値
+    #[serde(rename = "minecraft:custom_model_data", alias = "custom_model_data")]
+    CustomModelData {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index: Option<u32>,
+    },
+    /// 未知のプロパティ（元のJSONを保持）
+    #[serde(skip)]
+    Unknown(Map<String, Value>),
+}
+
+extensible_enum!(
+    SelectProperty,
+    "property",
+    [
+        "block_state",
+        "charge_type",
+        "component",
+        "context_dimension",
+        "context_entity_type",
+        "display_context",
+        "local_time",
+        "main_hand",
+        "trim_material",
+        "custom_model_data",
+    ]
+);
+
+/// `minecraft:range_dispatch` の数値プロパティ
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "property")]
+pub enum NumericProperty {
+    /// バンドルの充填率
+    #[serde(rename = "minecraft:bundle/fullness", alias = "bundle/fullness")]
+    BundleFullness,
+    /// コンパスの角度
+    #[serde(rename = "minecraft:compass", alias = "compass")]
+    Compass {
+        target: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wobble: Option<bool>,
+    },
+    /// クールダウンの残り割合
+    #[serde(rename = "minecraft:cooldown", alias = "cooldown")]
+    Cooldown,
+    /// スタック数
+    #[serde(rename = "minecraft:count", alias = "count")]
+    Count {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        normalize: Option<bool>,
+    },
+    /// クロスボウの引き具合
+    #[serde(rename = "minecraft:crossbow/pull", alias = "crossbow/pull")]
+    CrossbowPull,
+    /// 耐久値の減少量
+    #[serde(rename = "minecraft:damage", alias = "damage")]
+    Damage {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        normalize: Option<bool>,
+    },
+    /// 時刻
+    #[serde(rename = "minecraft:time", alias = "time")]
+    Time {
+        source: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wobble: Option<bool>,
+    },
+    /// 使用時間の周期
+    #[serde(rename = "minecraft:use_cycle", alias = "use_cycle")]
+    UseCycle {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        period: Option<Float>,
+    },
+    /// 使用時間
+    #[serde(rename = "minecraft:use_duration", alias = "use_duration")]
+    UseDuration {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        remaining: Option<bool>,
+    },
+    /// `custom_model_data` の `floats` の値
+    #[serde(rename = "minecraft:custom_model_data", alias = "custom_model_data")]
+    CustomModelData {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index: Option<u32>,
+    },
+    /// 未知のプロパティ（元のJSONを保持）
+    #[serde(skip)]
+    Unknown(Map<String, Value>),
+}
+
+extensible_enum!(
+    NumericProperty,
+    "property",
+    [
+        "bundle/fullness",
+        "compass",
+        "cooldown",
+        "count",
+        "crossbow/pull",
+        "damage",
+        "time",
+        "use_cycle",
+        "use_duration",
+        "custom_model_data",
+    ]
+);
+
+impl SelectProperty {
+    /// `custom_model_data` の先頭の値（`index` が 0）を参照しているか確認
+    pub const fn is_custom_model_data(&self) -> bool {
+        matches!(
+            self,
+            Self::CustomModelData {
+                index: None | Some(0)
+            }
+        )
+    }
+}
+
+impl NumericProperty {
+    /// `custom_model_data` の先頭の値（`index` が 0）を参照しているか確認
+    pub const fn is_custom_model_data(&self) -> bool {
+        matches!(
+            self,
+            Self::CustomModelData {
+                index: None | Some(0)
+            }
+        )
+    }
+}
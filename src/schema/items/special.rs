@@ -0,0 +1,174 @@
+//! 特殊モデル定義
+//!
+//! `minecraft:special` で使用する、ゲーム内で描画されるモデルの種類を定義します。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{
+    extensible::{extensible_enum, keep_short_id},
+    number::Float,
+};
+
+/// 特殊モデルの種類
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
+pub enum SpecialModelType {
+    /// 旗
+    #[serde(rename = "minecraft:banner", alias = "banner")]
+    Banner {
+        color: String,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// ベッド
+    #[serde(rename = "minecraft:bed", alias = "bed")]
+    Bed {
+        texture: String,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// チェスト
+    #[serde(rename = "minecraft:chest", alias = "chest")]
+    Chest {
+        texture: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        openness: Option<Float>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// コンジット
+    #[serde(rename = "minecraft:conduit", alias = "conduit")]
+    Conduit {
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 銅のゴーレムの像
+    #[serde(
+        rename = "minecraft:copper_golem_statue",
+        alias = "copper_golem_statue"
+    )]
+    CopperGolemStatue {
+        texture: String,
+        pose: String,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 飾り壺
+    #[serde(rename = "minecraft:decorated_pot", alias = "decorated_pot")]
+    DecoratedPot {
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 吊り看板
+    #[serde(rename = "minecraft:hanging_sign", alias = "hanging_sign")]
+    HangingSign {
+        wood_type: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        texture: Option<String>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 頭
+    #[serde(rename = "minecraft:head", alias = "head")]
+    Head {
+        kind: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        texture: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        animation: Option<Float>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// プレイヤーの頭
+    #[serde(rename = "minecraft:player_head", alias = "player_head")]
+    PlayerHead {
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 盾
+    #[serde(rename = "minecraft:shield", alias = "shield")]
+    Shield {
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// シュルカーボックス
+    #[serde(rename = "minecraft:shulker_box", alias = "shulker_box")]
+    ShulkerBox {
+        texture: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        openness: Option<Float>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        orientation: Option<String>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 看板
+    #[serde(rename = "minecraft:standing_sign", alias = "standing_sign")]
+    StandingSign {
+        wood_type: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        texture: Option<String>,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// トライデント
+    #[serde(rename = "minecraft:trident", alias = "trident")]
+    Trident {
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 未知の特殊モデル（元のJSONを保持）
+    #[serde(skip)]
+    Unknown(Map<String, Value>),
+}
+
+extensible_enum!(
+    SpecialModelType,
+    "type",
+    [
+        "banner",
+        "bed",
+        "chest",
+        "conduit",
+        "copper_golem_statue",
+        "decorated_pot",
+        "hanging_sign",
+        "head",
+        "player_head",
+        "shield",
+        "shulker_box",
+        "standing_sign",
+        "trident",
+    ],
+    restore = SpecialModelType::restore,
+);
+
+impl SpecialModelType {
+    /// 未知のフィールド（未知の特殊モデルの場合は `None`）
+    fn extra_mut(&mut self) -> Option<&mut Map<String, Value>> {
+        match self {
+            Self::Banner { extra, .. }
+            | Self::Bed { extra, .. }
+            | Self::Chest { extra, .. }
+            | Self::Conduit { extra, .. }
+            | Self::CopperGolemStatue { extra, .. }
+            | Self::DecoratedPot { extra, .. }
+            | Self::HangingSign { extra, .. }
+            | Self::Head { extra, .. }
+            | Self::PlayerHead { extra, .. }
+            | Self::Shield { extra, .. }
+            | Self::ShulkerBox { extra, .. }
+            | Self::StandingSign { extra, .. }
+            | Self::Trident { extra, .. } => Some(extra),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// 読み込んだJSONの種類の表記を復元する
+    fn restore(&mut self, original: &Map<String, Value>) {
+        if let Some(extra) = self.extra_mut() {
+            keep_short_id(extra, "type", original);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! ティントソース定義
+//!
+//! `minecraft:model` のモデルに適用する色（ティント）の取得元を定義します。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{
+    extensible::{extensible_enum, keep_short_id},
+    number::Float,
+};
+
+/// 色の値
+///
+/// RGBを詰めた整数、または0.0〜1.0の `[r, g, b]` で指定します。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Color {
+    /// RGBを詰めた整数（例: `16711680` = 赤）
+    Packed(i64),
+    /// 0.0〜1.0の `[r, g, b]`
+    Rgb([Float; 3]),
+}
+
+/// ティントソース
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
+pub enum TintSource {
+    /// 固定色
+    #[serde(rename = "minecraft:constant", alias = "constant")]
+    Constant {
+        value: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 染料の色（革の防具など）
+    #[serde(rename = "minecraft:dye", alias = "dye")]
+    Dye {
+        default: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 花火の星の色
+    #[serde(rename = "minecraft:firework", alias = "firework")]
+    Firework {
+        default: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 草の色
+    #[serde(rename = "minecraft:grass", alias = "grass")]
+    Grass {
+        temperature: Float,
+        downfall: Float,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 地図の色
+    #[serde(rename = "minecraft:map_color", alias = "map_color")]
+    MapColor {
+        default: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// ポーションの色
+    #[serde(rename = "minecraft:potion", alias = "potion")]
+    Potion {
+        default: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// チームの色
+    #[serde(rename = "minecraft:team", alias = "team")]
+    Team {
+        default: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// `custom_model_data` の `colors` の値
+    #[serde(rename = "minecraft:custom_model_data", alias = "custom_model_data")]
+    CustomModelData {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index: Option<u32>,
+        default: Color,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+    /// 未知のティントソース（元のJSONを保持）
+    #[serde(skip)]
+    Unknown(Map<String, Value>),
+}
+
+extensible_enum!(
+    TintSource,
+    "type",
+    [
+        "constant",
+        "dye",
+        "firework",
+        "grass",
+        "map_color",
+        "potion",
+        "team",
+        "custom_model_data",
+    ],
+    restore = TintSource::restore,
+);
+
+impl TintSource {
+    /// 未知のフィールド（未知のティントソースの場合は `None`）
+    fn extra_mut(&mut self) -> Option<&mut Map<String, Value>> {
+        match self {
+            Self::Constant { extra, .. }
+            | Self::Dye { extra, .. }
+            | Self::Firework { extra, .. }
+            | Self::Grass { extra, .. }
+            | Self::MapColor { extra, .. }
+            | Self::Potion { extra, .. }
+            | Self::Team { extra, .. }
+            | Self::CustomModelData { extra, .. } => Some(extra),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// 読み込んだJSONの種類の表記を復元する
+    fn restore(&mut self, original: &Map<String, Value>) {
+        if let Some(extra) = self.extra_mut() {
+            keep_short_id(extra, "type", original);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! 表示位置ごとの変形（`display`）
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::number;
+
+/// 表示位置ごとの変形
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Display {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thirdperson_righthand: Option<DisplayTransform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thirdperson_lefthand: Option<DisplayTransform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firstperson_righthand: Option<DisplayTransform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firstperson_lefthand: Option<DisplayTransform>,
+    /// インベントリなどのGUI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gui: Option<DisplayTransform>,
+    /// 頭に被ったとき
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<DisplayTransform>,
+    /// 地面に落ちているとき
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ground: Option<DisplayTransform>,
+    /// 額縁
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<DisplayTransform>,
+    /// その他の表示位置（`on_shelf` など）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// 変形（回転 → 平行移動 → 拡大の順に指定）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DisplayTransform {
+    /// 回転（度、X・Y・Z）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "number::option"
+    )]
+    pub rotation: Option<[f64; 3]>,
+    /// 平行移動（-80〜80）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "number::option"
+    )]
+    pub translation: Option<[f64; 3]>,
+    /// 拡大率（最大4）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "number::option"
+    )]
+    pub scale: Option<[f64; 3]>,
+    /// その他のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl DisplayTransform {
+    /// 回転・平行移動・拡大を指定して作成
+    #[must_use]
+    pub fn new(rotation: [f64; 3], translation: [f64; 3], scale: [f64; 3]) -> Self {
+        Self {
+            rotation: Some(rotation),
+            translation: Some(translation),
+            scale: Some(scale),
+            extra: Map::new(),
+        }
+    }
+
+    /// 回転（省略時は回転なし）
+    #[must_use]
+    pub fn rotation(&self) -> [f64; 3] {
+        self.rotation.unwrap_or_default()
+    }
+
+    /// 平行移動（省略時は移動なし）
+    #[must_use]
+    pub fn translation(&self) -> [f64; 3] {
+        self.translation.unwrap_or_default()
+    }
+
+    /// 拡大率（省略時は等倍）
+    #[must_use]
+    pub fn scale(&self) -> [f64; 3] {
+        self.scale.unwrap_or([1.0; 3])
+    }
+}
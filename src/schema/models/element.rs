@@ -0,0 +1,206 @@
+//! モデルの要素（`elements`）
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::number;
+
+/// モデルの要素（直方体）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Element {
+    /// 開始座標（0〜16の範囲外も -16〜32 まで可）
+    #[serde(serialize_with = "number::serialize")]
+    pub from: [f64; 3],
+    /// 終了座標
+    #[serde(serialize_with = "number::serialize")]
+    pub to: [f64; 3],
+    /// 要素の回転
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<ElementRotation>,
+    /// 方向による陰影をつけるか（省略時は `true`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shade: Option<bool>,
+    /// 発光レベル（0〜15）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub light_emission: Option<u8>,
+    /// 面の定義
+    #[serde(default)]
+    pub faces: Faces,
+    /// その他のフィールド（`name`、`color` など）
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// 要素の回転
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ElementRotation {
+    /// 回転角度（度）
+    #[serde(serialize_with = "number::scalar")]
+    pub angle: f64,
+    /// 回転軸
+    pub axis: Axis,
+    /// 回転の中心
+    #[serde(serialize_with = "number::serialize")]
+    pub origin: [f64; 3],
+    /// 回転に合わせて拡大するか
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rescale: Option<bool>,
+    /// その他のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// 回転軸
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// 面の方向
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+    Up,
+    Down,
+}
+
+/// 要素の各面
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Faces {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub north: Option<Face>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub east: Option<Face>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub south: Option<Face>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub west: Option<Face>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub up: Option<Face>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub down: Option<Face>,
+    /// その他のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// 面
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Face {
+    /// テクスチャの範囲（x1, y1, x2, y2）。省略時は要素の位置から求めます
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "number::option"
+    )]
+    pub uv: Option<[f64; 4]>,
+    /// テクスチャの回転（0, 90, 180, 270）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<u32>,
+    /// テクスチャ変数（`#0` など）
+    pub texture: String,
+    /// この方向にブロックがある場合に描画しない
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cullface: Option<Direction>,
+    /// ティントのインデックス
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tintindex: Option<i32>,
+    /// その他のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Element {
+    /// 方向による陰影をつけるか
+    #[must_use]
+    pub fn shade(&self) -> bool {
+        self.shade.unwrap_or(true)
+    }
+}
+
+impl Axis {
+    /// 座標配列のインデックス
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::Y => 1,
+            Self::Z => 2,
+        }
+    }
+}
+
+impl Direction {
+    /// すべての方向
+    pub const ALL: [Self; 6] = [
+        Self::North,
+        Self::East,
+        Self::South,
+        Self::West,
+        Self::Up,
+        Self::Down,
+    ];
+
+    /// JSONでの名前
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::North => "north",
+            Self::East => "east",
+            Self::South => "south",
+            Self::West => "west",
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
+}
+
+impl Faces {
+    /// 指定した方向の面
+    #[must_use]
+    pub const fn get(&self, direction: Direction) -> Option<&Face> {
+        match direction {
+            Direction::North => self.north.as_ref(),
+            Direction::East => self.east.as_ref(),
+            Direction::South => self.south.as_ref(),
+            Direction::West => self.west.as_ref(),
+            Direction::Up => self.up.as_ref(),
+            Direction::Down => self.down.as_ref(),
+        }
+    }
+
+    /// 指定した方向の面を設定
+    pub fn set(&mut self, direction: Direction, face: Face) {
+        let slot = match direction {
+            Direction::North => &mut self.north,
+            Direction::East => &mut self.east,
+            Direction::South => &mut self.south,
+            Direction::West => &mut self.west,
+            Direction::Up => &mut self.up,
+            Direction::Down => &mut self.down,
+        };
+        *slot = Some(face);
+    }
+
+    /// 定義されている面を方向とともに列挙
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &Face)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|direction| Some((direction, self.get(direction)?)))
+    }
+}
+
+impl Face {
+    /// テクスチャの回転（度）
+    #[must_use]
+    pub fn rotation(&self) -> u32 {
+        self.rotation.unwrap_or(0)
+    }
+}
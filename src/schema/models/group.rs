@@ -0,0 +1,55 @@
+//! Blockbench の要素グループ（`groups`）
+//!
+//! Minecraft は使用しませんが、Blockbench で再編集できるように保持します。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::number;
+
+/// グループの子（要素のインデックスまたは入れ子のグループ）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum GroupChild {
+    /// `elements` のインデックス
+    Element(usize),
+    /// 入れ子のグループ
+    Group(Group),
+}
+
+/// 要素グループ
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Group {
+    /// グループ名
+    pub name: String,
+    /// 回転の中心
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "number::option"
+    )]
+    pub origin: Option<[f64; 3]>,
+    /// Blockbench での表示色
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    /// 子
+    #[serde(default)]
+    pub children: Vec<GroupChild>,
+    /// その他のフィールド
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl GroupChild {
+    /// このグループ以下で参照されている要素のインデックス
+    pub fn element_indices(&self) -> Vec<usize> {
+        match self {
+            Self::Element(index) => vec![*index],
+            Self::Group(group) => group
+                .children
+                .iter()
+                .flat_map(Self::element_indices)
+                .collect(),
+        }
+    }
+}
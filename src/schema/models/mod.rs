@@ -0,0 +1,279 @@
+//! アイテムモデル定義
+//!
+//! Minecraftのアイテムモデルファイル（`assets/minecraft/models/item/*.json`）の
+//! スキーマを定義します。2Dモデルの `parent` / `textures` に加えて、
+//! Blockbench で作成した3Dモデルの要素・表示位置・グループを型付きで扱い、
+//! 未知のフィールド（`format_version`、`credit` など）は元のJSONのまま保持します。
+
+mod display;
+mod element;
+mod group;
+mod number;
+mod validate;
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::ItemModelParent;
+
+pub use display::{Display, DisplayTransform};
+pub use element::{Axis, Direction, Element, ElementRotation, Face, Faces};
+pub use group::{Group, GroupChild};
+
+/// アイテムモデル
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ItemModel {
+    /// その他のフィールド（Blockbench の `format_version`、`credit` など）
+    ///
+    /// Blockbench はこれらを先頭に書き出すため、最初に並べます。
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    /// 親モデル（`minecraft:item/handheld` など）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// 環境光による陰影をつけるか
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ambientocclusion: Option<bool>,
+    /// GUIでのライティング
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gui_light: Option<GuiLight>,
+    /// Blockbench のテクスチャサイズ（UVは常に0〜16で指定されるため描画には影響しません）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture_size: Option<[u32; 2]>,
+    /// テクスチャマッピング
+    #[serde(default)]
+    pub textures: Textures,
+    /// 要素（3Dモデル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elements: Option<Vec<Element>>,
+    /// 表示位置ごとの変形
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<Display>,
+    /// Blockbench の要素グループ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<GroupChild>>,
+}
+
+/// GUIでのライティング
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GuiLight {
+    /// 正面から照らす（アイテム向け）
+    Front,
+    /// 横から照らす（ブロック向け）
+    Side,
+}
+
+/// テクスチャマッピング
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Textures(BTreeMap<String, String>);
+
+impl ItemModel {
+    /// 新しい2Dアイテムモデルを作成
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use processor::schema::models::ItemModel;
+    /// use processor::types::ItemModelParent;
+    ///
+    /// let model = ItemModel::new(ItemModelParent::Handheld, "my_sword");
+    /// ```
+    pub fn new(parent: ItemModelParent, custom_model_data: &str) -> Self {
+        let mut textures = Textures::default();
+        textures.add_custom(custom_model_data);
+        Self {
+            parent: Some(parent.as_json_str().to_string()),
+            textures,
+            ..Self::default()
+        }
+    }
+
+    /// 要素の一覧（2Dモデルでは空）
+    #[must_use]
+    pub fn elements(&self) -> &[Element] {
+        self.elements.as_deref().unwrap_or_default()
+    }
+
+    /// GUIでの変形
+    #[must_use]
+    pub fn gui_transform(&self) -> Option<&DisplayTransform> {
+        self.display.as_ref()?.gui.as_ref()
+    }
+}
+
+impl Textures {
+    /// テクスチャ変数の値を取得（`#` の有無は問わない）
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key.trim_start_matches('#')).map(String::as_str)
+    }
+
+    /// テクスチャ変数と値を列挙
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// `#変数` の参照をたどってテクスチャのパスを求める
+    ///
+    /// 未定義の変数や循環参照の場合は `None` です。
+    #[must_use]
+    pub fn resolve(&self, key: &str) -> Option<&str> {
+        let mut key = key;
+        // 循環参照で止まらないように、変数の数までたどる
+        for _ in 0..=self.0.len() {
+            let value = self.get(key)?;
+            match value.strip_prefix('#') {
+                Some(next) => key = next,
+                None => return Some(value),
+            }
+        }
+        None
+    }
+
+    /// テクスチャ変数を設定
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.0
+            .insert(key.trim_start_matches('#').to_string(), value.to_string());
+    }
+
+    /// カスタムテクスチャを追加（layer0）
+    pub fn add_custom(&mut self, custom_model_data: &str) {
+        self.0
+            .insert("layer0".to_string(), format!("item/{custom_model_data}"));
+    }
+
+    /// テクスチャ変数を3Dモデルのレイヤー（`item/<名前>/<レイヤー>`）に設定
+    ///
+    /// 複数のテクスチャ変数が同じレイヤーの画像を共有できます。
+    pub fn set_layer(&mut self, key: &str, custom_model_data: &str, layer: &str) {
+        self.0.insert(
+            key.trim_start_matches('#').to_string(),
+            format!("item/{custom_model_data}/{layer}"),
+        );
+    }
+
+    /// 数字のみのテクスチャ変数（Blockbench の既定の名前）を数値の昇順で列挙
+    pub fn numeric_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .0
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !key.is_empty() && key.chars().all(|c| c.is_ascii_digit()))
+            .collect();
+        keys.sort_by_key(|key| (key.len(), *key));
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_model_new() {
+        let model = ItemModel::new(ItemModelParent::Handheld, "test_model");
+
+        assert!(model.parent.is_some());
+        assert_eq!(
+            model.parent.as_deref(),
+            Some(ItemModelParent::Handheld.as_json_str())
+        );
+        assert_eq!(
+            serde_json::to_value(&model).unwrap(),
+            serde_json::json!({
+                "parent": "minecraft:item/handheld",
+                "textures": {"layer0": "item/test_model"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_blockbench_model_round_trip() {
+        let json = serde_json::json!({
+            "format_version": "1.21.6",
+            "credit": "Made with Blockbench",
+            "texture_size": [64, 64],
+            "textures": {"0": "item/barrel/0", "particle": "#0"},
+            "elements": [{
+                "name": "body",
+                "from": [0, 0, 0],
+                "to": [16, 8.5, 16],
+                "rotation": {"angle": 22.5, "axis": "y", "origin": [8, 8, 8], "rescale": true},
+                "faces": {
+                    "north": {"uv": [0, 0, 4, 4], "rotation": 90, "texture": "#0", "cullface": "north"},
+                    "up": {"uv": [4, 12, 0, 8], "texture": "#0", "tintindex": 0}
+                }
+            }, {
+                "from": [4, 8.5, 4],
+                "to": [12, 10, 12],
+                "rotation": {"angle": -45, "axis": "x", "origin": [8, 8, 8]},
+                "faces": {}
+            }],
+            "gui_light": "front",
+            "display": {
+                "gui": {"rotation": [30, 225, 0], "scale": [0.625, 0.625, 0.625]},
+                "on_shelf": {"translation": [0, 1, 0]}
+            },
+            "groups": [{"name": "root", "origin": [8, 8, 8], "color": 0, "children": [0, {"name": "empty", "children": []}]}]
+        });
+
+        let model: ItemModel = serde_json::from_value(json.clone()).unwrap();
+        let element = &model.elements()[0];
+        assert_eq!(element.to, [16.0, 8.5, 16.0]);
+        assert_eq!(element.rotation.as_ref().unwrap().axis, Axis::Y);
+        assert_eq!(element.faces.north.as_ref().unwrap().rotation(), 90);
+        assert_eq!(element.faces.iter().count(), 2);
+        assert_eq!(model.elements()[1].rotation.as_ref().unwrap().angle, -45.0);
+        assert_eq!(
+            model.gui_transform().unwrap().rotation(),
+            [30.0, 225.0, 0.0]
+        );
+        assert_eq!(model.gui_light, Some(GuiLight::Front));
+        assert_eq!(model.groups.as_ref().unwrap()[0].element_indices(), vec![0]);
+        assert_eq!(model.textures.resolve("#particle"), Some("item/barrel/0"));
+
+        assert_eq!(serde_json::to_value(&model).unwrap(), json);
+    }
+
+    #[test]
+    fn test_textures_resolve_cycle() {
+        let mut textures = Textures::default();
+        textures.0.insert("a".to_string(), "#b".to_string());
+        textures.0.insert("b".to_string(), "#a".to_string());
+        assert_eq!(textures.resolve("a"), None);
+        assert_eq!(textures.resolve("missing"), None);
+    }
+
+    #[test]
+    fn test_textures_add_custom() {
+        let mut textures = Textures::default();
+        textures.add_custom("my_texture");
+
+        assert_eq!(textures.0.get("layer0").unwrap(), "item/my_texture");
+    }
+
+    #[test]
+    fn test_textures_set_layer() {
+        let mut textures = Textures::default();
+        textures.set_layer("blade", "my_model", "blade");
+        textures.set_layer("#particle", "my_model", "blade");
+
+        assert_eq!(textures.0.get("blade").unwrap(), "item/my_model/blade");
+        assert_eq!(textures.0.get("particle").unwrap(), "item/my_model/blade");
+    }
+
+    #[test]
+    fn test_textures_numeric_keys() {
+        let mut textures = Textures::default();
+        for key in ["10", "2", "0", "particle", "blade"] {
+            textures.0.insert(key.to_string(), format!("old/{key}"));
+        }
+
+        assert_eq!(textures.numeric_keys(), vec!["0", "2", "10"]);
+    }
+}
@@ -0,0 +1,54 @@
+//! 座標や角度などの数値のシリアライズ
+//!
+//! `f64` で読み込んだ整数値（`16` など）を `16.0` と書き出すと、
+//! 読み込んだモデルを書き戻したときに差分が出てしまいます。
+//! 整数値は整数として書き出します。
+
+use serde::Serializer;
+use serde::ser::SerializeTuple;
+
+/// 整数として表せる値の上限（`f64` で正確に表せる範囲）
+const MAX_EXACT: f64 = 9_007_199_254_740_992.0;
+
+/// 整数値は整数として数値を書き出す
+pub fn scalar<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.fract() == 0.0 && value.abs() < MAX_EXACT {
+        // 範囲内の整数値なので i64 に収まる
+        #[allow(clippy::cast_possible_truncation)]
+        serializer.serialize_i64(*value as i64)
+    } else {
+        serializer.serialize_f64(*value)
+    }
+}
+
+/// 整数値は整数として数値配列を書き出す
+pub fn serialize<S: Serializer, const N: usize>(
+    values: &[f64; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    /// 配列の要素を [`scalar`] で書き出す
+    struct Scalar(f64);
+
+    impl serde::Serialize for Scalar {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            scalar(&self.0, serializer)
+        }
+    }
+
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for value in values {
+        tuple.serialize_element(&Scalar(*value))?;
+    }
+    tuple.end()
+}
+
+/// `Option` の数値配列を書き出す（`skip_serializing_if` と併用）
+pub fn option<S: Serializer, const N: usize>(
+    values: &Option<[f64; N]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match values {
+        Some(values) => serialize(values, serializer),
+        None => serializer.serialize_none(),
+    }
+}
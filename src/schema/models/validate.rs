@@ -0,0 +1,221 @@
+//! 3Dモデルの検証
+//!
+//! Minecraft が読み込めない、または意図しない表示になるモデルを
+//! 追加前に検出します。
+
+use super::{Display, DisplayTransform, Element, ItemModel};
+
+/// 要素の座標の範囲
+const COORDINATE_RANGE: std::ops::RangeInclusive<f64> = -16.0..=32.0;
+/// UVの範囲
+const UV_RANGE: std::ops::RangeInclusive<f64> = 0.0..=16.0;
+/// 要素の回転に使用できる角度
+const ELEMENT_ANGLES: [f64; 5] = [-45.0, -22.5, 0.0, 22.5, 45.0];
+/// 面のテクスチャの回転に使用できる角度
+const FACE_ROTATIONS: [u32; 4] = [0, 90, 180, 270];
+/// `display` の平行移動の範囲
+const TRANSLATION_RANGE: std::ops::RangeInclusive<f64> = -80.0..=80.0;
+/// `display` の拡大率の範囲
+const SCALE_RANGE: std::ops::RangeInclusive<f64> = 0.0..=4.0;
+
+impl ItemModel {
+    /// 要素・テクスチャ参照・`display` が妥当か検証
+    ///
+    /// # Errors
+    ///
+    /// 問題が1つでもある場合（すべての問題を列挙します）
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let problems = self.problems();
+        if !problems.is_empty() {
+            anyhow::bail!(
+                "モデルに {} 件の問題があります:\n{}",
+                problems.len(),
+                problems
+                    .iter()
+                    .map(|problem| format!("  - {problem}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+        Ok(())
+    }
+
+    /// 問題の一覧（`elements[0].faces.north: ...` の形式）
+    #[must_use]
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (index, element) in self.elements().iter().enumerate() {
+            self.check_element(&format!("elements[{index}]"), element, &mut problems);
+        }
+        if let Some(display) = &self.display {
+            check_display(display, &mut problems);
+        }
+        problems
+    }
+
+    fn check_element(&self, path: &str, element: &Element, problems: &mut Vec<String>) {
+        for (name, coordinates) in [("from", element.from), ("to", element.to)] {
+            if !coordinates.iter().all(|c| COORDINATE_RANGE.contains(c)) {
+                problems.push(format!(
+                    "{path}.{name}: 座標 {coordinates:?} が -16〜32 の範囲外です"
+                ));
+            }
+        }
+
+        if let Some(rotation) = &element.rotation
+            && !ELEMENT_ANGLES.contains(&rotation.angle)
+        {
+            problems.push(format!(
+                "{path}.rotation: 角度 {} は使用できません（-45, -22.5, 0, 22.5, 45 のいずれか）",
+                rotation.angle
+            ));
+        }
+
+        if !element.faces.extra.is_empty() {
+            let keys: Vec<_> = element.faces.extra.keys().map(String::as_str).collect();
+            problems.push(format!(
+                "{path}.faces: 不明な面 {} があります",
+                keys.join(", ")
+            ));
+        }
+
+        for (direction, face) in element.faces.iter() {
+            let path = format!("{path}.faces.{}", direction.as_str());
+            if self.textures.get(&face.texture).is_none() {
+                problems.push(format!(
+                    "{path}: テクスチャ '{}' が textures に定義されていません",
+                    face.texture
+                ));
+            }
+            if let Some(uv) = face.uv
+                && !uv.iter().all(|value| UV_RANGE.contains(value))
+            {
+                problems.push(format!("{path}.uv: UV {uv:?} が 0〜16 の範囲外です"));
+            }
+            if !FACE_ROTATIONS.contains(&face.rotation()) {
+                problems.push(format!(
+                    "{path}.rotation: 回転 {} は使用できません（0, 90, 180, 270 のいずれか）",
+                    face.rotation()
+                ));
+            }
+        }
+    }
+}
+
+fn check_display(display: &Display, problems: &mut Vec<String>) {
+    let known = [
+        ("thirdperson_righthand", &display.thirdperson_righthand),
+        ("thirdperson_lefthand", &display.thirdperson_lefthand),
+        ("firstperson_righthand", &display.firstperson_righthand),
+        ("firstperson_lefthand", &display.firstperson_lefthand),
+        ("gui", &display.gui),
+        ("head", &display.head),
+        ("ground", &display.ground),
+        ("fixed", &display.fixed),
+    ];
+    for (name, transform) in known {
+        if let Some(transform) = transform {
+            check_transform(&format!("display.{name}"), transform, problems);
+        }
+    }
+
+    // 新しいバージョンの表示位置（`on_shelf` など）も形式だけは検証する
+    for (name, value) in &display.extra {
+        let path = format!("display.{name}");
+        match serde_json::from_value::<DisplayTransform>(value.clone()) {
+            Ok(transform) => check_transform(&path, &transform, problems),
+            Err(err) => problems.push(format!("{path}: 変形として解釈できません（{err}）")),
+        }
+    }
+}
+
+fn check_transform(path: &str, transform: &DisplayTransform, problems: &mut Vec<String>) {
+    if let Some(translation) = transform.translation
+        && !translation.iter().all(|t| TRANSLATION_RANGE.contains(t))
+    {
+        problems.push(format!(
+            "{path}.translation: {translation:?} が -80〜80 の範囲外です"
+        ));
+    }
+    if let Some(scale) = transform.scale
+        && !scale.iter().all(|s| SCALE_RANGE.contains(s))
+    {
+        problems.push(format!("{path}.scale: {scale:?} が 0〜4 の範囲外です"));
+    }
+    if !transform.extra.is_empty() {
+        let keys: Vec<_> = transform.extra.keys().map(String::as_str).collect();
+        problems.push(format!(
+            "{path}: 不明なフィールド {} があります",
+            keys.join(", ")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(element: serde_json::Value, display: serde_json::Value) -> ItemModel {
+        serde_json::from_value(serde_json::json!({
+            "textures": {"0": "item/test/0"},
+            "elements": [element],
+            "display": display
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_model() {
+        let model = model(
+            serde_json::json!({
+                "from": [-16, 0, 0],
+                "to": [32, 16, 16],
+                "rotation": {"angle": -22.5, "axis": "x", "origin": [8, 8, 8]},
+                "faces": {"north": {"uv": [0, 0, 16, 16], "rotation": 270, "texture": "#0"}}
+            }),
+            serde_json::json!({
+                "gui": {"rotation": [30, 225, 0], "translation": [0, 0, 0], "scale": [0.625, 0.625, 0.625]},
+                "on_shelf": {"scale": [1, 1, 1]}
+            }),
+        );
+        assert!(model.validate().is_ok(), "{:?}", model.problems());
+    }
+
+    #[test]
+    fn test_problems_reference_element_and_face() {
+        let model = model(
+            serde_json::json!({
+                "from": [0, -17, 0],
+                "to": [16, 16, 16],
+                "rotation": {"angle": 30, "axis": "y", "origin": [8, 8, 8]},
+                "faces": {
+                    "north": {"uv": [0, 0, 17, 16], "texture": "#1"},
+                    "up": {"rotation": 45, "texture": "#0"},
+                    "top": {"texture": "#0"}
+                }
+            }),
+            serde_json::json!({
+                "gui": {"translation": [0, 100, 0], "scale": [5, 1, 1]},
+                "on_shelf": {"rotation": "invalid"}
+            }),
+        );
+
+        let problems = model.problems();
+        let expected = [
+            "elements[0].from:",
+            "elements[0].rotation:",
+            "elements[0].faces: 不明な面 top",
+            "elements[0].faces.north: テクスチャ '#1'",
+            "elements[0].faces.north.uv:",
+            "elements[0].faces.up.rotation:",
+            "display.gui.translation:",
+            "display.gui.scale:",
+            "display.on_shelf:",
+        ];
+        assert_eq!(problems.len(), expected.len(), "{problems:#?}");
+        for (problem, prefix) in problems.iter().zip(expected) {
+            assert!(problem.starts_with(prefix), "{problem}");
+        }
+        assert!(model.validate().is_err());
+    }
+}
@@ -3,6 +3,7 @@ pub enum IssueType {
     Model,
     Model3d,
     Extend,
+    Unextend,
 }
 
 impl IssueType {
@@ -11,6 +12,7 @@ impl IssueType {
             IssueType::Model => "model",
             IssueType::Model3d => "model3d",
             IssueType::Extend => "extend",
+            IssueType::Unextend => "unextend",
         }
     }
 }
@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+/// `--layer` で指定するテクスチャ変数ごとの画像
+///
+/// `<テクスチャ変数>=<画像ファイルのパス>` の形式で指定します。
+/// 画像の代わりに別のテクスチャ変数を指定すると、その画像を共有します
+/// （例: `particle=blade`）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerTexture {
+    /// テクスチャ変数（`#` を除いたもの）
+    pub key: String,
+    /// 画像ファイルのパス、または別のテクスチャ変数
+    pub source: String,
+}
+
+impl FromStr for LayerTexture {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, source) = s
+            .split_once('=')
+            .map(|(key, source)| (key.trim().trim_start_matches('#'), source.trim()))
+            .filter(|(key, source)| !key.is_empty() && !source.is_empty())
+            .ok_or_else(|| {
+                format!(
+                    "'{}' は <テクスチャ変数>=<画像ファイル> の形式ではありません（例: blade=blade.png）",
+                    s
+                )
+            })?;
+
+        Ok(Self {
+            key: key.to_string(),
+            source: source.trim_start_matches('#').to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layer_texture() {
+        let layer: LayerTexture = "blade=textures/blade.png".parse().unwrap();
+        assert_eq!(layer.key, "blade");
+        assert_eq!(layer.source, "textures/blade.png");
+
+        let layer: LayerTexture = "#particle=#0".parse().unwrap();
+        assert_eq!(layer.key, "particle");
+        assert_eq!(layer.source, "0");
+
+        assert!("blade".parse::<LayerTexture>().is_err());
+        assert!("blade=".parse::<LayerTexture>().is_err());
+    }
+}
@@ -1,7 +1,13 @@
 mod github;
 mod issue;
 mod item_model;
+mod layer;
+mod stateful;
+mod tint;
 
 pub use github::GithubReaction;
 pub use issue::IssueType;
 pub use item_model::ItemModelParent;
+pub use layer::LayerTexture;
+pub use stateful::{StateTexture, StatefulKind};
+pub use tint::{Tint, TintKind};
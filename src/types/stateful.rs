@@ -0,0 +1,285 @@
+use std::{path::PathBuf, str::FromStr};
+
+use serde_json::Map;
+
+use crate::{
+    paths::Paths,
+    schema::items::{
+        ConditionModel, ConditionProperty, Float, ItemCase, ItemModelNode, ItemRangeEntry,
+        NumericProperty, RangeDispatchModel, SelectModel, SelectProperty, SelectWhen, SpecialModel,
+        SpecialModelType,
+    },
+};
+
+/// `add stateful` で作成する状態付きモデルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatefulKind {
+    Bow,
+    Crossbow,
+    FishingRod,
+    Shield,
+}
+
+impl StatefulKind {
+    const ALL: [Self; 4] = [Self::Bow, Self::Crossbow, Self::FishingRod, Self::Shield];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            StatefulKind::Bow => "bow",
+            StatefulKind::Crossbow => "crossbow",
+            StatefulKind::FishingRod => "fishing_rod",
+            StatefulKind::Shield => "shield",
+        }
+    }
+
+    /// 必要な状態の一覧
+    pub const fn states(&self) -> &'static [&'static str] {
+        match self {
+            StatefulKind::Bow => &["pulling_0", "pulling_1", "pulling_2"],
+            StatefulKind::Crossbow => &["pulling_0", "pulling_1", "pulling_2", "arrow", "firework"],
+            StatefulKind::FishingRod => &["cast"],
+            StatefulKind::Shield => &["blocking"],
+        }
+    }
+
+    /// カスタムモデルケースに入れるモデルの木構造を作成
+    ///
+    /// バニラのアイテム定義と同じ条件で、状態ごとのモデル
+    /// （`item/<名前>/<状態>`）に切り替えます。状態以外の場合は
+    /// ベースのモデル（`item/<名前>`）を使用します。
+    pub fn to_model(self, custom_model_data: &str) -> ItemModelNode {
+        self.build_tree(ItemModelNode::item(custom_model_data), |state| {
+            ItemModelNode::item(&Paths::state_name(custom_model_data, state))
+        })
+    }
+
+    /// バニラのアイテム定義のモデル
+    ///
+    /// アイテム定義ファイルがまだないマテリアルに追加する場合のフォールバックです。
+    /// 状態ごとのモデルは `minecraft:item/<マテリアル>_<状態>` を使用します。
+    pub fn vanilla_model(self) -> ItemModelNode {
+        let name = self.as_str();
+        let reference = |path: String| match self {
+            // 盾は模様を描画するため特殊モデルを使用する
+            StatefulKind::Shield => ItemModelNode::Special(SpecialModel {
+                model: SpecialModelType::Shield { extra: Map::new() },
+                base: path,
+                extra: Map::new(),
+            }),
+            _ => ItemModelNode::model(&path),
+        };
+
+        self.build_tree(reference(format!("minecraft:item/{name}")), |state| {
+            reference(format!("minecraft:item/{name}_{state}"))
+        })
+    }
+
+    /// 状態ごとの条件分岐の木構造を作成
+    fn build_tree(
+        self,
+        base: ItemModelNode,
+        state: impl Fn(&str) -> ItemModelNode,
+    ) -> ItemModelNode {
+        match self {
+            StatefulKind::Bow => condition(
+                ConditionProperty::UsingItem,
+                range_dispatch(
+                    NumericProperty::UseDuration { remaining: None },
+                    Some(0.05),
+                    vec![
+                        ItemRangeEntry::with_model(0.65, state("pulling_1")),
+                        ItemRangeEntry::with_model(0.9, state("pulling_2")),
+                    ],
+                    state("pulling_0"),
+                ),
+                base,
+            ),
+            StatefulKind::Crossbow => ItemModelNode::Select(SelectModel {
+                property: SelectProperty::ChargeType,
+                cases: vec![
+                    ItemCase {
+                        when: SelectWhen::Single("arrow".to_string()),
+                        model: state("arrow"),
+                        extra: Map::new(),
+                    },
+                    ItemCase {
+                        when: SelectWhen::Single("rocket".to_string()),
+                        model: state("firework"),
+                        extra: Map::new(),
+                    },
+                ],
+                fallback: Some(Box::new(condition(
+                    ConditionProperty::UsingItem,
+                    range_dispatch(
+                        NumericProperty::CrossbowPull,
+                        None,
+                        vec![
+                            ItemRangeEntry::with_model(0.58, state("pulling_1")),
+                            ItemRangeEntry::with_model(1.0, state("pulling_2")),
+                        ],
+                        state("pulling_0"),
+                    ),
+                    base,
+                ))),
+                extra: Map::new(),
+            }),
+            StatefulKind::FishingRod => {
+                condition(ConditionProperty::FishingRodCast, state("cast"), base)
+            }
+            StatefulKind::Shield => {
+                condition(ConditionProperty::UsingItem, state("blocking"), base)
+            }
+        }
+    }
+}
+
+fn condition(
+    property: ConditionProperty,
+    on_true: ItemModelNode,
+    on_false: ItemModelNode,
+) -> ItemModelNode {
+    ItemModelNode::Condition(ConditionModel {
+        property,
+        on_true: Box::new(on_true),
+        on_false: Box::new(on_false),
+        extra: Map::new(),
+    })
+}
+
+fn range_dispatch(
+    property: NumericProperty,
+    scale: Option<f64>,
+    entries: Vec<ItemRangeEntry>,
+    fallback: ItemModelNode,
+) -> ItemModelNode {
+    ItemModelNode::RangeDispatch(RangeDispatchModel {
+        property,
+        scale: scale.map(Float::new),
+        entries,
+        fallback: Some(Box::new(fallback)),
+        extra: Map::new(),
+    })
+}
+
+impl FromStr for StatefulKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("'{}' is not a valid StatefulKind", s))
+    }
+}
+
+impl clap::ValueEnum for StatefulKind {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.as_str()))
+    }
+
+    fn from_str(input: &str, ignore_case: bool) -> Result<Self, String> {
+        let input = if ignore_case {
+            input.to_lowercase()
+        } else {
+            input.to_string()
+        };
+        <Self as FromStr>::from_str(&input)
+    }
+}
+
+/// `--state` で指定する状態ごとのテクスチャ
+///
+/// `<状態>=<画像ファイルのパス>` の形式で指定します。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateTexture {
+    pub state: String,
+    pub path: PathBuf,
+}
+
+impl FromStr for StateTexture {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (state, path) = s
+            .split_once('=')
+            .map(|(state, path)| (state.trim(), path.trim()))
+            .filter(|(state, path)| !state.is_empty() && !path.is_empty())
+            .ok_or_else(|| {
+                format!(
+                    "'{}' は <状態>=<画像ファイル> の形式ではありません（例: pulling_0=bow_0.png）",
+                    s
+                )
+            })?;
+
+        Ok(Self {
+            state: state.to_string(),
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_state_texture() {
+        let texture: StateTexture = "pulling_0=images/bow_0.png".parse().unwrap();
+        assert_eq!(texture.state, "pulling_0");
+        assert_eq!(texture.path, PathBuf::from("images/bow_0.png"));
+        assert!("pulling_0".parse::<StateTexture>().is_err());
+        assert!("=bow.png".parse::<StateTexture>().is_err());
+    }
+
+    #[test]
+    fn test_bow_model() {
+        let model = StatefulKind::Bow.to_model("my_bow");
+        let json = serde_json::to_value(&model).unwrap();
+        assert_eq!(json["type"], "minecraft:condition");
+        assert_eq!(json["property"], "minecraft:using_item");
+        assert_eq!(json["on_false"]["model"], "item/my_bow");
+        assert_eq!(json["on_true"]["property"], "minecraft:use_duration");
+        assert_eq!(
+            json["on_true"]["fallback"]["model"],
+            "item/my_bow/pulling_0"
+        );
+        assert_eq!(
+            json["on_true"]["entries"][1]["model"]["model"],
+            "item/my_bow/pulling_2"
+        );
+        assert!(model.refers_to_item("my_bow"));
+        assert!(!model.refers_to_item("my"));
+    }
+
+    #[test]
+    fn test_vanilla_model() {
+        let json = serde_json::to_value(StatefulKind::FishingRod.vanilla_model()).unwrap();
+        assert_eq!(json["property"], "minecraft:fishing_rod/cast");
+        assert_eq!(json["on_true"]["model"], "minecraft:item/fishing_rod_cast");
+        assert_eq!(json["on_false"]["model"], "minecraft:item/fishing_rod");
+
+        // 盾は特殊モデル
+        let json = serde_json::to_value(StatefulKind::Shield.vanilla_model()).unwrap();
+        assert_eq!(json["on_true"]["type"], "minecraft:special");
+        assert_eq!(json["on_true"]["model"]["type"], "minecraft:shield");
+        assert_eq!(json["on_true"]["base"], "minecraft:item/shield_blocking");
+        assert_eq!(json["on_false"]["base"], "minecraft:item/shield");
+    }
+
+    #[test]
+    fn test_every_state_is_referenced() {
+        for kind in StatefulKind::ALL {
+            let model = kind.to_model("stateful");
+            let references = model.model_references();
+            assert!(references.contains(&"item/stateful"));
+            for state in kind.states() {
+                let path = format!("item/stateful/{state}");
+                assert!(references.contains(&path.as_str()), "{path}");
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt, str::FromStr};
+
+use serde_json::Map;
+
+use crate::schema::items::{Color, TintSource};
+
+/// 色を省略した場合の既定色（白）
+const DEFAULT_COLOR: i64 = 0xFF_FF_FF;
+
+/// `--tint` で指定するティントソースの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TintKind {
+    Constant,
+    Dye,
+    Firework,
+    MapColor,
+    Potion,
+    Team,
+    CustomModelData,
+}
+
+impl TintKind {
+    const ALL: [Self; 7] = [
+        Self::Constant,
+        Self::Dye,
+        Self::Firework,
+        Self::MapColor,
+        Self::Potion,
+        Self::Team,
+        Self::CustomModelData,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TintKind::Constant => "constant",
+            TintKind::Dye => "dye",
+            TintKind::Firework => "firework",
+            TintKind::MapColor => "map_color",
+            TintKind::Potion => "potion",
+            TintKind::Team => "team",
+            TintKind::CustomModelData => "custom_model_data",
+        }
+    }
+}
+
+/// `--tint` で指定するティント
+///
+/// `<種類>` または `<種類>=<色>` の形式で指定します。
+/// 色は `#RRGGBB` または10進数の整数です。`constant` 以外は色を省略でき、
+/// 省略時は白を既定色にします。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tint {
+    pub kind: TintKind,
+    pub color: Option<i64>,
+}
+
+impl Tint {
+    /// アイテム定義に書き込むティントソースに変換
+    pub fn to_source(self) -> TintSource {
+        let color = Color::Packed(self.color.unwrap_or(DEFAULT_COLOR));
+        match self.kind {
+            TintKind::Constant => TintSource::Constant {
+                value: color,
+                extra: Map::new(),
+            },
+            TintKind::Dye => TintSource::Dye {
+                default: color,
+                extra: Map::new(),
+            },
+            TintKind::Firework => TintSource::Firework {
+                default: color,
+                extra: Map::new(),
+            },
+            TintKind::MapColor => TintSource::MapColor {
+                default: color,
+                extra: Map::new(),
+            },
+            TintKind::Potion => TintSource::Potion {
+                default: color,
+                extra: Map::new(),
+            },
+            TintKind::Team => TintSource::Team {
+                default: color,
+                extra: Map::new(),
+            },
+            TintKind::CustomModelData => TintSource::CustomModelData {
+                index: None,
+                default: color,
+                extra: Map::new(),
+            },
+        }
+    }
+
+    /// カンマ区切りのティント一覧を解析
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(Self::from_str)
+            .collect()
+    }
+}
+
+impl FromStr for Tint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, color) = match s.split_once('=') {
+            Some((kind, color)) => (kind.trim(), Some(color.trim())),
+            None => (s.trim(), None),
+        };
+
+        let kind = TintKind::ALL
+            .into_iter()
+            .find(|k| k.as_str() == kind)
+            .ok_or_else(|| {
+                let names: Vec<_> = TintKind::ALL.iter().map(TintKind::as_str).collect();
+                format!(
+                    "'{}' は対応していないティントです（{}）",
+                    kind,
+                    names.join(", ")
+                )
+            })?;
+
+        let color = color.map(parse_color).transpose()?;
+        if kind == TintKind::Constant && color.is_none() {
+            return Err("constant には色を指定してください（例: constant=#FF0000）".to_string());
+        }
+
+        Ok(Self { kind, color })
+    }
+}
+
+impl fmt::Display for Tint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.color {
+            Some(color) => write!(f, "{}=#{:06X}", self.kind.as_str(), color),
+            None => f.write_str(self.kind.as_str()),
+        }
+    }
+}
+
+fn parse_color(s: &str) -> Result<i64, String> {
+    let parsed = match s.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 => i64::from_str_radix(hex, 16).ok(),
+        Some(_) => None,
+        None => s.parse::<i64>().ok(),
+    };
+    parsed
+        .filter(|color| (0..=0xFF_FF_FF).contains(color))
+        .ok_or_else(|| format!("'{}' は色として解釈できません（例: #A06540）", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tint() {
+        assert_eq!(
+            "dye".parse::<Tint>(),
+            Ok(Tint {
+                kind: TintKind::Dye,
+                color: None
+            })
+        );
+        assert_eq!(
+            "constant=#FF0000".parse::<Tint>().unwrap().to_source(),
+            TintSource::Constant {
+                value: Color::Packed(0xFF0000),
+                extra: Map::new(),
+            }
+        );
+        assert_eq!("potion=255".parse::<Tint>().unwrap().color, Some(255));
+        assert!("constant".parse::<Tint>().is_err());
+        assert!("rainbow".parse::<Tint>().is_err());
+        assert!("dye=#GGGGGG".parse::<Tint>().is_err());
+    }
+
+    #[test]
+    fn test_parse_tint_list() {
+        let tints = Tint::parse_list("dye=#A06540, team").unwrap();
+        assert_eq!(tints.len(), 2);
+        assert_eq!(tints[0].to_string(), "dye=#A06540");
+        assert_eq!(tints[1].to_string(), "team");
+        assert!(Tint::parse_list("dye, unknown").is_err());
+    }
+}
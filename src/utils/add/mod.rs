@@ -0,0 +1,293 @@
+mod transaction;
+
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::{
+    paths::Paths,
+    schema::{
+        animation::AnimationInfo,
+        items::{ItemCase, ItemModelNode, ItemRangeEntry, ItemResource},
+        models::ItemModel,
+    },
+    types::{ItemModelParent, StatefulKind},
+    utils::{items::list_item_files, json::read_json, materials::MaterialMapping},
+    validation::should_snake_case,
+};
+
+pub use transaction::Transaction;
+
+pub fn validate_materials(materials: &[String]) -> anyhow::Result<()> {
+    if materials.is_empty() {
+        anyhow::bail!("少なくとも1つのmaterialを指定してください。");
+    }
+    materials.iter().try_for_each(|m| should_snake_case(m))?;
+
+    let mapping = MaterialMapping::load()?;
+    let unknown: Vec<_> = materials
+        .iter()
+        .filter(|m| !mapping.contains(m))
+        .cloned()
+        .collect();
+    if !unknown.is_empty() {
+        anyhow::bail!("未対応のmaterialが指定されました: {}", unknown.join(", "));
+    }
+    Ok(())
+}
+
+pub fn infer_or_validate_name(
+    name: &Option<String>,
+    path_to_image: &Path,
+) -> anyhow::Result<String> {
+    let name = match name {
+        Some(n) => n.to_lowercase(),
+        None => path_to_image
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .context("画像ファイル名の取得に失敗")?
+            .to_string(),
+    };
+    should_snake_case(&name)?;
+    Ok(name)
+}
+
+pub fn ensure_not_exists_2d(custom_model_data: &str) -> anyhow::Result<()> {
+    let model_path = Paths::model_path(custom_model_data);
+    let texture_path = Paths::texture_path(custom_model_data);
+
+    if model_path.exists() {
+        anyhow::bail!(
+            "モデルファイルが既に存在します: {}",
+            model_path.to_string_lossy()
+        );
+    }
+    if texture_path.exists() {
+        anyhow::bail!(
+            "テクスチャファイルが既に存在します: {}",
+            texture_path.to_string_lossy()
+        );
+    }
+    Ok(())
+}
+
+pub fn write_new_item_model(
+    tx: &mut Transaction,
+    parent: ItemModelParent,
+    custom_model_data: &str,
+) -> anyhow::Result<()> {
+    let model_path = Paths::model_path(custom_model_data);
+    let item_model = ItemModel::new(parent, custom_model_data);
+    tx.write_json(&model_path, &item_model)
+        .with_context(|| format!("モデルファイルの書き込みに失敗: {}", model_path.display()))
+}
+
+/// 数値IDを検証
+///
+/// ゲームは数値IDを32ビット浮動小数点数として扱うため、`f32` で表せる有限の値のみ受け付けます。
+///
+/// # Errors
+///
+/// NaN・無限大、または `f32` の範囲外の値の場合
+#[allow(clippy::cast_possible_truncation)]
+pub fn validate_index(index: Option<f64>) -> anyhow::Result<()> {
+    if let Some(index) = index
+        && !(index as f32).is_finite()
+    {
+        anyhow::bail!("数値IDには有限の数値を指定してください: {}", index);
+    }
+    Ok(())
+}
+
+/// 数値IDのエントリーをアイテム定義に追加
+///
+/// 同じ数値IDが同じカスタムモデルデータで登録済みの場合は何もせず `false` を返します。
+/// 数値IDの重複は [`same_threshold`](crate::schema::items::same_threshold) で判定するため、
+/// ゲーム内で区別できない値（`1` と `1.000_000_01` など）も重複として扱います。
+///
+/// # Errors
+///
+/// 同じ数値IDが別のモデルで登録済みの場合
+pub fn add_index_entry(
+    resource: &mut ItemResource,
+    material: &str,
+    custom_model_data: &str,
+    index: f64,
+    model: &ItemModelNode,
+) -> anyhow::Result<bool> {
+    if let Some(existing) = resource.find_entry(index) {
+        if existing.model.refers_to_item(custom_model_data) {
+            return Ok(false);
+        }
+        anyhow::bail!(
+            "マテリアル '{}' の数値ID {} は既に '{}' に使用されています",
+            material,
+            index,
+            existing.model.model_path().unwrap_or("（モデル参照以外）")
+        );
+    }
+    resource.add_entry(ItemRangeEntry::with_model(index, model.clone()));
+    Ok(true)
+}
+
+/// 既存のアイテム定義から、カスタムモデルデータのケースに使われているモデルを探す
+///
+/// ティントや状態ごとのモデルを含むケースを別のマテリアルへ適用する際に、
+/// 同じモデルの木構造を再利用するために使用します。
+pub fn find_case_model(custom_model_data: &str) -> anyhow::Result<Option<ItemModelNode>> {
+    for item_file in list_item_files()? {
+        let resource = read_json::<ItemResource>(&item_file.path).with_context(|| {
+            format!(
+                "マテリアルファイルの読み込みに失敗: {}",
+                item_file.path.display()
+            )
+        })?;
+        if let Some(case) = resource
+            .cases()
+            .iter()
+            .find(|case| case.when.contains(custom_model_data))
+        {
+            return Ok(Some(case.model.clone()));
+        }
+    }
+    Ok(None)
+}
+
+pub fn update_materials(
+    tx: &mut Transaction,
+    materials: &[String],
+    custom_model_data: &str,
+    model: &ItemModelNode,
+    index: Option<f64>,
+) -> anyhow::Result<()> {
+    let case = ItemCase::with_model(custom_model_data, model.clone());
+    for material in materials {
+        let material_path = Paths::item_path(material);
+        let mut resource = if material_path.exists() {
+            read_json::<ItemResource>(&material_path).with_context(|| {
+                format!(
+                    "マテリアルファイルの読み込みに失敗: {}",
+                    material_path.display()
+                )
+            })?
+        } else if let Ok(kind) = material.parse::<StatefulKind>() {
+            // 弓などは状態によってモデルが変わるため、バニラの定義をそのまま使う
+            ItemResource::with_fallback_node(kind.vanilla_model())
+        } else {
+            let mapping = MaterialMapping::load()?;
+            let fallback = mapping.resolve_fallback_model_path(material)?;
+            ItemResource::new_with_fallback(&fallback)
+        };
+        resource.add_case(case.clone());
+        if let Some(index) = index {
+            add_index_entry(&mut resource, material, custom_model_data, index, model)?;
+        }
+        tx.write_json(&material_path, &resource).with_context(|| {
+            format!(
+                "マテリアルファイルの書き込みに失敗: {}",
+                material_path.display()
+            )
+        })?;
+        println!("  ✓ マテリアル '{}' に追加予定", material);
+    }
+    Ok(())
+}
+
+pub fn write_new_animation(
+    tx: &mut Transaction,
+    custom_model_data: &str,
+    animation_info: &AnimationInfo,
+) -> anyhow::Result<()> {
+    let animation_path = Paths::animation_path(custom_model_data);
+    tx.write_json(&animation_path, &animation_info)
+        .with_context(|| {
+            format!(
+                "アニメーションファイルの書き込みに失敗: {}",
+                animation_path.display()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(custom_model_data: &str) -> ItemModelNode {
+        ItemCase::new(custom_model_data).model
+    }
+
+    #[test]
+    fn test_validate_index() {
+        assert!(validate_index(None).is_ok());
+        assert!(validate_index(Some(1.5)).is_ok());
+        assert!(validate_index(Some(f64::NAN)).is_err());
+        assert!(validate_index(Some(f64::INFINITY)).is_err());
+        // f32 に収まらない値
+        assert!(validate_index(Some(1e39)).is_err());
+    }
+
+    #[test]
+    fn test_add_index_entry_duplicate() {
+        let mut resource = ItemResource::new_with_fallback("minecraft:item/diamond_sword");
+        let ruby = model("ruby_sword");
+
+        assert!(add_index_entry(&mut resource, "diamond_sword", "ruby_sword", 1.0, &ruby).unwrap());
+        // 同じモデルでの再登録は何もしない
+        assert!(
+            !add_index_entry(&mut resource, "diamond_sword", "ruby_sword", 1.0, &ruby).unwrap()
+        );
+        assert_eq!(resource.entries().len(), 1);
+
+        // 別のモデルでの重複はエラー（f32 で区別できない値も重複とみなす）
+        let other = model("other_sword");
+        for index in [1.0, 1.000_000_01] {
+            let err = add_index_entry(&mut resource, "diamond_sword", "other_sword", index, &other)
+                .unwrap_err();
+            assert!(err.to_string().contains("ruby_sword"), "{err}");
+        }
+
+        assert!(
+            add_index_entry(&mut resource, "diamond_sword", "other_sword", 2.0, &other).unwrap()
+        );
+        assert_eq!(resource.entries().len(), 2);
+    }
+
+    #[test]
+    fn test_update_materials_stateful_fallback() {
+        let _root = crate::paths::TestRoot::new();
+        let mut tx = Transaction::new();
+        let materials = ["bow".to_string(), "crossbow".to_string()];
+        update_materials(&mut tx, &materials, "ruby_bow", &model("ruby_bow"), None).unwrap();
+        tx.commit().unwrap();
+
+        // 新しく作成したアイテム定義のフォールバックはバニラと同じ条件分岐になる
+        let bow: serde_json::Value = read_json(&Paths::item_path("bow")).unwrap();
+        let fallback = &bow["model"]["fallback"];
+        assert_eq!(fallback["type"], "minecraft:condition");
+        assert_eq!(fallback["property"], "minecraft:using_item");
+        assert_eq!(fallback["on_false"]["model"], "minecraft:item/bow");
+        assert_eq!(fallback["on_true"]["type"], "minecraft:range_dispatch");
+        assert_eq!(fallback["on_true"]["scale"], 0.05);
+        assert_eq!(
+            fallback["on_true"]["fallback"]["model"],
+            "minecraft:item/bow_pulling_0"
+        );
+        assert_eq!(
+            fallback["on_true"]["entries"][1]["model"]["model"],
+            "minecraft:item/bow_pulling_2"
+        );
+        assert_eq!(bow["model"]["cases"][0]["when"], "ruby_bow");
+
+        let crossbow: serde_json::Value = read_json(&Paths::item_path("crossbow")).unwrap();
+        let fallback = &crossbow["model"]["fallback"];
+        assert_eq!(fallback["property"], "minecraft:charge_type");
+        assert_eq!(
+            fallback["cases"][1]["model"]["model"],
+            "minecraft:item/crossbow_firework"
+        );
+        assert_eq!(
+            fallback["fallback"]["on_false"]["model"],
+            "minecraft:item/crossbow"
+        );
+    }
+}
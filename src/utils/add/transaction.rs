@@ -0,0 +1,564 @@
+//! ファイル書き込みのトランザクション
+//!
+//! モデルの追加・削除・名前変更時のファイル書き込みや削除、移動をいったんメモリ上に集め、
+//! まとめて反映します。途中で失敗した場合は、反映前の状態に戻します。
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// ステージされたファイル書き込み・削除・移動の集合
+///
+/// 同じパスに複数回ステージした場合は、最後の操作が反映されます。
+/// 移動は書き込み・削除をすべて反映した後に、ステージした順で行います。
+#[derive(Debug, Default)]
+pub struct Transaction {
+    /// 対象のパス → 操作
+    staged: BTreeMap<PathBuf, Staged>,
+    /// 移動元 → 移動先（ステージした順）
+    moves: Vec<(PathBuf, PathBuf)>,
+}
+
+/// ステージされた操作
+#[derive(Debug)]
+enum Staged {
+    /// ファイルを書き込む
+    Write(Vec<u8>),
+    /// ファイルを削除する
+    RemoveFile,
+    /// ディレクトリを中身ごと削除する
+    RemoveDir,
+}
+
+/// 書き込み前の状態（ロールバック用）
+#[derive(Debug)]
+enum Undo {
+    /// 既存ファイルを元の内容に戻す
+    Restore { path: PathBuf, contents: Vec<u8> },
+    /// 新規作成したファイルを削除する
+    RemoveFile(PathBuf),
+    /// 新規作成したディレクトリを削除する
+    RemoveDir(PathBuf),
+    /// 削除したディレクトリを中身ごと元に戻す
+    RestoreDir {
+        path: PathBuf,
+        /// 配下のエントリ（親から順。`None` はディレクトリ）
+        entries: Vec<(PathBuf, Option<Vec<u8>>)>,
+    },
+    /// 移動したファイルまたはディレクトリを元の場所に戻す
+    Move { from: PathBuf, to: PathBuf },
+}
+
+impl Transaction {
+    /// 空のトランザクションを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ステージされた対象のパスの一覧（移動は移動元）
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.staged
+            .keys()
+            .chain(self.moves.iter().map(|(from, _)| from))
+            .map(PathBuf::as_path)
+    }
+
+    /// JSONの書き込みをステージ
+    ///
+    /// # Errors
+    ///
+    /// JSONのシリアライズに失敗した場合
+    pub fn write_json<T: serde::Serialize>(
+        &mut self,
+        path: &Path,
+        value: &T,
+    ) -> anyhow::Result<()> {
+        let json =
+            serde_json::to_string_pretty(value).context("JSONのシリアライズに失敗しました")?;
+        self.write(path, json.into_bytes());
+        Ok(())
+    }
+
+    /// バイト列の書き込みをステージ
+    pub fn write(&mut self, path: &Path, contents: Vec<u8>) {
+        self.staged
+            .insert(path.to_path_buf(), Staged::Write(contents));
+    }
+
+    /// ファイルの削除をステージ
+    pub fn remove_file(&mut self, path: &Path) {
+        self.staged.insert(path.to_path_buf(), Staged::RemoveFile);
+    }
+
+    /// ディレクトリの削除（中身ごと）をステージ
+    pub fn remove_dir(&mut self, path: &Path) {
+        self.staged.insert(path.to_path_buf(), Staged::RemoveDir);
+    }
+
+    /// ファイルまたはディレクトリの移動をステージ
+    ///
+    /// 書き込み・削除の反映後に移動するため、移動元への書き込みは移動先に引き継がれます。
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        self.moves.push((from.to_path_buf(), to.to_path_buf()));
+    }
+
+    /// ファイルのコピーをステージ
+    ///
+    /// コピー元はステージ時点で読み込まれます。
+    ///
+    /// # Errors
+    ///
+    /// コピー元の読み込みに失敗した場合
+    pub fn copy_file(&mut self, from: &Path, to: &Path) -> anyhow::Result<()> {
+        let contents = std::fs::read(from)
+            .with_context(|| format!("ファイルの読み込みに失敗: {}", from.display()))?;
+        self.write(to, contents);
+        Ok(())
+    }
+
+    /// ステージした書き込み・削除・移動をまとめて反映
+    ///
+    /// 反映前にすべての対象を検証し、途中で失敗した場合は
+    /// それまでに書き込んだファイル、作成したディレクトリ、
+    /// 削除したファイルとディレクトリ、移動したものを元に戻します。
+    ///
+    /// # Errors
+    ///
+    /// - 書き込み先が既存のディレクトリである場合
+    /// - 書き込み先の親パスにディレクトリでないものが存在する場合
+    /// - 削除対象が存在しない、または種類（ファイル・ディレクトリ）が異なる場合
+    /// - 削除するディレクトリの中に書き込もうとしている場合
+    /// - 移動元が存在しない、または移動先が既に存在する場合
+    /// - 反映に失敗した場合（ロールバック後にエラーを返します）
+    pub fn commit(self) -> anyhow::Result<()> {
+        self.validate()?;
+
+        let mut journal = Vec::new();
+        if let Err(err) = self.apply(&mut journal) {
+            eprintln!("⚠️  書き込みに失敗したため、変更を元に戻します...");
+            rollback(journal);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        for (from, to) in &self.moves {
+            if !from.exists() {
+                anyhow::bail!("移動元が見つかりません: {}", from.display());
+            }
+            if to.exists() || self.staged.contains_key(to) {
+                anyhow::bail!("移動先が既に存在します: {}", to.display());
+            }
+        }
+
+        for (path, staged) in &self.staged {
+            match staged {
+                Staged::Write(_) => {}
+                Staged::RemoveFile => {
+                    if !path.is_file() {
+                        anyhow::bail!("削除するファイルが見つかりません: {}", path.display());
+                    }
+                    continue;
+                }
+                Staged::RemoveDir => {
+                    if !path.is_dir() {
+                        anyhow::bail!("削除するディレクトリが見つかりません: {}", path.display());
+                    }
+                    continue;
+                }
+            }
+
+            if let Some(removed) = path
+                .ancestors()
+                .skip(1)
+                .find(|ancestor| matches!(self.staged.get(*ancestor), Some(Staged::RemoveDir)))
+            {
+                anyhow::bail!(
+                    "削除するディレクトリの中には書き込めません: {}",
+                    removed.display()
+                );
+            }
+            if path.is_dir() {
+                anyhow::bail!("書き込み先がディレクトリです: {}", path.display());
+            }
+            if let Some(blocker) = path
+                .ancestors()
+                .skip(1)
+                .find(|ancestor| ancestor.exists() && !ancestor.is_dir())
+            {
+                anyhow::bail!(
+                    "書き込み先の親パスがディレクトリではありません: {}",
+                    blocker.display()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(&self, journal: &mut Vec<Undo>) -> anyhow::Result<()> {
+        for (path, staged) in &self.staged {
+            let contents = match staged {
+                Staged::Write(contents) => contents,
+                Staged::RemoveFile => {
+                    let original = std::fs::read(path)
+                        .with_context(|| format!("ファイルの読み込みに失敗: {}", path.display()))?;
+                    std::fs::remove_file(path)
+                        .with_context(|| format!("ファイルの削除に失敗: {}", path.display()))?;
+                    journal.push(Undo::Restore {
+                        path: path.clone(),
+                        contents: original,
+                    });
+                    continue;
+                }
+                Staged::RemoveDir => {
+                    let mut entries = Vec::new();
+                    snapshot_dir(path, &mut entries)?;
+                    std::fs::remove_dir_all(path)
+                        .with_context(|| format!("ディレクトリの削除に失敗: {}", path.display()))?;
+                    journal.push(Undo::RestoreDir {
+                        path: path.clone(),
+                        entries,
+                    });
+                    continue;
+                }
+            };
+
+            if let Some(parent) = path.parent() {
+                create_dirs(parent, journal)?;
+            }
+
+            if path.exists() {
+                let original = std::fs::read(path)
+                    .with_context(|| format!("ファイルの読み込みに失敗: {}", path.display()))?;
+                journal.push(Undo::Restore {
+                    path: path.clone(),
+                    contents: original,
+                });
+            } else {
+                journal.push(Undo::RemoveFile(path.clone()));
+            }
+
+            std::fs::write(path, contents)
+                .with_context(|| format!("ファイルの書き込みに失敗: {}", path.display()))?;
+        }
+
+        for (from, to) in &self.moves {
+            if let Some(parent) = to.parent() {
+                create_dirs(parent, journal)?;
+            }
+            move_path(from, to)?;
+            journal.push(Undo::Move {
+                from: to.clone(),
+                to: from.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn move_path(from: &Path, to: &Path) -> anyhow::Result<()> {
+    std::fs::rename(from, to)
+        .with_context(|| format!("移動に失敗: {} -> {}", from.display(), to.display()))
+}
+
+/// 存在しないディレクトリを親から順に作成し、作成したものを記録する
+fn create_dirs(dir: &Path, journal: &mut Vec<Undo>) -> anyhow::Result<()> {
+    if dir.as_os_str().is_empty() || dir.is_dir() {
+        return Ok(());
+    }
+    if let Some(parent) = dir.parent() {
+        create_dirs(parent, journal)?;
+    }
+    std::fs::create_dir(dir)
+        .with_context(|| format!("ディレクトリの作成に失敗: {}", dir.display()))?;
+    journal.push(Undo::RemoveDir(dir.to_path_buf()));
+    Ok(())
+}
+
+/// ディレクトリ配下のエントリを親から順に読み込む
+fn snapshot_dir(dir: &Path, entries: &mut Vec<(PathBuf, Option<Vec<u8>>)>) -> anyhow::Result<()> {
+    let mut children = std::fs::read_dir(dir)
+        .with_context(|| format!("ディレクトリの読み込みに失敗: {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("ディレクトリエントリの取得に失敗: {}", dir.display()))?;
+    children.sort();
+    for child in children {
+        if child.is_dir() {
+            entries.push((child.clone(), None));
+            snapshot_dir(&child, entries)?;
+        } else {
+            let contents = std::fs::read(&child)
+                .with_context(|| format!("ファイルの読み込みに失敗: {}", child.display()))?;
+            entries.push((child, Some(contents)));
+        }
+    }
+    Ok(())
+}
+
+/// 記録した変更を逆順に元に戻す
+///
+/// 元に戻す処理自体の失敗は表示するのみで、可能な限り続行します。
+fn rollback(journal: Vec<Undo>) {
+    for undo in journal.into_iter().rev() {
+        let result = match &undo {
+            Undo::Restore { path, contents } => std::fs::write(path, contents)
+                .with_context(|| format!("ファイルの復元に失敗: {}", path.display())),
+            Undo::RemoveFile(path) => match std::fs::remove_file(path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                    Err(err).with_context(|| format!("ファイルの削除に失敗: {}", path.display()))
+                }
+                _ => Ok(()),
+            },
+            Undo::RemoveDir(path) => std::fs::remove_dir(path)
+                .with_context(|| format!("ディレクトリの削除に失敗: {}", path.display())),
+            Undo::RestoreDir { path, entries } => restore_dir(path, entries),
+            Undo::Move { from, to } => move_path(from, to),
+        };
+        if let Err(err) = result {
+            eprintln!("  ✗ {err:#}");
+        }
+    }
+}
+
+/// 削除したディレクトリを中身ごと作り直す
+fn restore_dir(dir: &Path, entries: &[(PathBuf, Option<Vec<u8>>)]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("ディレクトリの復元に失敗: {}", dir.display()))?;
+    for (path, contents) in entries {
+        match contents {
+            Some(contents) => std::fs::write(path, contents)
+                .with_context(|| format!("ファイルの復元に失敗: {}", path.display()))?,
+            None => std::fs::create_dir_all(path)
+                .with_context(|| format!("ディレクトリの復元に失敗: {}", path.display()))?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_writes_all_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("existing.json");
+        std::fs::write(&existing, "old").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.write(&existing, b"new".to_vec());
+        tx.write_json(
+            &dir.path().join("nested/dir/model.json"),
+            &serde_json::json!({"a": 1}),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "new");
+        assert!(dir.path().join("nested/dir/model.json").is_file());
+    }
+
+    #[test]
+    fn test_last_staged_write_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+
+        let mut tx = Transaction::new();
+        tx.write(&path, b"first".to_vec());
+        tx.write(&path, b"second".to_vec());
+        assert_eq!(tx.paths().count(), 1);
+        tx.commit().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+    }
+
+    #[test]
+    fn test_validation_failure_leaves_tree_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("a.json");
+        let blocker = dir.path().join("blocker");
+        std::fs::write(&existing, "old").unwrap();
+        std::fs::write(&blocker, "not a dir").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.write(&existing, b"new".to_vec());
+        tx.write(&blocker.join("x.json"), b"{}".to_vec());
+
+        assert!(tx.commit().is_err());
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "old");
+    }
+
+    #[test]
+    fn test_rollback_restores_previous_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("a.json");
+        std::fs::write(&existing, "old").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.write(&existing, b"new".to_vec());
+        tx.write(&dir.path().join("new_dir/b.json"), b"{}".to_vec());
+
+        let mut journal = Vec::new();
+        tx.apply(&mut journal).unwrap();
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "new");
+
+        rollback(journal);
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "old");
+        assert!(!dir.path().join("new_dir").exists());
+    }
+
+    #[test]
+    fn test_commit_removes_files_and_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model.json");
+        let tree = dir.path().join("layers");
+        std::fs::write(&file, "{}").unwrap();
+        std::fs::create_dir_all(tree.join("nested")).unwrap();
+        std::fs::write(tree.join("nested/0.png"), "png").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.remove_file(&file);
+        tx.remove_dir(&tree);
+        tx.commit().unwrap();
+
+        assert!(!file.exists());
+        assert!(!tree.exists());
+    }
+
+    #[test]
+    fn test_remove_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.json");
+        std::fs::write(&file, "old").unwrap();
+
+        // 存在しないファイルの削除
+        let mut tx = Transaction::new();
+        tx.remove_file(&file);
+        tx.remove_file(&dir.path().join("missing.json"));
+        assert!(tx.commit().is_err());
+        assert!(file.exists());
+
+        // ファイルをディレクトリとして削除
+        let mut tx = Transaction::new();
+        tx.remove_dir(&file);
+        assert!(tx.commit().is_err());
+
+        // 削除するディレクトリへの書き込み
+        let sub = dir.path().join("sub");
+        std::fs::create_dir(&sub).unwrap();
+        let mut tx = Transaction::new();
+        tx.remove_dir(&sub);
+        tx.write(&sub.join("x.json"), b"{}".to_vec());
+        assert!(tx.commit().is_err());
+        assert!(sub.is_dir());
+    }
+
+    #[test]
+    fn test_rollback_restores_removed_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.json");
+        let tree = dir.path().join("layers");
+        std::fs::write(&file, "old").unwrap();
+        std::fs::create_dir_all(tree.join("empty")).unwrap();
+        std::fs::create_dir_all(tree.join("nested")).unwrap();
+        std::fs::write(tree.join("nested/0.png"), "png").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.remove_file(&file);
+        tx.remove_dir(&tree);
+
+        let mut journal = Vec::new();
+        tx.apply(&mut journal).unwrap();
+        assert!(!file.exists());
+        assert!(!tree.exists());
+
+        rollback(journal);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "old");
+        assert_eq!(
+            std::fs::read_to_string(tree.join("nested/0.png")).unwrap(),
+            "png"
+        );
+        assert!(tree.join("empty").is_dir());
+    }
+
+    #[test]
+    fn test_rename_after_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("old.json");
+        let tree = dir.path().join("old");
+        std::fs::write(&file, "old").unwrap();
+        std::fs::create_dir_all(&tree).unwrap();
+        std::fs::write(tree.join("0.json"), "old").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.write(&file, b"new".to_vec());
+        tx.write(&tree.join("0.json"), b"new".to_vec());
+        tx.rename(&file, &dir.path().join("nested/new.json"));
+        tx.rename(&tree, &dir.path().join("new"));
+        tx.commit().unwrap();
+
+        assert!(!file.exists());
+        assert!(!tree.exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("nested/new.json")).unwrap(),
+            "new"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("new/0.json")).unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn test_rename_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.json");
+        let taken = dir.path().join("b.json");
+        std::fs::write(&file, "old").unwrap();
+        std::fs::write(&taken, "taken").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.write(&file, b"new".to_vec());
+        tx.rename(&file, &taken);
+        assert!(tx.commit().is_err());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "old");
+
+        let mut tx = Transaction::new();
+        tx.rename(&dir.path().join("missing.json"), &dir.path().join("c.json"));
+        assert!(tx.commit().is_err());
+    }
+
+    #[test]
+    fn test_rollback_restores_moved_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.json");
+        std::fs::write(&file, "old").unwrap();
+
+        let mut tx = Transaction::new();
+        tx.write(&file, b"new".to_vec());
+        tx.rename(&file, &dir.path().join("moved/a.json"));
+
+        let mut journal = Vec::new();
+        tx.apply(&mut journal).unwrap();
+        assert!(!file.exists());
+
+        rollback(journal);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "old");
+        assert!(!dir.path().join("moved").exists());
+    }
+
+    #[test]
+    fn test_copy_file_missing_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tx = Transaction::new();
+        assert!(
+            tx.copy_file(&dir.path().join("missing.png"), &dir.path().join("out.png"))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,371 @@
+//! 再現可能なZipアーカイブの作成
+//!
+//! 同じ入力から常にバイト単位で同一のZipファイルを生成するための
+//! アーカイブビルダーを提供します。エントリはパス順にソートされ、
+//! 更新日時とパーミッションは固定値に正規化されます。
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::Context;
+use zip::{CompressionMethod, DateTime, System, ZipWriter, write::SimpleFileOptions};
+
+/// アーカイブ内ファイルのパーミッション（rw-r--r--）
+const FILE_PERMISSIONS: u32 = 0o644;
+
+/// 再現可能なZipアーカイブのビルダー
+///
+/// 追加されたファイルはアーカイブ内のパスでソートされ、
+/// 更新日時は 1980-01-01 00:00:00 に固定されます。
+#[derive(Debug, Default)]
+pub struct ArchiveBuilder {
+    /// アーカイブのルートに対応するディレクトリ（空の場合はカレントディレクトリ）
+    root: PathBuf,
+    /// アーカイブ内のパス → 内容
+    entries: BTreeMap<String, Entry>,
+}
+
+/// エントリの内容
+#[derive(Debug)]
+enum Entry {
+    /// ディスク上のファイル
+    File(PathBuf),
+    /// 書き換えた内容
+    Data(Vec<u8>),
+}
+
+impl ArchiveBuilder {
+    /// 空のビルダーを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `root` をアーカイブのルートとするビルダーを作成
+    ///
+    /// 追加するパスは `root` 配下である必要があります。
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            ..Self::default()
+        }
+    }
+
+    /// ファイルまたはディレクトリを追加
+    ///
+    /// ディレクトリの場合は配下のファイルを再帰的に追加します。
+    /// アーカイブ内のパスはルートからの相対パスを `/` 区切りにしたものです。
+    ///
+    /// # Errors
+    ///
+    /// - パスが存在しない場合
+    /// - ディレクトリの読み込みに失敗した場合
+    /// - アーカイブ内のパスに変換できない場合
+    pub fn add_path(&mut self, path: &Path) -> anyhow::Result<()> {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)
+                .with_context(|| format!("ディレクトリの読み込みに失敗: {}", path.display()))?
+            {
+                let entry = entry.with_context(|| {
+                    format!("ディレクトリエントリの取得に失敗: {}", path.display())
+                })?;
+                self.add_path(&entry.path())?;
+            }
+            return Ok(());
+        }
+
+        if !path.is_file() {
+            anyhow::bail!("ファイルが見つかりません: {}", path.display());
+        }
+
+        let name = archive_name(&self.root, path)?;
+        self.entries.insert(name, Entry::File(path.to_path_buf()));
+        Ok(())
+    }
+
+    /// 内容を差し替えていないディスク上のファイル（アーカイブ内のパス順）
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.entries
+            .values()
+            .filter_map(|entry| match entry {
+                Entry::File(path) => Some(path.clone()),
+                Entry::Data(_) => None,
+            })
+            .collect()
+    }
+
+    /// 追加済みのファイルの内容を差し替える
+    ///
+    /// ディスク上のファイルは変更しません。追加されていないパスは無視します。
+    ///
+    /// # Errors
+    ///
+    /// アーカイブ内のパスに変換できない場合
+    pub fn replace_path(&mut self, path: &Path, data: Vec<u8>) -> anyhow::Result<()> {
+        if let Some(entry) = self.entries.get_mut(&archive_name(&self.root, path)?) {
+            *entry = Entry::Data(data);
+        }
+        Ok(())
+    }
+
+    /// 追加済みのファイルを取り除く
+    ///
+    /// # Errors
+    ///
+    /// アーカイブ内のパスに変換できない場合
+    pub fn remove_path(&mut self, path: &Path) -> anyhow::Result<()> {
+        self.entries.remove(&archive_name(&self.root, path)?);
+        Ok(())
+    }
+
+    /// 追加済みのエントリ数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// エントリが空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// アーカイブ内のパス一覧（ソート済み）
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Zipファイルを書き出す
+    ///
+    /// # Errors
+    ///
+    /// - 出力ファイルの作成に失敗した場合
+    /// - 入力ファイルの読み込みまたは圧縮に失敗した場合
+    pub fn write(&self, output: &Path) -> anyhow::Result<()> {
+        let file = File::create(output)
+            .with_context(|| format!("Zipファイルの作成に失敗: {}", output.display()))?;
+        let mut writer = ZipWriter::new(BufWriter::new(file));
+
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(DateTime::default())
+            .system(System::Unix)
+            .unix_permissions(FILE_PERMISSIONS);
+
+        for (name, entry) in &self.entries {
+            let file_data;
+            let data = match entry {
+                Entry::File(path) => {
+                    file_data = std::fs::read(path)
+                        .with_context(|| format!("ファイルの読み込みに失敗: {}", path.display()))?;
+                    &file_data
+                }
+                Entry::Data(data) => data,
+            };
+            writer
+                .start_file(name.as_str(), options)
+                .with_context(|| format!("Zipエントリの作成に失敗: {name}"))?;
+            writer
+                .write_all(data)
+                .with_context(|| format!("Zipエントリの書き込みに失敗: {name}"))?;
+        }
+
+        writer
+            .finish()
+            .context("Zipファイルの書き込みに失敗")?
+            .flush()
+            .context("Zipファイルのフラッシュに失敗")?;
+
+        Ok(())
+    }
+}
+
+/// ディスク上のパスをアーカイブ内のパス（`/` 区切り）に変換
+///
+/// `root` からの相対パスに変換します。絶対パスや `..` を含むなど、
+/// ルートの外を指すパスはエラーにします。
+fn archive_name(root: &Path, path: &Path) -> anyhow::Result<String> {
+    let relative = if root.as_os_str().is_empty() {
+        path
+    } else {
+        path.strip_prefix(root).with_context(|| {
+            format!(
+                "アーカイブのルート（{}）の外にあるパスは追加できません: {}",
+                root.display(),
+                path.display()
+            )
+        })?
+    };
+
+    let mut parts = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => {
+                parts.push(part.to_str().with_context(|| {
+                    format!("UTF-8でないパスは追加できません: {}", path.display())
+                })?)
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!(
+                    "アーカイブ内のパスは相対パスで、ルートの外を指さない必要があります: {}",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        anyhow::bail!("アーカイブ内のパスが空です: {}", path.display());
+    }
+
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_tree(root: &Path) {
+        std::fs::create_dir_all(root.join("assets/b")).unwrap();
+        std::fs::create_dir_all(root.join("assets/a")).unwrap();
+        std::fs::write(root.join("assets/b/two.json"), "{}").unwrap();
+        std::fs::write(root.join("assets/a/one.json"), "{\"a\":1}").unwrap();
+        std::fs::write(root.join("pack.mcmeta"), "{}").unwrap();
+    }
+
+    #[test]
+    fn test_archive_name() {
+        let cwd = Path::new("");
+        assert_eq!(
+            archive_name(cwd, Path::new("./assets/minecraft/x.json")).unwrap(),
+            "assets/minecraft/x.json"
+        );
+        assert!(archive_name(cwd, Path::new("./")).is_err());
+        assert!(archive_name(cwd, Path::new("/etc/passwd")).is_err());
+        assert!(archive_name(cwd, Path::new("../outside.json")).is_err());
+        assert!(archive_name(cwd, Path::new("assets/../../outside.json")).is_err());
+
+        let root = Path::new("/pack");
+        assert_eq!(
+            archive_name(root, Path::new("/pack/assets/x.json")).unwrap(),
+            "assets/x.json"
+        );
+        assert!(archive_name(root, Path::new("/other/x.json")).is_err());
+        assert!(archive_name(root, Path::new("/pack/../x.json")).is_err());
+    }
+
+    #[test]
+    fn test_entries_are_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        write_tree(dir.path());
+
+        let mut builder = ArchiveBuilder::with_root(dir.path());
+        builder.add_path(&dir.path().join("pack.mcmeta")).unwrap();
+        builder.add_path(&dir.path().join("assets")).unwrap();
+
+        let names: Vec<_> = builder.names().collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(names, sorted);
+        assert_eq!(
+            names,
+            ["assets/a/one.json", "assets/b/two.json", "pack.mcmeta"]
+        );
+    }
+
+    #[test]
+    fn test_add_path_outside_root() {
+        let dir = tempfile::tempdir().unwrap();
+        write_tree(dir.path());
+
+        // ルートを指定しない場合、絶対パスは追加できない
+        let mut builder = ArchiveBuilder::new();
+        assert!(builder.add_path(&dir.path().join("pack.mcmeta")).is_err());
+
+        let mut builder = ArchiveBuilder::with_root(dir.path().join("assets"));
+        assert!(builder.add_path(&dir.path().join("pack.mcmeta")).is_err());
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn test_output_is_reproducible() {
+        let dir = tempfile::tempdir().unwrap();
+        write_tree(dir.path());
+
+        let first = dir.path().join("first.zip");
+        let second = dir.path().join("second.zip");
+
+        let mut builder = ArchiveBuilder::with_root(dir.path());
+        builder.add_path(&dir.path().join("assets")).unwrap();
+        builder.write(&first).unwrap();
+
+        // 更新日時を変えても同じ内容になることを確認
+        let modified =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+        for path in ["assets/a/one.json", "assets/b/two.json"] {
+            let file = File::options()
+                .write(true)
+                .open(dir.path().join(path))
+                .unwrap();
+            file.set_modified(modified).unwrap();
+        }
+        assert_eq!(
+            std::fs::metadata(dir.path().join("assets/a/one.json"))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            modified
+        );
+
+        let mut builder = ArchiveBuilder::with_root(dir.path());
+        builder.add_path(&dir.path().join("assets")).unwrap();
+        builder.write(&second).unwrap();
+
+        assert_eq!(
+            std::fs::read(&first).unwrap(),
+            std::fs::read(&second).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_replace_and_remove_path() {
+        let dir = tempfile::tempdir().unwrap();
+        write_tree(dir.path());
+
+        let mut builder = ArchiveBuilder::with_root(dir.path());
+        builder.add_path(&dir.path().join("assets")).unwrap();
+        builder
+            .replace_path(&dir.path().join("assets/a/one.json"), b"{}".to_vec())
+            .unwrap();
+        builder
+            .replace_path(&dir.path().join("assets/missing.json"), b"{}".to_vec())
+            .unwrap();
+        builder
+            .remove_path(&dir.path().join("assets/b/two.json"))
+            .unwrap();
+        assert_eq!(builder.len(), 1);
+
+        let output = dir.path().join("out.zip");
+        builder.write(&output).unwrap();
+
+        // 元のファイルは変更しない
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("assets/a/one.json")).unwrap(),
+            "{\"a\":1}"
+        );
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        assert_eq!(archive.len(), 1);
+        let mut contents = String::new();
+        std::io::Read::read_to_string(&mut archive.by_index(0).unwrap(), &mut contents).unwrap();
+        assert_eq!(contents, "{}");
+    }
+
+    #[test]
+    fn test_add_missing_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut builder = ArchiveBuilder::new();
+        assert!(builder.add_path(&dir.path().join("missing")).is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! 同一テクスチャの重複排除
+//!
+//! `assets/minecraft/textures/item` 配下のテクスチャから、バイト単位または
+//! ピクセル単位で同一の画像を見つけ、モデルの `textures` の参照を
+//! 1つの共有テクスチャにまとめます。
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    paths::Paths,
+    utils::{
+        archive::ArchiveBuilder,
+        hash::file_sha1,
+        json::{read_json, write_json},
+        models::list_model_files,
+    },
+};
+
+/// 一致の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// ファイルの内容が完全に同じ
+    Bytes,
+    /// ファイルは異なるが、サイズと全ピクセルが同じ
+    Pixels,
+}
+
+impl MatchKind {
+    /// 表示名
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Bytes => "バイト一致",
+            Self::Pixels => "ピクセル一致",
+        }
+    }
+}
+
+/// 同一テクスチャのグループ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// 残すテクスチャ（最もファイルサイズが小さいもの）
+    pub kept: PathBuf,
+    /// 削除するテクスチャ
+    pub removed: Vec<PathBuf>,
+    /// 一致の種類
+    pub kind: MatchKind,
+    /// 削除により減るバイト数
+    pub saved_bytes: u64,
+}
+
+/// 参照を書き換えるモデル
+#[derive(Debug, Clone)]
+pub struct ModelRewrite {
+    /// モデルファイルのパス
+    pub path: PathBuf,
+    /// 書き換え後のモデル
+    pub model: Value,
+    /// 書き換えた参照の数
+    pub count: usize,
+}
+
+/// 重複排除の計画
+#[derive(Debug, Default)]
+pub struct DedupePlan {
+    /// 同一テクスチャのグループ
+    pub groups: Vec<DuplicateGroup>,
+    /// 参照を書き換えるモデル
+    pub rewrites: Vec<ModelRewrite>,
+}
+
+impl DedupePlan {
+    /// リソースパック全体を走査して計画を作成
+    ///
+    /// アニメーションテクスチャ（`.mcmeta` を持つもの）は対象外です。
+    ///
+    /// # Errors
+    ///
+    /// テクスチャやモデルの読み込みに失敗した場合
+    pub fn scan() -> anyhow::Result<Self> {
+        let mut textures = Vec::new();
+        collect_textures(&Paths::textures_dir(), &mut textures)?;
+        let groups = find_duplicates(&textures)?;
+
+        let mut replacements = HashMap::new();
+        for group in &groups {
+            let reference = texture_reference(&group.kept).with_context(|| {
+                format!(
+                    "テクスチャの参照名を求められません: {}",
+                    group.kept.display()
+                )
+            })?;
+            for removed in &group.removed {
+                replacements.insert(removed.clone(), reference.clone());
+            }
+        }
+
+        let mut rewrites = Vec::new();
+        for model_file in list_model_files()? {
+            let mut model = read_json::<Value>(&model_file.path).with_context(|| {
+                format!(
+                    "モデルファイルの読み込みに失敗: {}",
+                    model_file.path.display()
+                )
+            })?;
+            let count = rewrite_references(&mut model, &replacements);
+            if count > 0 {
+                rewrites.push(ModelRewrite {
+                    path: model_file.path,
+                    model,
+                    count,
+                });
+            }
+        }
+
+        Ok(Self { groups, rewrites })
+    }
+
+    /// 重複が見つからなかったか
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// 削除されるテクスチャの合計サイズ
+    pub fn saved_bytes(&self) -> u64 {
+        self.groups.iter().map(|group| group.saved_bytes).sum()
+    }
+
+    /// リポジトリのファイルに反映する
+    ///
+    /// モデルを書き換えてから重複したテクスチャを削除し、
+    /// 空になったテクスチャディレクトリも削除します。
+    ///
+    /// # Errors
+    ///
+    /// ファイルの書き込みまたは削除に失敗した場合
+    pub fn apply(&self) -> anyhow::Result<()> {
+        for rewrite in &self.rewrites {
+            write_json(&rewrite.path, &rewrite.model).with_context(|| {
+                format!("モデルファイルの書き込みに失敗: {}", rewrite.path.display())
+            })?;
+        }
+
+        let textures_dir = Paths::textures_dir();
+        for path in self.groups.iter().flat_map(|group| &group.removed) {
+            std::fs::remove_file(path)
+                .with_context(|| format!("テクスチャの削除に失敗: {}", path.display()))?;
+            if let Some(parent) = path.parent()
+                && parent != textures_dir
+                && std::fs::read_dir(parent).is_ok_and(|mut entries| entries.next().is_none())
+            {
+                std::fs::remove_dir(parent)
+                    .with_context(|| format!("ディレクトリの削除に失敗: {}", parent.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Zipアーカイブのエントリだけに反映する（リポジトリは変更しない）
+    ///
+    /// # Errors
+    ///
+    /// モデルのシリアライズに失敗した場合
+    pub fn apply_to_archive(&self, builder: &mut ArchiveBuilder) -> anyhow::Result<()> {
+        for rewrite in &self.rewrites {
+            let json = serde_json::to_string_pretty(&rewrite.model)
+                .context("JSONのシリアライズに失敗しました")?;
+            builder.replace_path(&rewrite.path, json.into_bytes())?;
+        }
+        for path in self.groups.iter().flat_map(|group| &group.removed) {
+            builder.remove_path(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// ディレクトリ配下の静止画テクスチャ（PNG）を再帰的に列挙
+fn collect_textures(dir: &Path, textures: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("ディレクトリの読み込みに失敗: {}", dir.display()))?
+    {
+        let path = entry
+            .with_context(|| format!("ディレクトリエントリの取得に失敗: {}", dir.display()))?
+            .path();
+        if path.is_dir() {
+            collect_textures(&path, textures)?;
+        } else if path.extension().is_some_and(|ext| ext == "png")
+            && !path.with_extension("png.mcmeta").exists()
+        {
+            textures.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 同一のテクスチャをグループにまとめる
+///
+/// 画像として読み込めないファイルは警告を出して対象外にします。
+///
+/// # Errors
+///
+/// ファイルの読み込みに失敗した場合
+pub fn find_duplicates(textures: &[PathBuf]) -> anyhow::Result<Vec<DuplicateGroup>> {
+    struct Candidate {
+        path: PathBuf,
+        size: u64,
+        sha1: String,
+    }
+
+    let mut by_pixels: BTreeMap<(u32, u32, Vec<u8>), Vec<Candidate>> = BTreeMap::new();
+    for path in textures {
+        let image = match image::open(path) {
+            Ok(image) => image.to_rgba8(),
+            Err(e) => {
+                eprintln!(
+                    "  ⚠️  画像を読み込めないためスキップ: {} ({e})",
+                    path.display()
+                );
+                continue;
+            }
+        };
+        let size = std::fs::metadata(path)
+            .with_context(|| format!("ファイル情報の取得に失敗: {}", path.display()))?
+            .len();
+        let key = (
+            image.width(),
+            image.height(),
+            Sha1::digest(image.as_raw()).to_vec(),
+        );
+        by_pixels.entry(key).or_default().push(Candidate {
+            path: path.clone(),
+            size,
+            sha1: file_sha1(path)?,
+        });
+    }
+
+    let mut groups = by_pixels
+        .into_values()
+        .filter(|candidates| candidates.len() > 1)
+        .map(|mut candidates| {
+            candidates.sort_by(|a, b| a.size.cmp(&b.size).then_with(|| a.path.cmp(&b.path)));
+            let kept = candidates.remove(0);
+            let kind = if candidates.iter().all(|c| c.sha1 == kept.sha1) {
+                MatchKind::Bytes
+            } else {
+                MatchKind::Pixels
+            };
+            DuplicateGroup {
+                saved_bytes: candidates.iter().map(|c| c.size).sum(),
+                removed: candidates.into_iter().map(|c| c.path).collect(),
+                kept: kept.path,
+                kind,
+            }
+        })
+        .collect::<Vec<_>>();
+    groups.sort_by(|a, b| a.kept.cmp(&b.kept));
+
+    Ok(groups)
+}
+
+/// テクスチャのパスから参照名（`item/<名前>`）を求める
+fn texture_reference(path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(Paths::textures_dir()).ok()?;
+    let relative = relative.with_extension("");
+    let parts = relative
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("item/{}", parts.join("/")))
+}
+
+/// モデルの `textures` のうち、削除するテクスチャへの参照を書き換える
+///
+/// `minecraft:` 名前空間の有無は維持します。
+///
+/// # Returns
+///
+/// 書き換えた参照の数
+fn rewrite_references(model: &mut Value, replacements: &HashMap<PathBuf, String>) -> usize {
+    let Some(Value::Object(textures)) = model.get_mut("textures") else {
+        return 0;
+    };
+
+    let mut count = 0;
+    for value in textures.values_mut() {
+        let Value::String(reference) = value else {
+            continue;
+        };
+        let Some(replacement) =
+            Paths::texture_reference_path(reference).and_then(|path| replacements.get(&path))
+        else {
+            continue;
+        };
+        *reference = if reference.starts_with("minecraft:") {
+            format!("minecraft:{replacement}")
+        } else {
+            replacement.clone()
+        };
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    fn write_png(path: &Path, color: [u8; 4], compression: image::codecs::png::CompressionType) {
+        let image = RgbaImage::from_pixel(4, 4, Rgba(color));
+        let file = std::fs::File::create(path).unwrap();
+        let encoder = image::codecs::png::PngEncoder::new_with_quality(
+            file,
+            compression,
+            image::codecs::png::FilterType::NoFilter,
+        );
+        image.write_with_encoder(encoder).unwrap();
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        use image::codecs::png::CompressionType;
+
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.png");
+        let b = dir.path().join("b.png");
+        let c = dir.path().join("c.png");
+        let d = dir.path().join("d.png");
+        write_png(&a, [255, 0, 0, 255], CompressionType::Best);
+        std::fs::copy(&a, &b).unwrap();
+        write_png(&c, [0, 255, 0, 255], CompressionType::Best);
+        // 同じピクセルで圧縮率だけ異なる
+        write_png(&d, [0, 255, 0, 255], CompressionType::Fast);
+
+        let groups = find_duplicates(&[a.clone(), b.clone(), c.clone(), d.clone()]).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        assert_eq!(groups[0].kept, a);
+        assert_eq!(groups[0].removed, vec![b.clone()]);
+        assert_eq!(groups[0].kind, MatchKind::Bytes);
+        assert_eq!(groups[0].saved_bytes, std::fs::metadata(&b).unwrap().len());
+
+        let green = &groups[1];
+        assert_eq!(green.kind, MatchKind::Pixels);
+        assert_eq!(green.removed.len(), 1);
+        // 小さいファイルを残す
+        assert!(
+            std::fs::metadata(&green.kept).unwrap().len()
+                <= std::fs::metadata(&green.removed[0]).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_find_duplicates_skips_invalid_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.png");
+        let b = dir.path().join("b.png");
+        std::fs::write(&a, b"not a png").unwrap();
+        std::fs::write(&b, b"not a png").unwrap();
+
+        assert!(find_duplicates(&[a, b]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_texture_reference() {
+        let path = Paths::textures_dir().join("my_model").join("blade.png");
+        assert_eq!(
+            texture_reference(&path).as_deref(),
+            Some("item/my_model/blade")
+        );
+        assert_eq!(texture_reference(Path::new("other/a.png")), None);
+    }
+
+    #[test]
+    fn test_rewrite_references() {
+        let mut model = serde_json::json!({
+            "parent": "minecraft:item/generated",
+            "textures": {
+                "layer0": "item/copy",
+                "particle": "minecraft:item/copy",
+                "other": "item/unique"
+            }
+        });
+        let replacements = HashMap::from([(
+            Paths::texture_reference_path("item/copy").unwrap(),
+            "item/original".to_string(),
+        )]);
+
+        assert_eq!(rewrite_references(&mut model, &replacements), 2);
+        assert_eq!(model["textures"]["layer0"], "item/original");
+        assert_eq!(model["textures"]["particle"], "minecraft:item/original");
+        assert_eq!(model["textures"]["other"], "item/unique");
+    }
+}
@@ -0,0 +1,13 @@
+//! 表示用のフォーマット
+
+/// ファイルサイズを読みやすい単位で表示
+#[must_use]
+pub fn format_size(size: u64) -> String {
+    if size > 1024 * 1024 {
+        format!("{:.2} MB", size as f64 / (1024.0 * 1024.0))
+    } else if size > 1024 {
+        format!("{:.2} KB", size as f64 / 1024.0)
+    } else {
+        format!("{} bytes", size)
+    }
+}
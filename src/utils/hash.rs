@@ -0,0 +1,104 @@
+//! ファイルハッシュの計算
+//!
+//! リソースパックのSHA1/SHA256をファイルを逐次読み込みながら計算します。
+//! `server.properties` の `resource-pack-sha1` に設定する値もここで計算します。
+
+use std::{fs::File, io::Read, path::Path};
+
+use anyhow::Context;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+/// 読み込みバッファのサイズ
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// ファイルのハッシュ値（16進小文字）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDigests {
+    /// SHA1ハッシュ
+    pub sha1: String,
+    /// SHA256ハッシュ（要求された場合のみ）
+    pub sha256: Option<String>,
+}
+
+/// ファイルのSHA1（と必要ならSHA256）を計算
+///
+/// ファイル全体をメモリに読み込まず、一定サイズずつ読み込んで計算します。
+///
+/// # Errors
+///
+/// ファイルのオープンまたは読み込みに失敗した場合
+pub fn file_digests(path: &Path, with_sha256: bool) -> anyhow::Result<FileDigests> {
+    let mut file = File::open(path)
+        .with_context(|| format!("ファイルのオープンに失敗: {}", path.display()))?;
+
+    let mut sha1 = Sha1::new();
+    let mut sha256 = with_sha256.then(Sha256::new);
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .with_context(|| format!("ファイルの読み込みに失敗: {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        sha1.update(&buffer[..read]);
+        if let Some(sha256) = sha256.as_mut() {
+            sha256.update(&buffer[..read]);
+        }
+    }
+
+    Ok(FileDigests {
+        sha1: to_hex(&sha1.finalize()),
+        sha256: sha256.map(|sha256| to_hex(&sha256.finalize())),
+    })
+}
+
+/// ファイルのSHA1を計算
+///
+/// # Errors
+///
+/// ファイルのオープンまたは読み込みに失敗した場合
+pub fn file_sha1(path: &Path) -> anyhow::Result<String> {
+    file_digests(path, false).map(|digests| digests.sha1)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_digests_known_values() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "abc").unwrap();
+
+        let digests = file_digests(file.path(), true).unwrap();
+        assert_eq!(digests.sha1, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            digests.sha256.as_deref(),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+    }
+
+    #[test]
+    fn test_file_sha1_spans_multiple_buffers() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let data = vec![b'a'; BUFFER_SIZE * 2 + 7];
+        std::fs::write(file.path(), &data).unwrap();
+
+        let expected = to_hex(&Sha1::digest(&data));
+        assert_eq!(file_sha1(file.path()).unwrap(), expected);
+        assert!(file_digests(file.path(), false).unwrap().sha256.is_none());
+    }
+
+    #[test]
+    fn test_file_digests_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(file_digests(&dir.path().join("missing.zip"), false).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! アイテム定義ファイルの列挙
+//!
+//! `assets/minecraft/items` 配下のアイテム定義ファイルを
+//! マテリアル名とともに列挙します。
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::paths::Paths;
+
+/// アイテム定義ファイル
+#[derive(Debug, Clone)]
+pub struct ItemFile {
+    /// マテリアル名（ファイル名から拡張子を除いたもの）
+    pub material: String,
+    /// ファイルパス
+    pub path: PathBuf,
+}
+
+/// すべてのアイテム定義ファイルをマテリアル名順に列挙
+///
+/// # Errors
+///
+/// アイテムディレクトリの読み込みに失敗した場合
+pub fn list_item_files() -> anyhow::Result<Vec<ItemFile>> {
+    let items_dir = Paths::items_dir();
+    let mut files = std::fs::read_dir(&items_dir)
+        .with_context(|| {
+            format!(
+                "アイテムディレクトリの読み込みに失敗: {}",
+                items_dir.display()
+            )
+        })?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|path| {
+            let material = path.file_stem()?.to_str()?.to_string();
+            Some(ItemFile { material, path })
+        })
+        .collect::<Vec<_>>();
+    files.sort_by(|a, b| a.material.cmp(&b.material));
+    Ok(files)
+}
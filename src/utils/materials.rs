@@ -3,7 +3,10 @@
 //! `items_textures.json` からマテリアルとテクスチャのマッピングを
 //! 読み込み、管理します。
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 use anyhow::Context;
 use serde::Deserialize;
@@ -25,6 +28,8 @@ pub struct ItemTextureEntry {
 #[derive(Debug, Default, Clone)]
 pub struct MaterialMapping {
     by_material: HashMap<String, String>,
+    /// バニラのテクスチャ（`minecraft:` なし、`item/...` または `block/...`）
+    vanilla_textures: HashSet<String>,
 }
 
 impl MaterialMapping {
@@ -39,6 +44,10 @@ impl MaterialMapping {
             .with_context(|| format!("items_textures.json の読み込みに失敗: {}", path.display()))?;
         let entries: Vec<ItemTextureEntry> =
             serde_json::from_str(&data).context("items_textures.json のパースに失敗")?;
+        let vanilla_textures = entries
+            .iter()
+            .map(|e| normalize_texture(&e.texture))
+            .collect();
         let by_material = entries
             .into_iter()
             .filter_map(|e| {
@@ -59,7 +68,10 @@ impl MaterialMapping {
                 Some((e.name, texture))
             })
             .collect();
-        Ok(Self { by_material })
+        Ok(Self {
+            by_material,
+            vanilla_textures,
+        })
     }
 
     /// マテリアルが存在するか確認
@@ -67,6 +79,24 @@ impl MaterialMapping {
         self.by_material.contains_key(material)
     }
 
+    /// バニラのフォールバックモデルへの参照か確認
+    ///
+    /// `minecraft:` 名前空間の有無は区別しません。
+    pub fn is_fallback_model(&self, reference: &str) -> bool {
+        let reference = reference.trim_start_matches("minecraft:");
+        self.by_material
+            .values()
+            .any(|model| model.trim_start_matches("minecraft:") == reference)
+    }
+
+    /// バニラのテクスチャへの参照か確認
+    ///
+    /// `minecraft:` 名前空間の有無と、旧形式のディレクトリ名（`items/`, `blocks/`）は区別しません。
+    pub fn is_vanilla_texture(&self, reference: &str) -> bool {
+        self.vanilla_textures
+            .contains(&normalize_texture(reference))
+    }
+
     /// マテリアルのフォールバックモデルパスを取得
     ///
     /// # Errors
@@ -83,6 +113,18 @@ impl MaterialMapping {
     }
 }
 
+/// テクスチャ参照を `minecraft:` なし、`item/` または `block/` 始まりの形式に揃える
+fn normalize_texture(reference: &str) -> String {
+    let texture = reference.trim_start_matches("minecraft:");
+    if let Some(rest) = texture.strip_prefix("items/") {
+        format!("item/{rest}")
+    } else if let Some(rest) = texture.strip_prefix("blocks/") {
+        format!("block/{rest}")
+    } else {
+        texture.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +154,35 @@ mod tests {
         assert_eq!(result.unwrap(), "minecraft:item/diamond_sword");
     }
 
+    #[test]
+    fn test_material_mapping_is_fallback_model() {
+        let mut mapping = MaterialMapping::default();
+        mapping.by_material.insert(
+            "diamond_sword".to_string(),
+            "minecraft:item/diamond_sword".to_string(),
+        );
+
+        assert!(mapping.is_fallback_model("minecraft:item/diamond_sword"));
+        assert!(mapping.is_fallback_model("item/diamond_sword"));
+        assert!(!mapping.is_fallback_model("item/my_sword"));
+    }
+
+    #[test]
+    fn test_material_mapping_is_vanilla_texture() {
+        let mut mapping = MaterialMapping::default();
+        mapping
+            .vanilla_textures
+            .insert(normalize_texture("items/bow"));
+        mapping
+            .vanilla_textures
+            .insert(normalize_texture("minecraft:item/diamond_sword"));
+
+        assert!(mapping.is_vanilla_texture("minecraft:item/bow"));
+        assert!(mapping.is_vanilla_texture("item/diamond_sword"));
+        assert!(mapping.is_vanilla_texture("minecraft:items/diamond_sword"));
+        assert!(!mapping.is_vanilla_texture("item/my_sword"));
+    }
+
     #[test]
     fn test_material_mapping_resolve_not_found() {
         let mapping = MaterialMapping::default();
@@ -1,3 +1,10 @@
 pub mod add;
+pub mod archive;
+pub mod dedupe;
+pub mod format;
+pub mod hash;
+pub mod items;
 pub mod json;
 pub mod materials;
+pub mod models;
+pub mod optimize;
@@ -0,0 +1,123 @@
+//! モデルファイルの列挙
+//!
+//! `assets/minecraft/models/item` 配下のモデルファイルを
+//! カスタムモデルデータ名とともに列挙します。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::{paths::Paths, schema::models::ItemModel, utils::json::read_json};
+
+/// モデルファイル
+#[derive(Debug, Clone)]
+pub struct ModelFile {
+    /// カスタムモデルデータ名（ファイル名から拡張子を除いたもの）
+    ///
+    /// 状態ごとのモデルは `<名前>/<状態>` になります。
+    pub name: String,
+    /// ファイルパス
+    pub path: PathBuf,
+}
+
+/// すべてのモデルファイルを名前順に列挙
+///
+/// カスタムモデルデータ名のディレクトリ内にある状態ごとのモデルも含みます。
+///
+/// # Errors
+///
+/// モデルディレクトリの読み込みに失敗した場合
+pub fn list_model_files() -> anyhow::Result<Vec<ModelFile>> {
+    let models_dir = Paths::models_dir();
+    let mut files = Vec::new();
+    for path in read_dir_paths(&models_dir)? {
+        if path.is_dir() {
+            let Some(dir_name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let dir_name = dir_name.to_string();
+            files.extend(
+                read_dir_paths(&path)?
+                    .into_iter()
+                    .filter_map(|path| json_model_file(path, Some(&dir_name))),
+            );
+        } else {
+            files.extend(json_model_file(path, None));
+        }
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// モデルが表示に使う2Dテクスチャ
+///
+/// 通常は `item/<名前>.png` ですが、`dedupe` で共有テクスチャにまとめた場合は
+/// モデルの `layer0` が参照している画像を返します。
+///
+/// # Errors
+///
+/// モデルファイルの読み込みに失敗した場合
+pub fn model_texture_path(custom_model_data: &str) -> anyhow::Result<Option<PathBuf>> {
+    let texture_path = Paths::texture_path(custom_model_data);
+    if texture_path.is_file() {
+        return Ok(Some(texture_path));
+    }
+
+    let model_path = Paths::model_path(custom_model_data);
+    if !model_path.is_file() {
+        return Ok(None);
+    }
+    let model = read_json::<ItemModel>(&model_path)
+        .with_context(|| format!("モデルファイルの読み込みに失敗: {}", model_path.display()))?;
+    Ok(model
+        .textures
+        .resolve("layer0")
+        .and_then(Paths::texture_reference_path)
+        .filter(|path| path.is_file()))
+}
+
+/// 指定したテクスチャのいずれかを参照しているモデルの名前
+///
+/// # Errors
+///
+/// モデルファイルの読み込みに失敗した場合
+pub fn models_referencing(textures: &[PathBuf]) -> anyhow::Result<Vec<String>> {
+    let mut names = Vec::new();
+    for model_file in list_model_files()? {
+        let model = read_json::<ItemModel>(&model_file.path).with_context(|| {
+            format!(
+                "モデルファイルの読み込みに失敗: {}",
+                model_file.path.display()
+            )
+        })?;
+        let references = model
+            .textures
+            .iter()
+            .filter_map(|(_, value)| Paths::texture_reference_path(value))
+            .any(|path| textures.contains(&path));
+        if references {
+            names.push(model_file.name);
+        }
+    }
+    Ok(names)
+}
+
+fn read_dir_paths(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    Ok(std::fs::read_dir(dir)
+        .with_context(|| format!("モデルディレクトリの読み込みに失敗: {}", dir.display()))?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .collect())
+}
+
+fn json_model_file(path: PathBuf, dir_name: Option<&str>) -> Option<ModelFile> {
+    if path.extension().and_then(|s| s.to_str()) != Some("json") {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let name = match dir_name {
+        Some(dir_name) => Paths::state_name(dir_name, stem),
+        None => stem.to_string(),
+    };
+    Some(ModelFile { name, path })
+}
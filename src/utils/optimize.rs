@@ -0,0 +1,324 @@
+//! PNGの可逆最適化
+//!
+//! テクスチャを可逆に再エンコードしてファイルサイズを削減します。
+//! 再エンコード時に補助チャンク（テキスト・ガンマ・Exif など）は書き出さず、
+//! 色数が256以下ならインデックスカラー、無彩色ならグレースケール、
+//! 不透明ならアルファなしを候補にして、最も小さいものを選びます。
+//! 置き換える前に、元の画像とすべてのピクセルが一致することを確認します。
+
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    path::Path,
+};
+
+use anyhow::Context;
+use image::RgbaImage;
+use png::{BitDepth, ColorType, Compression, Filter};
+
+/// 最適化の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Optimized {
+    /// 最適化後のPNG
+    pub data: Vec<u8>,
+    /// 元のサイズ
+    pub original_size: u64,
+}
+
+impl Optimized {
+    /// 削減されたバイト数
+    pub fn saved_bytes(&self) -> u64 {
+        self.original_size - self.data.len() as u64
+    }
+}
+
+/// ファイルのPNGを最適化
+///
+/// # Returns
+///
+/// 元より小さくなった場合のみ `Some`
+///
+/// # Errors
+///
+/// ファイルの読み込み、またはPNGのデコード・エンコードに失敗した場合
+pub fn optimize_file(path: &Path) -> anyhow::Result<Option<Optimized>> {
+    let data = std::fs::read(path)
+        .with_context(|| format!("ファイルの読み込みに失敗: {}", path.display()))?;
+    optimize_png(&data).with_context(|| format!("PNGの最適化に失敗: {}", path.display()))
+}
+
+/// PNGを可逆に最適化
+///
+/// 16bit の画像は、8bit に落としても値が変わらない場合のみ最適化します。
+///
+/// # Returns
+///
+/// 元より小さく、すべてのピクセルが一致する場合のみ `Some`
+///
+/// # Errors
+///
+/// PNGのデコードまたはエンコードに失敗した場合
+pub fn optimize_png(data: &[u8]) -> anyhow::Result<Option<Optimized>> {
+    let original = image::load_from_memory_with_format(data, image::ImageFormat::Png)
+        .context("PNGのデコードに失敗")?;
+    let pixels = original.to_rgba8();
+
+    let mut best: Option<Vec<u8>> = None;
+    for candidate in candidates(&pixels) {
+        for filter in [Filter::Adaptive, Filter::NoFilter] {
+            let encoded = candidate.encode(pixels.width(), pixels.height(), filter)?;
+            if best.as_ref().is_none_or(|best| encoded.len() < best.len()) {
+                best = Some(encoded);
+            }
+        }
+    }
+    let Some(best) = best.filter(|best| best.len() < data.len()) else {
+        return Ok(None);
+    };
+
+    // 16bit の精度も含めて、元の画像と完全に一致するか確認する
+    let decoded = image::load_from_memory_with_format(&best, image::ImageFormat::Png)
+        .context("最適化したPNGのデコードに失敗")?;
+    if decoded.to_rgba16() != original.to_rgba16() {
+        return Ok(None);
+    }
+
+    Ok(Some(Optimized {
+        data: best,
+        original_size: data.len() as u64,
+    }))
+}
+
+/// エンコードの候補
+struct Candidate {
+    color: ColorType,
+    depth: BitDepth,
+    palette: Option<(Vec<u8>, Vec<u8>)>,
+    data: Vec<u8>,
+}
+
+impl Candidate {
+    fn encode(&self, width: u32, height: u32, filter: Filter) -> anyhow::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(self.color);
+        encoder.set_depth(self.depth);
+        encoder.set_compression(Compression::High);
+        encoder.set_filter(filter);
+        if let Some((palette, trns)) = &self.palette {
+            encoder.set_palette(palette.as_slice());
+            if !trns.is_empty() {
+                encoder.set_trns(trns.as_slice());
+            }
+        }
+        let mut writer = encoder
+            .write_header()
+            .context("PNGヘッダーの書き込みに失敗")?;
+        writer
+            .write_image_data(&self.data)
+            .context("PNG画像データの書き込みに失敗")?;
+        writer.finish().context("PNGの書き込みに失敗")?;
+        Ok(out)
+    }
+}
+
+/// 画像の内容から可逆なエンコードの候補を列挙
+fn candidates(image: &RgbaImage) -> Vec<Candidate> {
+    let opaque = image.pixels().all(|p| p[3] == 255);
+    let gray = image.pixels().all(|p| p[0] == p[1] && p[1] == p[2]);
+
+    let mut candidates = Vec::new();
+    candidates.extend(indexed(image));
+
+    let (color, data) = match (gray, opaque) {
+        (true, true) => (
+            ColorType::Grayscale,
+            image.pixels().map(|p| p[0]).collect::<Vec<_>>(),
+        ),
+        (true, false) => (
+            ColorType::GrayscaleAlpha,
+            image.pixels().flat_map(|p| [p[0], p[3]]).collect(),
+        ),
+        (false, true) => (
+            ColorType::Rgb,
+            image.pixels().flat_map(|p| [p[0], p[1], p[2]]).collect(),
+        ),
+        (false, false) => (ColorType::Rgba, image.as_raw().clone()),
+    };
+    candidates.push(Candidate {
+        color,
+        depth: BitDepth::Eight,
+        palette: None,
+        data,
+    });
+
+    candidates
+}
+
+/// 色数が256以下の場合のインデックスカラーの候補
+///
+/// 透明度を持つ色をパレットの先頭に並べ、`tRNS` を短くします。
+fn indexed(image: &RgbaImage) -> Option<Candidate> {
+    let mut colors = Vec::new();
+    let mut seen = HashMap::new();
+    for pixel in image.pixels() {
+        if let Entry::Vacant(entry) = seen.entry(pixel.0) {
+            if colors.len() == 256 {
+                return None;
+            }
+            entry.insert(0u8);
+            colors.push(pixel.0);
+        }
+    }
+    colors.sort_by_key(|color| (color[3] == 255, *color));
+    for (index, color) in colors.iter().enumerate() {
+        seen.insert(*color, index as u8);
+    }
+
+    let palette = colors.iter().flat_map(|c| [c[0], c[1], c[2]]).collect();
+    let trns = colors
+        .iter()
+        .take_while(|c| c[3] != 255)
+        .map(|c| c[3])
+        .collect();
+
+    let bits: u8 = match colors.len() {
+        0..=2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        _ => 8,
+    };
+    let depth = match bits {
+        1 => BitDepth::One,
+        2 => BitDepth::Two,
+        4 => BitDepth::Four,
+        _ => BitDepth::Eight,
+    };
+
+    // 1行ごとに、左のピクセルから上位ビットに詰める
+    let width = image.width() as usize;
+    let per_byte = usize::from(8 / bits);
+    let mut data = Vec::with_capacity(width.div_ceil(per_byte) * image.height() as usize);
+    for row in image.rows() {
+        let indices = row.map(|pixel| seen[&pixel.0]).collect::<Vec<_>>();
+        for chunk in indices.chunks(per_byte) {
+            let mut byte = 0u8;
+            for (i, index) in chunk.iter().enumerate() {
+                byte |= index << (8 - bits * (i as u8 + 1));
+            }
+            data.push(byte);
+        }
+    }
+
+    Some(Candidate {
+        color: ColorType::Indexed,
+        depth,
+        palette: Some((palette, trns)),
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{ImageBuffer, ImageEncoder, Rgba, codecs::png::PngEncoder};
+
+    use super::*;
+
+    fn encode_rgba(image: &RgbaImage) -> Vec<u8> {
+        let mut out = Vec::new();
+        PngEncoder::new_with_quality(
+            &mut out,
+            image::codecs::png::CompressionType::Fast,
+            image::codecs::png::FilterType::NoFilter,
+        )
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            image::ExtendedColorType::Rgba8,
+        )
+        .unwrap();
+        out
+    }
+
+    fn decode(data: &[u8]) -> RgbaImage {
+        image::load_from_memory(data).unwrap().to_rgba8()
+    }
+
+    #[test]
+    fn test_optimize_to_indexed() {
+        // 規則的すぎると RGBA のままでも十分に圧縮されるため、疑似乱数で色を選ぶ
+        let image = RgbaImage::from_fn(64, 64, |x, y| {
+            match (x * 31 + y * 17).wrapping_mul(2_654_435_761) >> 30 {
+                0 => Rgba([255, 0, 0, 255]),
+                1 => Rgba([0, 0, 255, 128]),
+                _ => Rgba([0, 0, 0, 0]),
+            }
+        });
+        let data = encode_rgba(&image);
+
+        let optimized = optimize_png(&data).unwrap().unwrap();
+        assert!(optimized.data.len() < data.len());
+        assert_eq!(optimized.original_size, data.len() as u64);
+        assert_eq!(decode(&optimized.data), image);
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(&optimized.data));
+        let reader = decoder.read_info().unwrap();
+        assert_eq!(reader.info().color_type, ColorType::Indexed);
+        assert_eq!(reader.info().bit_depth, BitDepth::Two);
+    }
+
+    #[test]
+    fn test_optimize_many_colors() {
+        let image = RgbaImage::from_fn(32, 32, |x, y| {
+            Rgba([(x * 8) as u8, (y * 8) as u8, ((x + y) * 4) as u8, 255])
+        });
+        let data = encode_rgba(&image);
+
+        let optimized = optimize_png(&data).unwrap().unwrap();
+        assert_eq!(decode(&optimized.data), image);
+    }
+
+    #[test]
+    fn test_optimize_strips_text_chunks() {
+        let image = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, 4, 4);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder
+            .add_text_chunk("Comment".to_string(), "x".repeat(200))
+            .unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(image.as_raw()).unwrap();
+        writer.finish().unwrap();
+
+        let optimized = optimize_png(&data).unwrap().unwrap();
+        let reader = png::Decoder::new(std::io::Cursor::new(&optimized.data))
+            .read_info()
+            .unwrap();
+        assert!(reader.info().uncompressed_latin1_text.is_empty());
+        assert_eq!(decode(&optimized.data), image);
+    }
+
+    #[test]
+    fn test_lossy_16bit_is_not_optimized() {
+        let image: ImageBuffer<Rgba<u16>, Vec<u16>> =
+            ImageBuffer::from_fn(8, 8, |x, y| Rgba([(x * 1000 + y) as u16, 1, 2, 65535]));
+        let mut data = Vec::new();
+        image::DynamicImage::ImageRgba16(image)
+            .write_to(
+                &mut std::io::Cursor::new(&mut data),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+
+        assert!(optimize_png(&data).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_already_small_is_not_optimized() {
+        let image = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 255]));
+        let data = optimize_png(&encode_rgba(&image)).unwrap().unwrap().data;
+        assert!(optimize_png(&data).unwrap().is_none());
+    }
+}
@@ -6,6 +6,22 @@ export interface AnimationData {
   frame_count: number;
   /** フレームタイム（tick数） */
   frametime: number;
+  /** アニメーション1周の合計tick数 */
+  total_duration: number;
+  /** フレーム間を補間するか */
+  interpolate?: boolean;
+  /** 再生順のフレーム */
+  frames?: AnimationFrame[];
+}
+
+/**
+ * アニメーションの各フレーム
+ */
+export interface AnimationFrame {
+  /** フレーム番号 */
+  index: number;
+  /** 表示するtick数 */
+  time: number;
 }
 
 /**
@@ -18,6 +34,8 @@ export interface ModelData {
   materials: string[];
   /** テクスチャパス（3Dモデルの場合は undefined） */
   texture_path?: string;
+  /** プレビュー画像のパス（3Dモデルの表示に使用） */
+  preview_path?: string;
   /** 追加日時（ISO 8601形式） */
   added_date: string;
   /** アニメーション情報（アニメーションテクスチャの場合のみ） */